@@ -14,6 +14,9 @@
 
 #![forbid(unsafe_code)]
 
+mod mnemonic;
+pub use mnemonic::*;
+
 use snarkvm::{
     console::{network::prelude::*, types::Field},
     prelude::*,
@@ -22,6 +25,11 @@ use snarkvm::{
 use colored::*;
 use core::fmt;
 
+/// The domain separator for deriving a child account seed from a master seed.
+const ACCOUNT_CHILD_SEED_DOMAIN: &str = "AleoAccountChildSeed0";
+/// The domain separator for signing an off-chain message.
+const SIGNED_MESSAGE_DOMAIN: &str = "AleoSignedMessage0";
+
 /// A helper struct for an Aleo account.
 #[derive(Clone, Debug)]
 pub struct Account<N: Network> {
@@ -39,6 +47,33 @@ impl<N: Network> Account<N> {
         Self::try_from(PrivateKey::new(rng)?)
     }
 
+    /// Initializes a new account from an account seed.
+    pub fn from_seed(seed: Field<N>) -> Result<Self> {
+        Self::try_from(PrivateKey::try_from(seed)?)
+    }
+
+    /// Derives the child account at the given account and address index from a master seed.
+    ///
+    /// Every child account is hardened, as its private key cannot be recovered from its address
+    /// (or the address of any other child) without the master seed.
+    pub fn derive(master_seed: Field<N>, account_index: u32, address_index: u32) -> Result<Self> {
+        // Construct the domain separator.
+        let domain = Field::new_domain_separator(ACCOUNT_CHILD_SEED_DOMAIN);
+        // Derive the child seed from the master seed and the indices.
+        let input = [domain, master_seed, Field::from_u32(account_index), Field::from_u32(address_index)];
+        Self::from_seed(N::hash_psd4(&input)?)
+    }
+
+    /// Derives the child account at the given account and address index from a mnemonic phrase and passphrase.
+    pub fn from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        account_index: u32,
+        address_index: u32,
+    ) -> Result<Self> {
+        Self::derive(mnemonic.master_seed(passphrase), account_index, address_index)
+    }
+
     /// Returns the account private key.
     pub const fn private_key(&self) -> &PrivateKey<N> {
         &self.private_key
@@ -85,6 +120,32 @@ impl<N: Network> Account<N> {
     pub fn verify_bits(&self, message: &[bool], signature: &Signature<N>) -> bool {
         signature.verify_bits(&self.address, message)
     }
+
+    /// Returns a signature for the given off-chain message, using the account private key.
+    ///
+    /// The message is hashed with a domain separator, so the signature can prove ownership
+    /// of the address without being valid for any transaction or other signed payload.
+    pub fn sign_message<R: Rng + CryptoRng>(&self, message: &[u8], rng: &mut R) -> Result<Signature<N>> {
+        self.sign(&Self::message_digest(message)?, rng)
+    }
+
+    /// Verifies a signature for the given off-chain message, using the given address.
+    pub fn verify_message(address: &Address<N>, message: &[u8], signature: &Signature<N>) -> bool {
+        match Self::message_digest(message) {
+            Ok(digest) => signature.verify(address, &digest),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the domain-separated digest of the given off-chain message.
+    fn message_digest(message: &[u8]) -> Result<[Field<N>; 2]> {
+        // Construct the domain separator.
+        let domain = Field::new_domain_separator(SIGNED_MESSAGE_DOMAIN);
+        // Hash the length-prefixed message, so the digest is unambiguous (and its input non-empty).
+        let mut bits = (message.len() as u64).to_bits_le();
+        bits.extend(message.to_bits_le());
+        Ok([domain, N::hash_bhp1024(&bits)?])
+    }
 }
 
 impl<N: Network> TryFrom<PrivateKey<N>> for Account<N> {
@@ -201,4 +262,47 @@ mod tests {
         let signature = account.sign_bits(&message, &mut rng).unwrap();
         assert!(account.verify_bits(&message, &signature));
     }
+
+    #[test]
+    fn test_sign_message() {
+        // Initialize the RNG.
+        let mut rng = TestRng::default();
+        // Prepare the account and message.
+        let account = Account::<CurrentNetwork>::new(&mut rng).unwrap();
+        let message = b"I own this address".to_vec();
+        // Sign and verify.
+        let signature = account.sign_message(&message, &mut rng).unwrap();
+        assert!(Account::verify_message(&account.address(), &message, &signature));
+        // Ensure the signature round-trips through its string encoding.
+        let signature = Signature::<CurrentNetwork>::from_str(&signature.to_string()).unwrap();
+        assert!(Account::verify_message(&account.address(), &message, &signature));
+        // Ensure the signature does not verify for a different message or address.
+        assert!(!Account::verify_message(&account.address(), b"I own this address!", &signature));
+        let other = Account::<CurrentNetwork>::new(&mut rng).unwrap();
+        assert!(!Account::verify_message(&other.address(), &message, &signature));
+        // Ensure the signature is domain-separated from a signature over the raw bytes.
+        assert!(!account.verify_bytes(&message, &signature));
+        // Ensure an empty message can be signed.
+        let signature = account.sign_message(&[], &mut rng).unwrap();
+        assert!(Account::verify_message(&account.address(), &[], &signature));
+    }
+
+    #[test]
+    fn test_derive() {
+        // Initialize the RNG.
+        let mut rng = TestRng::default();
+        // Sample the master seed.
+        let master_seed = Field::rand(&mut rng);
+        // Ensure the derivation is deterministic.
+        let account = Account::<CurrentNetwork>::derive(master_seed, 0, 0).unwrap();
+        assert_eq!(account.address(), Account::<CurrentNetwork>::derive(master_seed, 0, 0).unwrap().address());
+        // Ensure the derived account differs from the account of the master seed itself.
+        assert_ne!(account.address(), Account::<CurrentNetwork>::from_seed(master_seed).unwrap().address());
+        // Ensure each account and address index derives a distinct account.
+        assert_ne!(account.address(), Account::<CurrentNetwork>::derive(master_seed, 0, 1).unwrap().address());
+        assert_ne!(account.address(), Account::<CurrentNetwork>::derive(master_seed, 1, 0).unwrap().address());
+        // Ensure a different master seed derives a distinct account.
+        let other_seed = Field::rand(&mut rng);
+        assert_ne!(account.address(), Account::<CurrentNetwork>::derive(other_seed, 0, 0).unwrap().address());
+    }
 }
@@ -0,0 +1,112 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::console::{network::prelude::*, types::Field};
+
+use core::fmt;
+
+/// A mnemonic phrase (BIP39), which encodes the master seed of a wallet as a list of words with a checksum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mnemonic(bip39::Mnemonic);
+
+impl Mnemonic {
+    /// The number of words in a new mnemonic phrase, by default.
+    pub const DEFAULT_NUM_WORDS: usize = 24;
+
+    /// Samples a new mnemonic phrase with the given number of words (12, 15, 18, 21, or 24).
+    pub fn new<R: Rng + CryptoRng>(num_words: usize, rng: &mut R) -> Result<Self> {
+        // Ensure the number of words is valid.
+        if !(12..=24).contains(&num_words) || num_words % 3 != 0 {
+            bail!("Invalid number of words ({num_words}), expected 12, 15, 18, 21, or 24");
+        }
+        // Sample the entropy, where every 3 words encode 32 bits of entropy.
+        let entropy = (0..num_words / 3 * 4).map(|_| rng.gen::<u8>()).collect::<Vec<_>>();
+        Ok(Self(bip39::Mnemonic::from_entropy(&entropy)?))
+    }
+
+    /// Returns the number of words in the mnemonic phrase.
+    pub fn num_words(&self) -> usize {
+        self.0.word_count()
+    }
+
+    /// Returns the master seed for the mnemonic phrase and the given (possibly empty) passphrase.
+    pub fn master_seed<N: Network>(&self, passphrase: &str) -> Field<N> {
+        Field::new(<N as Environment>::Field::from_bytes_le_mod_order(&self.0.to_seed(passphrase)))
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = Error;
+
+    /// Parses a mnemonic phrase, ensuring its words and checksum are valid.
+    fn from_str(phrase: &str) -> Result<Self, Self::Err> {
+        Ok(Self(bip39::Mnemonic::parse(phrase).map_err(|e| anyhow!("Invalid mnemonic phrase - {e}"))?))
+    }
+}
+
+impl Display for Mnemonic {
+    /// Renders the mnemonic phrase as a string.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Account;
+    use snarkvm::prelude::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_mnemonic() {
+        // Initialize the RNG.
+        let mut rng = TestRng::default();
+
+        for num_words in [12, 15, 18, 21, 24] {
+            // Sample a mnemonic phrase, and ensure it round-trips through its string representation.
+            let mnemonic = Mnemonic::new(num_words, &mut rng).unwrap();
+            assert_eq!(mnemonic.num_words(), num_words);
+            assert_eq!(mnemonic, Mnemonic::from_str(&mnemonic.to_string()).unwrap());
+        }
+        // Ensure an invalid number of words is rejected.
+        assert!(Mnemonic::new(11, &mut rng).is_err());
+        assert!(Mnemonic::new(27, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_master_seed() {
+        // Initialize the RNG.
+        let mut rng = TestRng::default();
+        let mnemonic = Mnemonic::new(Mnemonic::DEFAULT_NUM_WORDS, &mut rng).unwrap();
+
+        // Ensure the mnemonic phrase recovers the same account.
+        let account = Account::<CurrentNetwork>::from_mnemonic(&mnemonic, "", 0, 0).unwrap();
+        let recovered = Account::<CurrentNetwork>::from_mnemonic(&mnemonic.to_string().parse().unwrap(), "", 0, 0);
+        assert_eq!(account.address(), recovered.unwrap().address());
+        // Ensure the passphrase changes the master seed.
+        assert_ne!(mnemonic.master_seed::<CurrentNetwork>(""), mnemonic.master_seed::<CurrentNetwork>("passphrase"));
+    }
+
+    #[test]
+    fn test_mnemonic_checksum() {
+        // Ensure a phrase with an invalid checksum is rejected.
+        let phrase = ["abandon"; 12].join(" ");
+        assert!(Mnemonic::from_str(&phrase).is_err());
+        // Ensure a phrase with a valid checksum is accepted.
+        let phrase = format!("{} about", ["abandon"; 11].join(" "));
+        assert!(Mnemonic::from_str(&phrase).is_ok());
+    }
+}
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use snarkos_account::Mnemonic;
 use snarkvm::console::{
-    account::PrivateKey,
+    account::{Address, PrivateKey, Signature},
     prelude::{Environment, Uniform},
     types::Field,
 };
@@ -39,20 +40,70 @@ pub enum Account {
         /// Try until an address with the vanity string is found
         #[clap(short = 'v', long)]
         vanity: Option<String>,
+        /// Generate the account from a new mnemonic phrase
+        #[clap(short = 'm', long)]
+        mnemonic: bool,
+    },
+    /// Derives a child Aleo account from a master seed or a mnemonic phrase
+    Derive {
+        /// The master seed, as a numeric value
+        #[clap(short = 's', long)]
+        seed: Option<String>,
+        /// The mnemonic phrase
+        #[clap(short = 'm', long)]
+        mnemonic: Option<String>,
+        /// The passphrase of the mnemonic phrase
+        #[clap(default_value = "", long)]
+        passphrase: String,
+        /// The account index
+        #[clap(default_value_t = 0, long)]
+        account: u32,
+        /// The address index
+        #[clap(default_value_t = 0, long)]
+        index: u32,
+    },
+    /// Signs a message with an Aleo account private key, to prove ownership of its address
+    Sign {
+        /// The private key of the account
+        #[clap(long)]
+        private_key: String,
+        /// The message to sign
+        #[clap(short = 'm', long)]
+        message: String,
+    },
+    /// Verifies the signature of a message for an Aleo address
+    Verify {
+        /// The address of the signer
+        #[clap(short = 'a', long)]
+        address: String,
+        /// The signed message
+        #[clap(short = 'm', long)]
+        message: String,
+        /// The signature of the message
+        #[clap(short = 's', long)]
+        signature: String,
     },
 }
 
 impl Account {
     pub fn parse(self) -> Result<String> {
         match self {
-            Self::New { seed, vanity } => {
+            Self::New { seed, vanity, mnemonic } => {
                 // Ensure only the seed or the vanity string is specified.
                 if seed.is_some() && vanity.is_some() {
                     bail!("Cannot specify both the '--seed' and '--vanity' flags");
                 }
+                // Ensure the mnemonic phrase is not combined with the seed or the vanity string.
+                if mnemonic && (seed.is_some() || vanity.is_some()) {
+                    bail!("Cannot specify the '--mnemonic' flag with the '--seed' or '--vanity' flags");
+                }
 
+                // Generate an account from a new mnemonic phrase.
+                if mnemonic {
+                    Self::new_mnemonic()
+                }
                 // Generate a vanity account.
-                if let Some(vanity) = vanity {
+                else if let Some(vanity) = vanity {
                     Self::new_vanity(&vanity)
                 }
                 // Default to generating a normal account, with an optional seed.
@@ -60,6 +111,34 @@ impl Account {
                     Self::new_seeded(seed)
                 }
             }
+            Self::Derive { seed, mnemonic, passphrase, account, index } => {
+                // Recover the master seed from the seed or the mnemonic phrase.
+                let master_seed = match (seed, mnemonic) {
+                    (Some(seed), None) => Field::new(
+                        <Network as Environment>::Field::from_str(&seed).map_err(|e| anyhow!("Invalid seed - {e}"))?,
+                    ),
+                    (None, Some(mnemonic)) => Mnemonic::from_str(&mnemonic)?.master_seed::<Network>(&passphrase),
+                    _ => bail!("Specify exactly one of the '--seed' and '--mnemonic' flags"),
+                };
+                // Derive the child account.
+                let account = snarkos_account::Account::<Network>::derive(master_seed, account, index)?;
+                // Print the derived Aleo account.
+                Ok(account.to_string())
+            }
+            Self::Sign { private_key, message } => {
+                let account = snarkos_account::Account::<Network>::from_str(&private_key)?;
+                // Sign the message, and print the signature.
+                Ok(account.sign_message(message.as_bytes(), &mut ChaChaRng::from_entropy())?.to_string())
+            }
+            Self::Verify { address, message, signature } => {
+                let address = Address::<Network>::from_str(&address)?;
+                let signature = Signature::<Network>::from_str(&signature)?;
+                // Verify the signature of the message.
+                match snarkos_account::Account::verify_message(&address, message.as_bytes(), &signature) {
+                    true => Ok(format!("✅ The signature is valid for '{address}'")),
+                    false => bail!("The signature is invalid for '{address}'"),
+                }
+            }
         }
     }
 
@@ -121,6 +200,16 @@ impl Account {
         }
     }
 
+    /// Generates a new Aleo account from a new mnemonic phrase.
+    fn new_mnemonic() -> Result<String> {
+        // Sample a new mnemonic phrase.
+        let mnemonic = Mnemonic::new(Mnemonic::DEFAULT_NUM_WORDS, &mut ChaChaRng::from_entropy())?;
+        // Derive the first account of the mnemonic phrase.
+        let account = snarkos_account::Account::<Network>::from_mnemonic(&mnemonic, "", 0, 0)?;
+        // Print the mnemonic phrase and the new Aleo account.
+        Ok(format!(" {:>12}  {mnemonic}\n{account}", "Mnemonic".cyan().bold()))
+    }
+
     /// Generates a new Aleo account with an optional seed.
     fn new_seeded(seed: Option<String>) -> Result<String> {
         // Recover the seed.
@@ -151,7 +240,7 @@ mod tests {
     #[test]
     fn test_new() {
         for _ in 0..3 {
-            let account = Account::New { seed: None, vanity: None };
+            let account = Account::New { seed: None, vanity: None, mnemonic: false };
             assert!(account.parse().is_ok());
         }
     }
@@ -177,7 +266,7 @@ mod tests {
         );
 
         let vanity = None;
-        let account = Account::New { seed, vanity };
+        let account = Account::New { seed, vanity, mnemonic: false };
         let actual = account.parse().unwrap();
         assert_eq!(expected, actual);
     }
@@ -203,8 +292,77 @@ mod tests {
         );
 
         let vanity = None;
-        let account = Account::New { seed, vanity };
+        let account = Account::New { seed, vanity, mnemonic: false };
         let actual = account.parse().unwrap();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_derive() {
+        let seed = 1231275789u64.to_string();
+
+        let derive = |seed: &str, index| Account::Derive {
+            seed: Some(seed.to_string()),
+            mnemonic: None,
+            passphrase: String::new(),
+            account: 0,
+            index,
+        };
+
+        // Ensure the derivation is deterministic.
+        let account = derive(&seed, 0).parse().unwrap();
+        assert_eq!(account, derive(&seed, 0).parse().unwrap());
+        // Ensure the derived account differs from the seeded account, and across indices.
+        assert_ne!(account, Account::New { seed: Some(seed.clone()), vanity: None, mnemonic: false }.parse().unwrap());
+        assert_ne!(account, derive(&seed, 1).parse().unwrap());
+        // Ensure an invalid seed is rejected.
+        assert!(derive("invalid", 0).parse().is_err());
+    }
+
+    #[test]
+    fn test_derive_mnemonic() {
+        let mnemonic = Some(format!("{} about", ["abandon"; 11].join(" ")));
+        let derive = |mnemonic: Option<String>, passphrase: &str| Account::Derive {
+            seed: None,
+            mnemonic,
+            passphrase: passphrase.to_string(),
+            account: 0,
+            index: 0,
+        };
+
+        // Ensure the derivation is deterministic, and depends on the passphrase.
+        let account = derive(mnemonic.clone(), "").parse().unwrap();
+        assert_eq!(account, derive(mnemonic.clone(), "").parse().unwrap());
+        assert_ne!(account, derive(mnemonic, "passphrase").parse().unwrap());
+        // Ensure an invalid mnemonic phrase is rejected.
+        assert!(derive(Some(["abandon"; 12].join(" ")), "").parse().is_err());
+        // Ensure exactly one of the seed and the mnemonic phrase is required.
+        assert!(derive(None, "").parse().is_err());
+    }
+
+    #[test]
+    fn test_new_mnemonic() {
+        let account = Account::New { seed: None, vanity: None, mnemonic: true };
+        assert!(account.parse().is_ok());
+        let account = Account::New { seed: Some("1".to_string()), vanity: None, mnemonic: true };
+        assert!(account.parse().is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let private_key = "APrivateKey1zkp2n22c19hNdGF8wuEoQcuiyuWbquY6up4CtG5DYKqPX2X".to_string();
+        let address = "aleo1uxl69laseuv3876ksh8k0nd7tvpgjt6ccrgccedpjk9qwyfensxst9ftg5".to_string();
+        let message = "I own this address".to_string();
+
+        // Sign the message.
+        let signature = Account::Sign { private_key, message: message.clone() }.parse().unwrap();
+        // Ensure the signature is valid for the message, and invalid for a different message.
+        let verify = |message: &str| Account::Verify {
+            address: address.clone(),
+            message: message.to_string(),
+            signature: signature.clone(),
+        };
+        assert!(verify(&message).parse().is_ok());
+        assert!(verify("I do not own this address").parse().is_err());
+    }
 }
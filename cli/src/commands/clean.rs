@@ -15,6 +15,7 @@
 use anyhow::{bail, Result};
 use clap::Parser;
 use colored::Colorize;
+use std::path::Path;
 
 /// Cleans the snarkOS node storage.
 #[derive(Debug, Parser)]
@@ -31,21 +32,21 @@ impl Clean {
     /// Cleans the snarkOS node storage.
     pub fn parse(self) -> Result<String> {
         // Remove the specified ledger from storage.
-        Self::remove_ledger(self.network, self.dev)
+        let ledger = Self::remove_dir(&aleo_std::aleo_ledger_dir(self.network, self.dev))?;
+        // Remove the state of the node (e.g. its peers and memory pool) for the specified network.
+        let state = Self::remove_dir(&snarkos_node::storage_dir(&aleo_std::aleo_dir(), self.network, self.dev))?;
+        Ok(format!("{ledger}\n{state}"))
     }
 
-    /// Removes the specified ledger from storage.
-    fn remove_ledger(network: u16, dev: Option<u16>) -> Result<String> {
-        // Construct the path to the ledger in storage.
-        let path = aleo_std::aleo_ledger_dir(network, dev);
-
+    /// Removes the specified directory from storage.
+    fn remove_dir(path: &Path) -> Result<String> {
         // Prepare the path string.
         let path_string = format!("(in \"{}\")", path.display()).dimmed();
 
-        // Check if the path to the ledger exists in storage.
+        // Check if the path exists in storage.
         if path.exists() {
-            // Remove the ledger files from storage.
-            match std::fs::remove_dir_all(&path) {
+            // Remove the files from storage.
+            match std::fs::remove_dir_all(path) {
                 Ok(_) => Ok(format!("✅ Cleaned the snarkOS node storage {path_string}")),
                 Err(error) => {
                     bail!("Failed to remove the snarkOS node storage {path_string}\n{}", error.to_string().dimmed())
@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CurrentNetwork;
+use crate::helpers::GenesisBuilder;
+
+use snarkvm::prelude::{Address, PrivateKey};
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use colored::Colorize;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use std::{path::PathBuf, str::FromStr};
+
+/// Generates a genesis block for a private network.
+#[derive(Debug, Parser)]
+pub struct Genesis {
+    /// The private key of the beacon, which is allocated the starting supply unless allocations are given.
+    #[clap(long)]
+    pub private_key: String,
+    /// An allocation of microcredits, as `<ADDRESS>=<MICROCREDITS>`, which must add up to the starting supply.
+    #[clap(long = "allocation")]
+    pub allocations: Vec<String>,
+    /// The path to write the genesis block to, as JSON if the path ends in `.json`, or as bytes otherwise.
+    #[clap(short, long)]
+    pub output: PathBuf,
+    /// The seed for the RNG, to generate the genesis block reproducibly.
+    #[clap(long)]
+    pub seed: Option<u64>,
+}
+
+impl Genesis {
+    pub fn parse(self) -> Result<String> {
+        // Parse the beacon private key.
+        let private_key = PrivateKey::<CurrentNetwork>::from_str(&self.private_key)?;
+        // Initialize the RNG.
+        let mut rng = match self.seed {
+            Some(seed) => ChaChaRng::seed_from_u64(seed),
+            None => ChaChaRng::from_entropy(),
+        };
+
+        println!("📦 Creating the genesis block...\n");
+
+        // Initialize the genesis block with the allocations.
+        let builder = self.allocations.iter().try_fold(GenesisBuilder::new(private_key), |builder, allocation| {
+            let (address, amount) = Self::parse_allocation(allocation)?;
+            Ok::<_, anyhow::Error>(builder.allocate(address, amount))
+        })?;
+        let genesis = builder.build(&mut rng)?;
+        // Write the genesis block to the output path.
+        crate::helpers::save_genesis(&genesis, &self.output)?;
+
+        Ok(format!(
+            "✅ Created the genesis block '{}' at '{}'",
+            genesis.hash().to_string().bold(),
+            self.output.display()
+        ))
+    }
+
+    /// Parses an allocation of the form `<ADDRESS>=<MICROCREDITS>`.
+    fn parse_allocation(allocation: &str) -> Result<(Address<CurrentNetwork>, u64)> {
+        let (address, amount) = allocation
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid allocation '{allocation}', expected <ADDRESS>=<MICROCREDITS>"))?;
+        Ok((Address::from_str(address.trim())?, amount.trim().parse()?))
+    }
+}
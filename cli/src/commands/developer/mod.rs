@@ -21,6 +21,9 @@ pub use deploy::*;
 mod execute;
 pub use execute::*;
 
+mod genesis;
+pub use genesis::*;
+
 mod scan;
 pub use scan::*;
 
@@ -49,6 +52,8 @@ pub enum Developer {
     Deploy(Deploy),
     /// Execute a program function.
     Execute(Execute),
+    /// Generate a genesis block for a private network.
+    Genesis(Genesis),
     /// Scan the node for records.
     Scan(Scan),
     /// Execute the `credits.aleo/transfer_private` function.
@@ -61,6 +66,7 @@ impl Developer {
             Self::Decrypt(decrypt) => decrypt.parse(),
             Self::Deploy(deploy) => deploy.parse(),
             Self::Execute(execute) => execute.parse(),
+            Self::Genesis(genesis) => genesis.parse(),
             Self::Scan(scan) => scan.parse(),
             Self::TransferPrivate(transfer_private) => transfer_private.parse(),
         }
@@ -122,7 +128,7 @@ impl Developer {
     }
 
     /// Determine if the transaction should be broadcast or displayed to user.
-    fn handle_transaction(
+    pub(crate) fn handle_transaction(
         broadcast: Option<String>,
         dry_run: bool,
         store: Option<String>,
@@ -134,6 +140,8 @@ impl Developer {
 
         // Ensure the transaction is not a fee transaction.
         ensure!(!transaction.is_fee(), "The transaction is a fee transaction and cannot be broadcast");
+        // Ensure the transaction does not exceed the maximum transaction size of the memory pool.
+        snarkos_node::consensus::check_transaction_size(&transaction)?;
 
         // Determine if the transaction should be stored.
         if let Some(path) = store {
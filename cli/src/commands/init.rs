@@ -0,0 +1,142 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::{Config, NetworkConfig, ProverConfig};
+use snarkos_account::{Account, Mnemonic};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use colored::Colorize;
+use core::str::FromStr;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use std::{io::Write, path::Path};
+
+type Network = snarkvm::prelude::Testnet3;
+
+/// Sets up a new node, with a new account and a starter configuration.
+#[derive(Debug, Parser)]
+pub struct Init {
+    /// Specify the network ID of the node
+    #[clap(default_value = "3", long = "network")]
+    pub network: u16,
+    /// Specify the mnemonic phrase of an existing account, instead of generating a new account
+    #[clap(long)]
+    pub mnemonic: Option<String>,
+    /// If the flag is set, the node is configured as a client, instead of a prover that mines with the account
+    #[clap(long)]
+    pub client: bool,
+    /// If the flag is set, an existing configuration is overwritten
+    #[clap(long)]
+    pub force: bool,
+    /// If the flag is set, the setup does not prompt for confirmation
+    #[clap(short = 'y', long)]
+    pub yes: bool,
+}
+
+impl Init {
+    /// Sets up the node, and returns the instructions to start it.
+    pub fn parse(self) -> Result<String> {
+        self.init(&aleo_std::aleo_dir())
+    }
+
+    /// Sets up the node in the given data directory, and returns the instructions to start it.
+    fn init(&self, data_dir: &Path) -> Result<String> {
+        // Ensure the network is supported.
+        if self.network != 3 {
+            bail!("Invalid network ID specified ({}), only network 3 is supported", self.network);
+        }
+        // Ensure an existing configuration is only overwritten if the flag is set.
+        let path = Config::default_path(data_dir);
+        if path.exists() && !self.force {
+            bail!("A configuration already exists at '{}' (use '--force' to overwrite it)", path.display());
+        }
+
+        // Recover the mnemonic phrase, or sample a new one.
+        let (mnemonic, is_new) = match &self.mnemonic {
+            Some(mnemonic) => (Mnemonic::from_str(mnemonic)?, false),
+            None => (Mnemonic::new(Mnemonic::DEFAULT_NUM_WORDS, &mut ChaChaRng::from_entropy())?, true),
+        };
+        // Derive the first account of the mnemonic phrase.
+        let account = Account::<Network>::from_mnemonic(&mnemonic, "", 0, 0)?;
+
+        // Print the new account, and ensure its mnemonic phrase is written down before it is used.
+        if is_new {
+            println!("\n {:>12}  {mnemonic}\n{account}\n", "Mnemonic".cyan().bold());
+            println!(
+                "{}\n",
+                " The mnemonic phrase is the only way to recover this account. Write it down, and keep it secret."
+                    .yellow()
+            );
+            if !self.yes && !confirm("Have you written down the mnemonic phrase?")? {
+                bail!("The setup was cancelled, and no configuration was written");
+            }
+        }
+
+        // Write the configuration.
+        self.to_config(&account).save(&path)?;
+
+        // Print the instructions to start the node.
+        let mut output = format!("✅ Wrote the configuration to '{}'\n\n", path.display());
+        match self.client {
+            true => output += &format!(" {:>12}  {}\n", "Address".cyan().bold(), account.address()),
+            false => output += &format!(" {:>12}  {}\n", "Mining".cyan().bold(), account.address()),
+        }
+        output += &format!("\nRun '{}' to start the node.", "snarkos start".bold());
+        Ok(output)
+    }
+
+    /// Returns the starter configuration, for the given account.
+    fn to_config(&self, account: &Account<Network>) -> Config {
+        Config {
+            network: NetworkConfig { id: Some(self.network), ..Default::default() },
+            prover: ProverConfig { private_key: (!self.client).then(|| account.private_key().to_string()) },
+            ..Default::default()
+        }
+    }
+}
+
+/// Prompts for a yes or no answer on the terminal, and returns `true` if the answer is yes.
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{prompt} [y/N]: ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init() {
+        let data_dir = std::env::temp_dir().join("snarkos-test-init");
+        let mnemonic = format!("{} about", ["abandon"; 11].join(" "));
+        let init = |client, force| Init { network: 3, mnemonic: Some(mnemonic.clone()), client, force, yes: true };
+
+        // Ensure the configuration specifies the account as the prover.
+        init(false, false).init(&data_dir).unwrap();
+        let config = Config::load(&Config::default_path(&data_dir), true).unwrap();
+        let account = Account::<Network>::from_mnemonic(&Mnemonic::from_str(&mnemonic).unwrap(), "", 0, 0).unwrap();
+        assert_eq!(config.prover.private_key, Some(account.private_key().to_string()));
+
+        // Ensure an existing configuration is only overwritten if the flag is set.
+        assert!(init(true, false).init(&data_dir).is_err());
+        init(true, true).init(&data_dir).unwrap();
+        let config = Config::load(&Config::default_path(&data_dir), true).unwrap();
+        assert_eq!(config.prover.private_key, None);
+        std::fs::remove_dir_all(&data_dir).unwrap();
+    }
+}
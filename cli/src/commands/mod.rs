@@ -21,15 +21,30 @@ pub use clean::*;
 mod developer;
 pub use developer::*;
 
+mod init;
+pub use init::*;
+
+mod rpc;
+pub use rpc::*;
+
 mod start;
 pub use start::*;
 
+mod status;
+pub use status::*;
+
+mod stop;
+pub use stop::*;
+
 mod update;
 pub use update::*;
 
+mod wallet;
+pub use wallet::*;
+
 use anstyle::{AnsiColor, Color, Style};
-use anyhow::Result;
-use clap::{builder::Styles, Parser};
+use anyhow::{anyhow, Result};
+use clap::{builder::Styles, ArgMatches, Parser};
 
 const HEADER_COLOR: Option<Color> = Some(Color::Ansi(AnsiColor::Yellow));
 const LITERAL_COLOR: Option<Color> = Some(Color::Ansi(AnsiColor::Green));
@@ -39,7 +54,13 @@ const STYLES: Styles = Styles::plain()
     .literal(Style::new().bold().fg_color(LITERAL_COLOR));
 
 #[derive(Debug, Parser)]
-#[clap(name = "snarkOS", author = "The Aleo Team <hello@aleo.org>", styles = STYLES)]
+#[clap(
+    name = "snarkOS",
+    author = "The Aleo Team <hello@aleo.org>",
+    version = snarkos_node_env::VERSION,
+    long_version = snarkos_node_env::long_version(),
+    styles = STYLES
+)]
 pub struct CLI {
     /// Specify the verbosity [options: 0, 1, 2, 3]
     #[clap(default_value = "2", short, long)]
@@ -57,21 +78,39 @@ pub enum Command {
     Clean(Clean),
     #[clap(subcommand)]
     Developer(Developer),
+    #[clap(name = "init")]
+    Init(Init),
+    #[clap(name = "rpc")]
+    Rpc(Rpc),
     #[clap(name = "start")]
     Start(Box<Start>),
+    #[clap(name = "status")]
+    Status(Status),
+    #[clap(name = "stop")]
+    Stop(Stop),
     #[clap(name = "update")]
     Update(Update),
+    #[clap(subcommand)]
+    Wallet(Wallet),
 }
 
 impl Command {
-    /// Parses the command.
-    pub fn parse(self) -> Result<String> {
+    /// Parses the command, given the argument matches of the CLI it was parsed from.
+    pub fn parse(self, matches: &ArgMatches) -> Result<String> {
         match self {
             Self::Account(command) => command.parse(),
             Self::Clean(command) => command.parse(),
             Self::Developer(command) => command.parse(),
-            Self::Start(command) => command.parse(),
+            Self::Init(command) => command.parse(),
+            Self::Rpc(command) => command.parse(),
+            Self::Start(command) => match matches.subcommand_matches("start") {
+                Some(matches) => command.parse(matches),
+                None => Err(anyhow!("Missing the arguments of the 'start' command")),
+            },
+            Self::Status(command) => command.parse(),
+            Self::Stop(command) => command.parse(),
             Self::Update(command) => command.parse(),
+            Self::Wallet(command) => command.parse(),
         }
     }
 }
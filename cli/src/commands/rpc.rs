@@ -0,0 +1,121 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use clap::Parser;
+
+/// Queries the REST API of a running node, and pretty-prints the response.
+#[derive(Debug, Parser)]
+pub struct Rpc {
+    /// Specify the method, as its REST path (e.g. `latest/height`, `block`, `peers/info`, or `health`)
+    pub method: String,
+    /// Specify the parameters of the method, which are appended to the REST path (e.g. a block height)
+    pub params: Vec<String>,
+    /// Specify the JSON body to POST to the method (e.g. `'"127.0.0.1:4133"'` for `peers/connect`)
+    #[clap(long)]
+    pub body: Option<String>,
+    /// Specify the JWT token, for nodes that require one
+    #[clap(long)]
+    pub token: Option<String>,
+    /// Specify the REST endpoint of the node
+    #[clap(default_value = "http://127.0.0.1:3033", long)]
+    pub endpoint: String,
+}
+
+impl Rpc {
+    /// The methods that are served outside of the network path.
+    const UNVERSIONED_METHODS: [&'static str; 2] = ["health", "ready"];
+
+    /// Sends the request to the node, and returns the pretty-printed response.
+    pub fn parse(self) -> Result<String> {
+        Ok(Self::pretty_print(&self.send()?))
+    }
+
+    /// Sends the request to the node, and returns the response.
+    pub(crate) fn send(&self) -> Result<String> {
+        let url = self.url();
+
+        // Prepare the request.
+        let request = match self.body {
+            Some(_) => ureq::post(&url).set("Content-Type", "application/json"),
+            None => ureq::get(&url),
+        };
+        let request = match &self.token {
+            Some(token) => request.set("Authorization", &format!("Bearer {token}")),
+            None => request,
+        };
+
+        // Send the request.
+        let response = match &self.body {
+            Some(body) => request.send_string(body),
+            None => request.call(),
+        };
+
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(code, response)) => {
+                bail!("The node responded with status code {code}: {}", response.into_string()?)
+            }
+            Err(error) => bail!("Failed to reach the node at '{}' - {error}", self.endpoint),
+        }
+    }
+
+    /// Returns the URL of the method, with the parameters appended to the path.
+    fn url(&self) -> String {
+        let endpoint = self.endpoint.trim_end_matches('/');
+        let method = self.method.trim_matches('/');
+        let path = std::iter::once(method).chain(self.params.iter().map(|param| param.as_str())).collect::<Vec<_>>();
+        match Self::UNVERSIONED_METHODS.contains(&method) {
+            true => format!("{endpoint}/{}", path.join("/")),
+            false => format!("{endpoint}/testnet3/{}", path.join("/")),
+        }
+    }
+
+    /// Returns the given response as pretty-printed JSON, or as is if it is not JSON.
+    fn pretty_print(response: &str) -> String {
+        match serde_json::from_str::<serde_json::Value>(response) {
+            Ok(json) => serde_json::to_string_pretty(&json).unwrap_or_else(|_| response.to_string()),
+            Err(_) => response.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url() {
+        let rpc = Rpc::try_parse_from(["snarkos", "latest/height"].iter()).unwrap();
+        assert_eq!(rpc.url(), "http://127.0.0.1:3033/testnet3/latest/height");
+
+        let rpc = Rpc::try_parse_from(["snarkos", "block", "100", "transactions"].iter()).unwrap();
+        assert_eq!(rpc.url(), "http://127.0.0.1:3033/testnet3/block/100/transactions");
+
+        let rpc =
+            Rpc::try_parse_from(["snarkos", "/peers/info/", "--endpoint", "http://10.0.0.1:3030/"].iter()).unwrap();
+        assert_eq!(rpc.url(), "http://10.0.0.1:3030/testnet3/peers/info");
+
+        let rpc = Rpc::try_parse_from(["snarkos", "health"].iter()).unwrap();
+        assert_eq!(rpc.url(), "http://127.0.0.1:3033/health");
+    }
+
+    #[test]
+    fn test_pretty_print() {
+        assert_eq!(Rpc::pretty_print("[1,2]"), "[\n  1,\n  2\n]");
+        assert_eq!(Rpc::pretty_print("12345"), "12345");
+        assert_eq!(Rpc::pretty_print("not json"), "not json");
+        assert_eq!(Rpc::pretty_print(""), "");
+    }
+}
@@ -14,7 +14,8 @@
 
 use snarkos_account::Account;
 use snarkos_display::Display;
-use snarkos_node::{messages::NodeType, Node};
+use snarkos_node::{messages::NodeType, wallet::Wallet, Node, NodeOptions, NotifyHook, NotifyHooks};
+use snarkos_node_rest::{Claims, Permission, RestOptions, DEFAULT_REST_RPS};
 use snarkvm::prelude::{
     block::Block,
     store::{helpers::memory::ConsensusMemory, ConsensusStore},
@@ -25,8 +26,20 @@ use snarkvm::prelude::{
     VM,
 };
 
+use crate::helpers::{
+    Config,
+    LogFile,
+    LogFormat,
+    LogRotation,
+    LoggingConfig,
+    NetworkConfig,
+    ProverConfig,
+    RestConfig,
+    StorageConfig,
+    PID_FILE_NAME,
+};
 use anyhow::{bail, Result};
-use clap::Parser;
+use clap::{parser::ValueSource, ArgMatches, Parser};
 use colored::Colorize;
 use core::str::FromStr;
 use rand::SeedableRng;
@@ -43,11 +56,21 @@ const RECOMMENDED_MIN_NOFILES_LIMIT_BEACON: u64 = 2048;
 #[cfg(target_family = "unix")]
 const RECOMMENDED_MIN_NOFILES_LIMIT_VALIDATOR: u64 = 1024;
 
+/// Overrides the given field with the given setting of the configuration file, unless the flag
+/// with the given ID is passed on the command line, or set by an environment variable.
+fn apply_setting<T, S: Into<T>>(matches: &ArgMatches, id: &str, setting: Option<S>, field: &mut T) {
+    if let Some(setting) = setting {
+        if matches!(matches.value_source(id), None | Some(ValueSource::DefaultValue)) {
+            *field = setting.into();
+        }
+    }
+}
+
 /// Starts the snarkOS node.
 #[derive(Clone, Debug, Parser)]
 pub struct Start {
     /// Specify the network ID of this node
-    #[clap(default_value = "3", long = "network")]
+    #[clap(default_value = "3", long = "network", env = "SNARKOS_NETWORK")]
     pub network: u16,
 
     /// Specify this node as a beacon, with the account private key as an argument
@@ -57,49 +80,139 @@ pub struct Start {
     #[clap(long = "validator")]
     pub validator: Option<String>,
     /// Specify this node as a prover, with the account private key as an argument
-    #[clap(long = "prover")]
+    #[clap(long = "prover", env = "SNARKOS_PROVER")]
     pub prover: Option<String>,
     /// Specify this node as a client, with an optional account private key as an argument
     #[clap(long = "client")]
     pub client: Option<String>,
 
     /// Specify the IP address and port for the node server
-    #[clap(default_value = "0.0.0.0:4133", long = "node")]
+    #[clap(default_value = "0.0.0.0:4133", long = "node", env = "SNARKOS_NODE")]
     pub node: SocketAddr,
     /// Specify the IP address and port of a peer to connect to
-    #[clap(default_value = "", long = "connect")]
+    #[clap(default_value = "", long = "connect", env = "SNARKOS_CONNECT")]
     pub connect: String,
 
     /// Specify the IP address and port for the REST server
-    #[clap(default_value = "0.0.0.0:3033", long = "rest")]
+    #[clap(default_value = "0.0.0.0:3033", long = "rest", env = "SNARKOS_REST")]
     pub rest: SocketAddr,
     /// If the flag is set, the node will not initialize the REST server
-    #[clap(long)]
+    #[clap(long, env = "SNARKOS_NOREST")]
     pub norest: bool,
+    /// If the flag is set, the REST server will require a JWT token for the read-only routes
+    #[clap(long, env = "SNARKOS_RESTAUTH")]
+    pub restauth: bool,
+    /// Specify the maximum number of REST requests per second from each IP address (except for `/health` and `/ready`)
+    #[clap(default_value_t = DEFAULT_REST_RPS, long = "rest-rps", env = "SNARKOS_REST_RPS")]
+    pub rest_rps: u32,
+    /// Specify the origins allowed to make cross-origin REST requests, separated by commas (any origin by default)
+    #[clap(long = "rest-cors-origins", value_delimiter = ',', env = "SNARKOS_REST_CORS_ORIGINS")]
+    pub rest_cors_origins: Vec<String>,
+
+    /// Specify the IP address and port for the gRPC server, which requires a JWT token (disabled by default)
+    #[clap(long = "grpc", env = "SNARKOS_GRPC")]
+    pub grpc: Option<SocketAddr>,
+
+    /// Specify a command (with `%s` replaced by the block hash) or a webhook URL to notify of each new block
+    /// (beacons and validators only)
+    #[clap(long = "block-notify")]
+    pub block_notify: Option<NotifyHook>,
+    /// Specify a command (with `%s` replaced by the transaction ID) or a webhook URL to notify of each
+    /// confirmed transaction that creates a record owned by this node, or by its wallet if it has one
+    /// (beacons and validators only)
+    #[clap(long = "tx-notify")]
+    pub tx_notify: Option<NotifyHook>,
+    /// Specify a command (with `%s` replaced by the event as JSON) or a webhook URL to notify of each
+    /// event of the wallet (a record received or spent, or a balance changed)
+    #[clap(long = "wallet-notify")]
+    pub wallet_notify: Option<NotifyHook>,
+
+    /// Specify the IP address and port for the Prometheus metrics server (disabled by default)
+    #[clap(long = "metrics", env = "SNARKOS_METRICS")]
+    pub metrics: Option<SocketAddr>,
+    /// Specify the path to an encrypted wallet file, whose records the node will track
+    #[clap(long = "wallet", env = "SNARKOS_WALLET")]
+    pub wallet: Option<PathBuf>,
 
     /// If the flag is set, the node will not render the display
-    #[clap(long)]
+    #[clap(long, env = "SNARKOS_NODISPLAY")]
     pub nodisplay: bool,
     /// Specify the verbosity of the node [options: 0, 1, 2, 3, 4]
-    #[clap(default_value = "2", long = "verbosity")]
+    #[clap(default_value = "2", long = "verbosity", env = "SNARKOS_VERBOSITY")]
     pub verbosity: u8,
     /// Specify the path to the file where logs will be stored
-    #[clap(default_value_os_t = std::env::temp_dir().join("snarkos.log"), long = "logfile")]
+    #[clap(default_value_os_t = std::env::temp_dir().join("snarkos.log"), long = "logfile", env = "SNARKOS_LOGFILE")]
     pub logfile: PathBuf,
+    /// Specify the verbosity of the log file, independently of the node [options: 0, 1, 2, 3, 4] (the node verbosity by default)
+    #[clap(long = "logfile-verbosity", env = "SNARKOS_LOGFILE_VERBOSITY")]
+    pub logfile_verbosity: Option<u8>,
+    /// Specify the size in megabytes past which the log file is rotated (0 for unbounded)
+    #[clap(default_value = "100", long = "logfile-max-size", env = "SNARKOS_LOGFILE_MAX_SIZE")]
+    pub logfile_max_size: u64,
+    /// Specify the interval at which the log file is rotated, regardless of its size [options: never, hourly, daily]
+    #[clap(default_value = "never", long = "logfile-rotation", value_enum, env = "SNARKOS_LOGFILE_ROTATION")]
+    pub logfile_rotation: LogRotation,
+    /// Specify the number of rotated log files that are retained
+    #[clap(default_value = "10", long = "logfile-max-files", env = "SNARKOS_LOGFILE_MAX_FILES")]
+    pub logfile_max_files: usize,
+    /// Specify the format of the logs [options: text, json]
+    #[clap(default_value = "text", long = "log-format", value_enum, env = "SNARKOS_LOG_FORMAT")]
+    pub log_format: LogFormat,
+    /// Specify the log filter directives per module, separated by commas (e.g. `snarkos_node_router=debug`)
+    #[clap(default_value = "", long = "log-filter", env = "SNARKOS_LOG_FILTER")]
+    pub log_filter: String,
 
     /// Enables the node to prefetch initial blocks from a CDN
-    #[clap(default_value = "https://testnet3.blocks.aleo.org/phase3", long = "cdn")]
+    #[clap(default_value = "https://testnet3.blocks.aleo.org/phase3", long = "cdn", env = "SNARKOS_CDN")]
     pub cdn: String,
     /// Enables development mode, specify a unique ID for this node
-    #[clap(long)]
+    #[clap(long, env = "SNARKOS_DEV")]
     pub dev: Option<u16>,
+    /// Specify the path to a custom genesis block, as JSON if the path ends in `.json`, or as bytes otherwise
+    #[clap(long = "genesis", env = "SNARKOS_GENESIS")]
+    pub genesis: Option<PathBuf>,
+
+    /// Specify the path to the configuration file, whose settings are overridden by the environment
+    /// variables and flags (`snarkos.toml` in `~/.aleo` by default)
+    #[clap(long = "config", env = "SNARKOS_CONFIG")]
+    pub config: Option<PathBuf>,
+    /// If the flag is set, the node will print the effective configuration, and exit
+    #[clap(long = "print-config")]
+    pub print_config: bool,
+
+    /// If the flag is set, the node will run in the background, without the display (Unix only)
+    #[clap(long = "daemon")]
+    pub daemon: bool,
+    /// Specify the path to the PID file, which the node locks while it runs (`snarkos.pid` in `~/.aleo` with `--daemon`)
+    #[clap(long = "pid-file", env = "SNARKOS_PID_FILE")]
+    pub pid_file: Option<PathBuf>,
 }
 
 impl Start {
-    /// Starts the snarkOS node.
-    pub fn parse(self) -> Result<String> {
+    /// Starts the snarkOS node, given the argument matches of the command, which tell the settings
+    /// that were given by a flag or an environment variable apart from those in the configuration file.
+    pub fn parse(mut self, matches: &ArgMatches) -> Result<String> {
+        // Load the configuration file, underneath the flags and environment variables that are set.
+        self.load_config(matches)?;
+        // If the flag is set, print the effective configuration, instead of starting the node.
+        if self.print_config {
+            return self.to_config().to_toml_string();
+        }
+        // If the flag is set, restart the node in the background, and exit.
+        if self.daemon {
+            return self.daemonize();
+        }
+        // If a PID file is specified (e.g. by `--daemon`), write the PID of the node to it, until the node shuts down.
+        self.write_pid_file()?;
+
         // Initialize the logger.
-        let log_receiver = crate::helpers::initialize_logger(self.verbosity, self.nodisplay, self.logfile.clone());
+        let log_receiver = crate::helpers::initialize_logger(
+            self.verbosity,
+            self.nodisplay,
+            self.log_format,
+            &self.log_filter,
+            self.parse_logfile(),
+        )?;
         // Initialize the runtime.
         Self::runtime().block_on(async move {
             // Clone the configurations.
@@ -127,6 +240,148 @@ impl Start {
 }
 
 impl Start {
+    /// Loads the configuration file, and applies each of its settings whose flag was neither
+    /// passed on the command line, nor set by an environment variable, in the given matches.
+    fn load_config(&mut self, matches: &ArgMatches) -> Result<()> {
+        // A configuration file at the default path is optional, while one that is specified must exist.
+        let config = match &self.config {
+            Some(path) => Config::load(path, true)?,
+            None => Config::load(&Config::default_path(&aleo_std::aleo_dir()), false)?,
+        };
+        self.apply_config(config, matches);
+        Ok(())
+    }
+
+    /// Applies each of the settings of the given configuration whose flag is not set in the given matches.
+    fn apply_config(&mut self, config: Config, matches: &ArgMatches) {
+        let Config { network, rest, storage, prover, logging } = config;
+        apply_setting(matches, "network", network.id, &mut self.network);
+        apply_setting(matches, "node", network.node, &mut self.node);
+        apply_setting(
+            matches,
+            "connect",
+            network.connect.map(|peers| peers.iter().map(ToString::to_string).collect::<Vec<_>>().join(",")),
+            &mut self.connect,
+        );
+        apply_setting(matches, "cdn", network.cdn, &mut self.cdn);
+        apply_setting(matches, "dev", network.dev, &mut self.dev);
+        apply_setting(matches, "norest", rest.enabled.map(|enabled| !enabled), &mut self.norest);
+        apply_setting(matches, "rest", rest.address, &mut self.rest);
+        apply_setting(matches, "restauth", rest.auth, &mut self.restauth);
+        apply_setting(matches, "rest_rps", rest.rps, &mut self.rest_rps);
+        apply_setting(matches, "rest_cors_origins", rest.cors_origins, &mut self.rest_cors_origins);
+        apply_setting(matches, "grpc", rest.grpc, &mut self.grpc);
+        apply_setting(matches, "metrics", rest.metrics, &mut self.metrics);
+        apply_setting(matches, "genesis", storage.genesis, &mut self.genesis);
+        apply_setting(matches, "wallet", storage.wallet, &mut self.wallet);
+        apply_setting(matches, "prover", prover.private_key, &mut self.prover);
+        apply_setting(matches, "verbosity", logging.verbosity, &mut self.verbosity);
+        apply_setting(matches, "logfile", logging.logfile, &mut self.logfile);
+        apply_setting(matches, "logfile_verbosity", logging.logfile_verbosity, &mut self.logfile_verbosity);
+        apply_setting(matches, "logfile_max_size", logging.logfile_max_size, &mut self.logfile_max_size);
+        apply_setting(matches, "logfile_rotation", logging.logfile_rotation, &mut self.logfile_rotation);
+        apply_setting(matches, "logfile_max_files", logging.logfile_max_files, &mut self.logfile_max_files);
+        apply_setting(matches, "log_format", logging.format, &mut self.log_format);
+        apply_setting(matches, "log_filter", logging.filter, &mut self.log_filter);
+        apply_setting(matches, "nodisplay", logging.display.map(|display| !display), &mut self.nodisplay);
+    }
+
+    /// Returns the effective configuration.
+    /// Note: The private key of the prover is omitted, so that it is not printed.
+    fn to_config(&self) -> Config {
+        Config {
+            network: NetworkConfig {
+                id: Some(self.network),
+                node: Some(self.node),
+                connect: self.parse_trusted_peers().ok(),
+                cdn: Some(self.cdn.clone()),
+                dev: self.dev,
+            },
+            rest: RestConfig {
+                enabled: Some(!self.norest),
+                address: Some(self.rest),
+                auth: Some(self.restauth),
+                rps: Some(self.rest_rps),
+                cors_origins: Some(self.rest_cors_origins.clone()),
+                grpc: self.grpc,
+                metrics: self.metrics,
+            },
+            storage: StorageConfig { genesis: self.genesis.clone(), wallet: self.wallet.clone() },
+            prover: ProverConfig { private_key: None },
+            logging: LoggingConfig {
+                verbosity: Some(self.verbosity),
+                logfile: Some(self.logfile.clone()),
+                logfile_verbosity: self.logfile_verbosity,
+                logfile_max_size: Some(self.logfile_max_size),
+                logfile_rotation: Some(self.logfile_rotation),
+                logfile_max_files: Some(self.logfile_max_files),
+                format: Some(self.log_format),
+                filter: Some(self.log_filter.clone()),
+                display: Some(!self.nodisplay),
+            },
+        }
+    }
+
+    /// Returns the settings of the log file, from the given configurations.
+    fn parse_logfile(&self) -> LogFile {
+        LogFile {
+            path: self.logfile.clone(),
+            verbosity: self.logfile_verbosity.unwrap_or(self.verbosity),
+            max_size: self.logfile_max_size.saturating_mul(1024 * 1024),
+            rotation: self.logfile_rotation,
+            max_files: self.logfile_max_files,
+        }
+    }
+
+    /// Returns the path to the PID file, from the given configurations.
+    fn parse_pid_file(&self) -> PathBuf {
+        self.pid_file.clone().unwrap_or_else(|| aleo_std::aleo_dir().join(PID_FILE_NAME))
+    }
+
+    /// Restarts the node in the background, and returns the instructions to stop it.
+    #[cfg(target_family = "unix")]
+    fn daemonize(&self) -> Result<String> {
+        let pid_file = self.parse_pid_file();
+        let stderr_file = aleo_std::aleo_dir().join(crate::helpers::STDERR_FILE_NAME);
+        let pid = crate::helpers::daemonize(&pid_file, &stderr_file)?;
+
+        let stop = match self.pid_file.is_some() {
+            true => format!("snarkos stop --pid-file {}", pid_file.display()),
+            false => "snarkos stop".to_string(),
+        };
+        Ok(format!(
+            "✅ Started snarkOS in the background (PID {pid}), logging to '{}'\n\nRun '{}' to stop it.",
+            self.logfile.display(),
+            stop.bold()
+        ))
+    }
+
+    /// Restarts the node in the background, and returns the instructions to stop it.
+    #[cfg(not(target_family = "unix"))]
+    fn daemonize(&self) -> Result<String> {
+        bail!("The '--daemon' flag is only supported on Unix")
+    }
+
+    /// Writes the PID of the node to the PID file, if one is specified, which is locked while the node runs,
+    /// and removed once the node shuts down.
+    #[cfg(target_family = "unix")]
+    fn write_pid_file(&self) -> Result<()> {
+        if let Some(path) = &self.pid_file {
+            let pid_file = crate::helpers::PidFile::create(path)?;
+            snarkos_node::on_shutdown(move || pid_file.remove());
+        }
+        Ok(())
+    }
+
+    /// Writes the PID of the node to the PID file, if one is specified.
+    #[cfg(not(target_family = "unix"))]
+    fn write_pid_file(&self) -> Result<()> {
+        match self.pid_file.is_some() {
+            true => bail!("The '--pid-file' flag is only supported on Unix"),
+            false => Ok(()),
+        }
+    }
+
     /// Returns the initial node(s) to connect to, from the given configurations.
     fn parse_trusted_peers(&self) -> Result<Vec<SocketAddr>> {
         match self.connect.is_empty() {
@@ -257,6 +512,11 @@ impl Start {
 
         // Parse the development configurations, and determine the genesis block.
         let genesis = self.parse_development::<N>(&mut trusted_peers)?;
+        // If a custom genesis block is specified, it overrides the genesis block.
+        let genesis = match &self.genesis {
+            Some(path) => crate::helpers::load_genesis::<N>(path)?,
+            None => genesis,
+        };
 
         // Parse the REST IP.
         let rest_ip = match self.norest {
@@ -267,6 +527,24 @@ impl Start {
         // Parse the node account and node type.
         let (account, node_type) = self.parse_account::<N>()?;
 
+        // Ensure the wallet notification hook has a wallet, which may be given by the configuration file.
+        if self.wallet_notify.is_some() && self.wallet.is_none() {
+            bail!("The '--wallet-notify' flag requires a wallet, given by the '--wallet' flag");
+        }
+        // Ensure the block and transaction notification hooks are only set for the node types that notify them.
+        let is_notifying = node_type.is_beacon() || node_type.is_validator();
+        if (self.block_notify.is_some() || self.tx_notify.is_some()) && !is_notifying {
+            bail!("The '--block-notify' and '--tx-notify' flags are only supported by a beacon or a validator");
+        }
+        // Parse the notification hooks.
+        let notify_hooks = NotifyHooks {
+            block: self.block_notify.clone(),
+            transaction: self.tx_notify.clone(),
+            wallet: self.wallet_notify.clone(),
+        };
+        // Open the wallet, if specified.
+        let wallet = self.wallet.as_deref().map(Wallet::<N>::open).transpose()?;
+
         // If the display is not enabled, render the welcome message.
         if self.nodisplay {
             // Print the Aleo address.
@@ -280,18 +558,37 @@ impl Start {
                 self.node.to_string().bold()
             );
 
-            // If the node is running a REST server, print the REST IP and JWT.
-            if node_type.is_beacon() || node_type.is_validator() {
-                if let Some(rest_ip) = rest_ip {
-                    println!("🌐 Starting the REST server at {}.\n", rest_ip.to_string().bold());
+            // If the node is running a REST server, print the REST IP.
+            let rest_ip = rest_ip.filter(|_| node_type.is_beacon() || node_type.is_validator() || node_type.is_prover());
+            if let Some(rest_ip) = rest_ip {
+                println!("🌐 Starting the REST server at {}.\n", rest_ip.to_string().bold());
+            }
+
+            // If the node is running a gRPC server, print the gRPC IP.
+            let grpc_ip = self.grpc.filter(|_| node_type.is_beacon() || node_type.is_validator());
+            if let Some(grpc_ip) = grpc_ip {
+                println!("📡 Starting the gRPC server at {}.\n", grpc_ip.to_string().bold());
+            }
 
-                    if let Ok(jwt_token) = snarkos_node_rest::Claims::new(account.address()).to_jwt_string() {
-                        println!("🔑 Your one-time JWT token is {}\n", jwt_token.dimmed());
-                    }
+            // If the node is running a REST or gRPC server, print the JWT tokens.
+            if rest_ip.is_some() || grpc_ip.is_some() {
+                if let Ok(jwt_token) = Claims::new(account.address(), Permission::Admin).to_jwt_string() {
+                    println!("🔑 Your one-time admin JWT token is {}\n", jwt_token.dimmed());
+                }
+                if let Ok(jwt_token) = Claims::new(account.address(), Permission::ReadOnly).to_jwt_string() {
+                    println!("🔑 Your one-time read-only JWT token is {}\n", jwt_token.dimmed());
                 }
             }
         }
 
+        // If enabled, start the Prometheus metrics server.
+        if let Some(metrics_ip) = self.metrics {
+            if self.nodisplay {
+                println!("📈 Starting the metrics server at {}.\n", metrics_ip.to_string().bold());
+            }
+            snarkos_node::initialize_metrics::<N>(metrics_ip, self.dev)?;
+        }
+
         // If the node is a beacon, check if the open files limit is lower than recommended.
         if node_type.is_beacon() {
             #[cfg(target_family = "unix")]
@@ -304,11 +601,28 @@ impl Start {
         }
 
         // Initialize the node.
+        let options = NodeOptions {
+            node_ip: self.node,
+            rest: rest_ip.map(|ip| RestOptions {
+                ip,
+                auth: self.restauth,
+                rps: self.rest_rps,
+                cors_origins: self.rest_cors_origins.clone(),
+            }),
+            grpc_ip: self.grpc,
+            notify_hooks,
+            wallet,
+            trusted_peers,
+            cdn,
+            // Note: The node state is kept next to the ledger, as snarkVM does not support a custom ledger directory.
+            data_dir: aleo_std::aleo_dir(),
+            dev: self.dev,
+        };
         match node_type {
-            NodeType::Beacon => Node::new_beacon(self.node, rest_ip, account, &trusted_peers, genesis, cdn, self.dev).await,
-            NodeType::Validator => Node::new_validator(self.node, rest_ip, account, &trusted_peers, genesis, cdn, self.dev).await,
-            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, self.dev).await,
-            NodeType::Client => Node::new_client(self.node, account, &trusted_peers, genesis, self.dev).await,
+            NodeType::Beacon => Node::new_beacon(options, account, genesis).await,
+            NodeType::Validator => Node::new_validator(options, account, genesis).await,
+            NodeType::Prover => Node::new_prover(options, account, genesis).await,
+            NodeType::Client => Node::new_client(options, account, genesis).await,
         }
     }
 
@@ -346,6 +660,7 @@ impl Start {
 mod tests {
     use super::*;
     use crate::commands::{Command, CLI};
+    use clap::{CommandFactory, FromArgMatches};
     use snarkvm::prelude::Testnet3;
 
     type CurrentNetwork = Testnet3;
@@ -536,6 +851,48 @@ mod tests {
         assert_eq!(genesis, expected_genesis);
     }
 
+    #[test]
+    fn test_apply_config() {
+        let config = Config {
+            network: NetworkConfig {
+                node: Some(SocketAddr::from_str("0.0.0.0:4140").unwrap()),
+                connect: Some(vec![
+                    SocketAddr::from_str("1.2.3.4:5").unwrap(),
+                    SocketAddr::from_str("6.7.8.9:0").unwrap(),
+                ]),
+                ..Default::default()
+            },
+            rest: RestConfig { enabled: Some(false), rps: Some(20), ..Default::default() },
+            storage: StorageConfig { wallet: Some(PathBuf::from("wallet.json")), ..Default::default() },
+            logging: LoggingConfig { verbosity: Some(4), display: Some(false), ..Default::default() },
+            ..Default::default()
+        };
+
+        let matches = Start::command().try_get_matches_from(["snarkos", "--rest-rps", "5"].iter()).unwrap();
+        let mut start = Start::from_arg_matches(&matches).unwrap();
+        start.apply_config(config, &matches);
+        assert_eq!(start.node, SocketAddr::from_str("0.0.0.0:4140").unwrap());
+        assert_eq!(start.connect, "1.2.3.4:5,6.7.8.9:0");
+        assert!(start.norest);
+        assert_eq!(start.verbosity, 4);
+        assert!(start.nodisplay);
+        assert_eq!(start.wallet, Some(PathBuf::from("wallet.json")));
+        // Ensure a flag overrides the setting.
+        assert_eq!(start.rest_rps, 5);
+        // Ensure a missing setting keeps the default value.
+        assert_eq!(start.rest, SocketAddr::from_str("0.0.0.0:3033").unwrap());
+        assert_eq!(start.network, 3);
+
+        // Ensure the effective configuration reflects the settings.
+        let effective = start.to_config();
+        assert_eq!(effective.network.node, Some(start.node));
+        assert_eq!(effective.network.connect.map(|peers| peers.len()), Some(2));
+        assert_eq!(effective.rest.enabled, Some(false));
+        assert_eq!(effective.rest.rps, Some(5));
+        assert_eq!(effective.logging.display, Some(false));
+        assert!(start.to_config().to_toml_string().is_ok());
+    }
+
     #[test]
     fn clap_snarkos_start() {
         let arg_vec = vec![
@@ -0,0 +1,321 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Rpc;
+use snarkos_node::messages::NodeType;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{io, net::SocketAddr, time::Duration};
+use tui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Gauge, Paragraph, Row, Table},
+    Frame,
+    Terminal,
+};
+
+/// Shows the status of a running node, from its REST API.
+#[derive(Debug, Parser)]
+pub struct Status {
+    /// If the flag is set, the status is rendered as a dashboard, which refreshes until `q` or `Esc` is pressed
+    #[clap(long)]
+    pub watch: bool,
+    /// Specify the interval in seconds at which the dashboard refreshes
+    #[clap(default_value = "2", long)]
+    pub interval: u64,
+    /// Specify the JWT token, for nodes that require one (the recent logs require an admin JWT token)
+    #[clap(long)]
+    pub token: Option<String>,
+    /// Specify the REST endpoint of the node
+    #[clap(default_value = "http://127.0.0.1:3033", long)]
+    pub endpoint: String,
+}
+
+/// The status of the node, from the `node/status` method.
+#[derive(Debug, Deserialize)]
+struct NodeStatus {
+    network: String,
+    node_type: NodeType,
+    height: u32,
+    hash: String,
+    peer_height: u32,
+    is_synced: bool,
+    sync_progress: f64,
+}
+
+/// A connected peer, from the `peers/info` method.
+#[derive(Debug, Deserialize)]
+struct PeerInfo {
+    ip: SocketAddr,
+    node_type: NodeType,
+    latency_ms: Option<u64>,
+}
+
+/// The memory pool statistics, from the `memoryPool/info` method.
+#[derive(Debug, Deserialize)]
+struct MemoryPoolInfo {
+    size: usize,
+    bytes: usize,
+    min_fee: u64,
+}
+
+/// A snapshot of the node, in which each field is `None` if its method is not served by the node.
+#[derive(Debug, Default)]
+struct Snapshot {
+    status: Option<NodeStatus>,
+    peers: Option<Vec<PeerInfo>>,
+    memory_pool: Option<MemoryPoolInfo>,
+    hashrate: Option<f64>,
+    logs: Vec<String>,
+}
+
+impl Status {
+    /// Shows the status of the node, once, or as a dashboard if `--watch` is set.
+    pub fn parse(self) -> Result<String> {
+        match self.watch {
+            true => self.dashboard().map(|_| String::new()),
+            false => Ok(self.fetch()?.summary()),
+        }
+    }
+
+    /// Returns a snapshot of the node.
+    fn fetch(&self) -> Result<Snapshot> {
+        let snapshot = Snapshot {
+            status: self.get("node/status").ok(),
+            peers: self.get("peers/info").ok(),
+            memory_pool: self.get("memoryPool/info").ok(),
+            hashrate: self.get("prover/hashrate").ok(),
+            logs: self.get("node/logs").unwrap_or_default(),
+        };
+        // A node serves at least its status or its hashrate, so the node is unreachable if neither is served.
+        if snapshot.status.is_none() && snapshot.hashrate.is_none() {
+            bail!("Failed to retrieve the status of the node at '{}'", self.endpoint);
+        }
+        Ok(snapshot)
+    }
+
+    /// Calls the given method of the REST API, and deserializes the response.
+    fn get<T: DeserializeOwned>(&self, method: &str) -> Result<T> {
+        let rpc = Rpc {
+            method: method.to_string(),
+            params: vec![],
+            body: None,
+            token: self.token.clone(),
+            endpoint: self.endpoint.clone(),
+        };
+        Ok(serde_json::from_str(&rpc.send()?)?)
+    }
+
+    /// Renders the dashboard, until `q` or `Esc` is pressed.
+    fn dashboard(&self) -> Result<()> {
+        // Initialize the dashboard.
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+        // Render the dashboard.
+        let result = self.render(&mut terminal);
+
+        // Terminate the dashboard.
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        result
+    }
+
+    /// Refreshes the dashboard at each interval, until `q` or `Esc` is pressed.
+    fn render<B: Backend>(&self, terminal: &mut Terminal<B>) -> Result<()> {
+        let interval = Duration::from_secs(self.interval.max(1));
+        loop {
+            // Note: A node that is unreachable is shown as such, as it may be restarting.
+            let snapshot = self.fetch();
+            terminal.draw(|f| match &snapshot {
+                Ok(snapshot) => snapshot.draw(f),
+                Err(error) => draw_error(f, &error.to_string()),
+            })?;
+
+            if event::poll(interval)? {
+                if let Event::Key(key) = event::read()? {
+                    if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Snapshot {
+    /// Returns the snapshot as lines of text.
+    fn summary(&self) -> String {
+        let mut lines = vec![];
+        if let Some(status) = &self.status {
+            lines.push(format!("Node: {:?} on {}", status.node_type, status.network));
+            lines.push(format!("Height: {} ({})", status.height, status.hash));
+            lines.push(format!("Sync: {}", status.sync_description()));
+        }
+        if let Some(peers) = &self.peers {
+            lines.push(format!("Peers: {}", peers.len()));
+            for peer in peers {
+                lines.push(format!("  {} {:?} ({})", peer.ip, peer.node_type, peer.latency_description()));
+            }
+        }
+        if let Some(memory_pool) = &self.memory_pool {
+            lines.push(format!("Memory pool: {}", memory_pool.description()));
+        }
+        if let Some(hashrate) = self.hashrate {
+            lines.push(format!("Hashrate: {hashrate:.2} solutions/s"));
+        }
+        if !self.logs.is_empty() {
+            lines.push("Recent logs:".to_string());
+            lines.extend(self.logs.iter().map(|log| format!("  {log}")));
+        }
+        lines.join("\n")
+    }
+
+    /// Draws the snapshot as a dashboard.
+    fn draw<B: Backend>(&self, f: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .margin(1)
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(6), Constraint::Length(3), Constraint::Percentage(40), Constraint::Min(0)])
+            .split(f.size());
+
+        /* Status */
+
+        let mut lines = vec![];
+        if let Some(status) = &self.status {
+            lines.push(Spans::from(format!("Node:   {:?} on {}", status.node_type, status.network)));
+            lines.push(Spans::from(format!("Height: {} ({})", status.height, status.hash)));
+        }
+        if let Some(memory_pool) = &self.memory_pool {
+            lines.push(Spans::from(format!("Memory pool: {}", memory_pool.description())));
+        }
+        if let Some(hashrate) = self.hashrate {
+            lines.push(Spans::from(format!("Hashrate: {hashrate:.2} solutions/s")));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled("snarkOS status (press q to quit)", Style::default().add_modifier(Modifier::BOLD)));
+        f.render_widget(Paragraph::new(lines).block(block), chunks[0]);
+
+        /* Sync */
+
+        let (ratio, label) = match &self.status {
+            Some(status) => (status.sync_progress.clamp(0.0, 1.0), status.sync_description()),
+            None => (0.0, "unavailable".to_string()),
+        };
+        let gauge = Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title("Sync"))
+            .gauge_style(Style::default().fg(Color::Green))
+            .ratio(ratio)
+            .label(label);
+        f.render_widget(gauge, chunks[1]);
+
+        /* Peers */
+
+        let peers = self.peers.as_deref().unwrap_or_default();
+        let rows = peers.iter().map(|peer| {
+            Row::new(vec![peer.ip.to_string(), format!("{:?}", peer.node_type), peer.latency_description()])
+        });
+        let table = Table::new(rows)
+            .header(Row::new(vec!["IP", "Type", "Latency"]).style(Style::default().fg(Color::Yellow)))
+            .block(Block::default().borders(Borders::ALL).title(format!("Peers ({})", peers.len())))
+            .widths(&[Constraint::Length(24), Constraint::Length(12), Constraint::Length(12)]);
+        f.render_widget(table, chunks[2]);
+
+        /* Logs */
+
+        // Show the most recent logs that fit in the area.
+        let height = chunks[3].height.saturating_sub(2) as usize;
+        let logs = self.logs.iter().skip(self.logs.len().saturating_sub(height)).map(|log| Spans::from(log.as_str()));
+        let paragraph =
+            Paragraph::new(logs.collect::<Vec<_>>()).block(Block::default().borders(Borders::ALL).title("Recent logs"));
+        f.render_widget(paragraph, chunks[3]);
+    }
+}
+
+impl NodeStatus {
+    /// Returns the sync progress, as a description.
+    fn sync_description(&self) -> String {
+        match self.is_synced {
+            true => format!("synced at height {}", self.height),
+            false => format!("{:.2}% ({} of {})", self.sync_progress * 100.0, self.height, self.peer_height),
+        }
+    }
+}
+
+impl PeerInfo {
+    /// Returns the latency of the peer, as a description.
+    fn latency_description(&self) -> String {
+        match self.latency_ms {
+            Some(latency_ms) => format!("{latency_ms} ms"),
+            None => "-".to_string(),
+        }
+    }
+}
+
+impl MemoryPoolInfo {
+    /// Returns the memory pool statistics, as a description.
+    fn description(&self) -> String {
+        format!("{} transactions, {} bytes, minimum fee of {} microcredits", self.size, self.bytes, self.min_fee)
+    }
+}
+
+/// Draws the given error, in place of the dashboard.
+fn draw_error<B: Backend>(f: &mut Frame<B>, error: &str) {
+    let block = Block::default().borders(Borders::ALL).title("snarkOS status (press q to quit)");
+    let paragraph = Paragraph::new(Span::styled(error, Style::default().fg(Color::Red))).block(block);
+    f.render_widget(paragraph, f.size());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let status = serde_json::from_str::<NodeStatus>(
+            r#"{"network":"Aleo Testnet 3","node_type":"Validator","height":90,"hash":"ab1xx","peer_height":100,
+            "is_synced":false,"sync_progress":0.9,"coinbase_target":1,"proof_target":1,"peers":1,"memory_pool_size":0}"#,
+        )
+        .unwrap();
+        let peers = serde_json::from_str::<Vec<PeerInfo>>(
+            r#"[{"ip":"1.2.3.4:4130","address":"aleo1xx","node_type":"Beacon","version":1,"is_outbound":true,"latency_ms":12}]"#,
+        )
+        .unwrap();
+        let snapshot =
+            Snapshot { status: Some(status), peers: Some(peers), logs: vec!["log".into()], ..Default::default() };
+
+        let summary = snapshot.summary();
+        assert!(summary.contains("Height: 90 (ab1xx)"));
+        assert!(summary.contains("Sync: 90.00% (90 of 100)"));
+        assert!(summary.contains("1.2.3.4:4130 Beacon (12 ms)"));
+        assert!(summary.contains("  log"));
+        // Ensure the routes that are not served by the node are omitted.
+        assert!(!summary.contains("Memory pool"));
+        assert!(!summary.contains("Hashrate"));
+    }
+}
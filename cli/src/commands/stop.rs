@@ -0,0 +1,54 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Stops the snarkOS node running in the background.
+#[derive(Debug, Parser)]
+pub struct Stop {
+    /// Specify the path to the PID file of the node (`snarkos.pid` in `~/.aleo` by default)
+    #[clap(long = "pid-file", env = "SNARKOS_PID_FILE")]
+    pub pid_file: Option<PathBuf>,
+    /// Specify the number of seconds to wait for the node to shut down
+    #[clap(default_value = "60", long)]
+    pub timeout: u64,
+}
+
+impl Stop {
+    /// Stops the node, once it has shut down cleanly.
+    #[cfg(target_family = "unix")]
+    pub fn parse(self) -> Result<String> {
+        let pid_file = match self.pid_file {
+            Some(pid_file) => pid_file,
+            None => aleo_std::aleo_dir().join(crate::helpers::PID_FILE_NAME),
+        };
+        // Retrieve the PID of the node, only if it still holds the lock on its PID file,
+        // so that a stale PID (e.g. of a node that crashed) is never signaled.
+        let pid = match crate::helpers::running_pid(&pid_file)? {
+            Some(pid) => pid,
+            None => bail!("snarkOS is not running in the background (no running node holds '{}')", pid_file.display()),
+        };
+        // Stop the node, which removes its PID file once it has shut down.
+        crate::helpers::stop(pid, std::time::Duration::from_secs(self.timeout))?;
+        Ok(format!("✅ Stopped snarkOS (PID {pid})"))
+    }
+
+    /// Stops the node, once it has shut down cleanly.
+    #[cfg(not(target_family = "unix"))]
+    pub fn parse(self) -> Result<String> {
+        bail!("The 'stop' command is only supported on Unix")
+    }
+}
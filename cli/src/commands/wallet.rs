@@ -0,0 +1,329 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Developer;
+use snarkos_account::Account;
+use snarkos_node::wallet::{HistoryFormat, Keystore, PartialTransaction};
+use snarkvm::prelude::{
+    query::Query,
+    store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    PrivateKey,
+    ViewKey,
+    VM,
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use core::str::FromStr;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal,
+    tty::IsTty,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+type Aleo = snarkvm::circuit::AleoV0;
+type Network = snarkvm::prelude::Testnet3;
+
+/// The environment variable from which the wallet password is read, instead of prompting for it.
+pub const WALLET_PASSWORD_ENV: &str = "SNARKOS_WALLET_PASSWORD";
+
+/// Commands to manage encrypted wallet files.
+#[derive(Debug, Parser)]
+pub enum Wallet {
+    /// Creates a new encrypted wallet file, from the given private keys and view keys, or a new account
+    New {
+        /// The path to write the wallet file to
+        #[clap(short = 'p', long)]
+        path: PathBuf,
+        /// The private key of an account to store in the wallet (may be repeated)
+        #[clap(long)]
+        private_key: Vec<String>,
+        /// The view key of a watch-only account to store in the wallet (may be repeated)
+        #[clap(long)]
+        view_key: Vec<String>,
+    },
+    /// Signs a partial transaction file with a private key, which does not require access to the network
+    Sign {
+        /// The path to the partial transaction file, which is overwritten with the signed transaction
+        #[clap(short = 't', long)]
+        transaction: PathBuf,
+        /// The private key to sign the transaction with
+        #[clap(long)]
+        private_key: String,
+    },
+    /// Proves a signed partial transaction file, for its next signature or to complete the transaction
+    Prove {
+        /// The path to the partial transaction file, which is overwritten with the proven transaction
+        #[clap(short = 't', long)]
+        transaction: PathBuf,
+        /// The endpoint to query node state from
+        #[clap(short, long)]
+        query: String,
+        /// The endpoint used to broadcast the complete transaction
+        #[clap(short, long, conflicts_with = "dry_run")]
+        broadcast: Option<String>,
+        /// Performs a dry-run of the complete transaction
+        #[clap(short, long, conflicts_with = "broadcast")]
+        dry_run: bool,
+        /// Store the complete transaction to a local file
+        #[clap(long)]
+        store: Option<String>,
+    },
+    /// Exports the transaction history of the wallet of a running node, to CSV or JSON
+    History {
+        /// The format to export the history in (`csv` or `json`)
+        #[clap(short, long, default_value = "csv")]
+        format: HistoryFormat,
+        /// The path to write the history to, instead of printing it
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+        /// The admin JWT token of the node
+        #[clap(long)]
+        token: String,
+        /// The REST endpoint of the node
+        #[clap(default_value = "http://127.0.0.1:3033", long)]
+        endpoint: String,
+    },
+}
+
+impl Wallet {
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::New { path, private_key, view_key } => {
+                // Ensure an existing wallet file is not overwritten.
+                ensure!(!path.exists(), "The wallet file '{}' already exists", path.display());
+
+                // Parse the accounts and the view keys, or sample a new account.
+                let view_keys = view_key.iter().map(|key| ViewKey::from_str(key)).collect::<Result<Vec<_>>>()?;
+                let accounts = match private_key.is_empty() && view_keys.is_empty() {
+                    true => {
+                        let account = Account::<Network>::new(&mut ChaChaRng::from_entropy())?;
+                        println!(
+                            "⚠️  Attention - Sampled a new account for the wallet, please save this securely:\n\n{account}\n"
+                        );
+                        vec![account]
+                    }
+                    false => private_key.iter().map(|key| Account::from_str(key)).collect::<Result<Vec<_>>>()?,
+                };
+
+                // Encrypt the private keys with the password, and write the wallet file.
+                let password = read_new_password("Enter a password for the wallet")?;
+                let mut keystore = Keystore::new(&accounts, &password, &mut ChaChaRng::from_entropy())?;
+                let mut addresses = accounts.iter().map(|account| account.address().to_string()).collect::<Vec<_>>();
+                for view_key in view_keys {
+                    addresses.push(format!("{} (watch-only)", keystore.add_watch_only(view_key)?));
+                }
+                keystore.save(&path)?;
+
+                Ok(format!("✅ Created the wallet '{}' for {}", path.display(), addresses.join(", ").bold()))
+            }
+            Self::Sign { transaction, private_key } => {
+                let partial = load_partial_transaction(&transaction)?;
+                let call = match partial.unsigned_call() {
+                    Some(call) => call,
+                    None => bail!("The transaction awaits a proof, run 'snarkos wallet prove' instead"),
+                };
+                println!(
+                    "🔏 Signing the call to '{}/{}' by {}...\n",
+                    call.program_id(),
+                    call.function_name(),
+                    call.signer()
+                );
+
+                // Sign the transaction, and write it back to the file.
+                let private_key = PrivateKey::<Network>::from_str(&private_key)?;
+                let signed = partial.sign(&private_key, &mut ChaChaRng::from_entropy())?;
+                save_partial_transaction(&transaction, &signed)?;
+                Ok(format!(
+                    "✅ Signed the transaction '{}', prove it next with 'snarkos wallet prove'",
+                    transaction.display()
+                ))
+            }
+            Self::Prove { transaction, query, broadcast, dry_run, store } => {
+                let partial = load_partial_transaction(&transaction)?;
+                // Initialize an RNG, the VM, and the query.
+                let rng = &mut ChaChaRng::from_entropy();
+                let vm = VM::from(ConsensusStore::<Network, ConsensusMemory<Network>>::open(None)?)?;
+                let query = Query::from(&query);
+
+                match partial {
+                    PartialTransaction::SignedExecution { .. } => {
+                        println!("📦 Proving the execution of the transaction...\n");
+                        let proven = partial.prove_execution(&vm, query, rng)?;
+                        save_partial_transaction(&transaction, &proven)?;
+                        Ok(format!(
+                            "✅ Proved the execution of '{}', sign its fee next with 'snarkos wallet sign'",
+                            transaction.display()
+                        ))
+                    }
+                    PartialTransaction::SignedFee { .. } => {
+                        // Ensure that the user has specified an action.
+                        if !dry_run && broadcast.is_none() && store.is_none() {
+                            bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
+                        }
+                        println!("📦 Proving the fee of the transaction...\n");
+                        let complete = partial.prove_fee::<Aleo, _, _>(&vm, query, rng)?;
+                        let operation = complete
+                            .transitions()
+                            .next()
+                            .map(|transition| format!("{}/{}", transition.program_id(), transition.function_name()))
+                            .unwrap_or_default();
+                        Developer::handle_transaction(broadcast, dry_run, store, complete, operation)
+                    }
+                    _ => bail!("The transaction awaits a signature, run 'snarkos wallet sign' instead"),
+                }
+            }
+            Self::History { format, output, token, endpoint } => {
+                let url = format!("{}/testnet3/wallet/history?format={format}", endpoint.trim_end_matches('/'));
+                let history = match ureq::get(&url).set("Authorization", &format!("Bearer {token}")).call() {
+                    Ok(response) => response.into_string()?,
+                    Err(ureq::Error::Status(code, response)) => {
+                        bail!("The node responded with status code {code}: {}", response.into_string()?)
+                    }
+                    Err(error) => bail!("Failed to reach the node at '{endpoint}' - {error}"),
+                };
+
+                match output {
+                    Some(path) => {
+                        std::fs::write(&path, history)?;
+                        Ok(format!("✅ Exported the transaction history to '{}'", path.display()))
+                    }
+                    None => Ok(history),
+                }
+            }
+        }
+    }
+}
+
+/// Loads the partial transaction from the file at the given path.
+fn load_partial_transaction(path: &Path) -> Result<PartialTransaction<Network>> {
+    let bytes = std::fs::read(path)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Saves the partial transaction to the file at the given path.
+fn save_partial_transaction(path: &Path, partial: &PartialTransaction<Network>) -> Result<()> {
+    Ok(std::fs::write(path, serde_json::to_vec_pretty(partial)?)?)
+}
+
+/// Returns the password of a new wallet from the environment variable, or prompts for it twice on the terminal,
+/// so that a mistyped password never locks the wallet.
+pub(crate) fn read_new_password(prompt: &str) -> Result<String> {
+    if let Ok(password) = std::env::var(WALLET_PASSWORD_ENV) {
+        return Ok(password);
+    }
+    let password = prompt_password(prompt)?;
+    if password.is_empty() {
+        bail!("The wallet password must not be empty");
+    }
+    if prompt_password("Confirm the password")? != password {
+        bail!("The passwords do not match");
+    }
+    Ok(password)
+}
+
+/// Prompts for a password on the terminal, without echoing it.
+/// Note: If the standard input is not a terminal (e.g. it is piped), the password is read as a line.
+fn prompt_password(prompt: &str) -> Result<String> {
+    print!("{prompt}: ");
+    std::io::stdout().flush()?;
+    if !std::io::stdin().is_tty() {
+        let mut password = String::new();
+        std::io::stdin().read_line(&mut password)?;
+        return Ok(password.trim_end_matches(['\r', '\n']).to_string());
+    }
+
+    // Read the password in raw mode, so that it is not echoed.
+    terminal::enable_raw_mode()?;
+    let mut password = String::new();
+    let result = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Enter => break Ok(password),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(anyhow!("The password entry was cancelled"));
+                }
+                KeyCode::Char(c) => password.push(c),
+                KeyCode::Backspace => {
+                    password.pop();
+                }
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(error) => break Err(error.into()),
+        }
+    };
+    // Restore the terminal, and end the line, as the newline is not echoed either.
+    terminal::disable_raw_mode()?;
+    println!();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{Address, PrivateKey};
+
+    #[test]
+    fn test_new() {
+        std::env::set_var(WALLET_PASSWORD_ENV, "password");
+        let path = std::env::temp_dir().join("snarkos-test-cli-wallet.json");
+        let private_key = "APrivateKey1zkp2n22c19hNdGF8wuEoQcuiyuWbquY6up4CtG5DYKqPX2X".to_string();
+
+        // Ensure the wallet file stores the encrypted private key.
+        let new = || Wallet::New { path: path.clone(), private_key: vec![private_key.clone()], view_key: vec![] };
+        new().parse().unwrap();
+        let keystore = Keystore::<Network>::load(&path).unwrap();
+        assert_eq!(keystore.decrypt("password").unwrap(), vec![PrivateKey::from_str(&private_key).unwrap()]);
+
+        // Ensure an existing wallet file is not overwritten.
+        assert!(new().parse().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_new_watch_only() {
+        std::env::set_var(WALLET_PASSWORD_ENV, "password");
+        let path = std::env::temp_dir().join("snarkos-test-cli-wallet-watch-only.json");
+        let view_key = "AViewKey1pNxZHn79XVJ4D2WG5Vn2YWsAzf5wzAs3dAuQtUAmUFF7".to_string();
+
+        // Ensure the wallet file stores the watch-only account, without sampling a new account.
+        Wallet::New { path: path.clone(), private_key: vec![], view_key: vec![view_key.clone()] }.parse().unwrap();
+        let keystore = Keystore::<Network>::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let address = Address::try_from(ViewKey::from_str(&view_key).unwrap()).unwrap();
+        assert_eq!(keystore.watch_only().collect::<Vec<_>>(), vec![address]);
+        assert!(keystore.decrypt("password").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_history() {
+        // Ensure the history is exported to CSV by default.
+        let history = Wallet::try_parse_from(["snarkos", "history", "--token", "jwt"].iter()).unwrap();
+        assert!(matches!(history, Wallet::History { format: HistoryFormat::Csv, output: None, .. }));
+
+        let history = Wallet::try_parse_from(["snarkos", "history", "--token", "jwt", "-f", "json"].iter()).unwrap();
+        assert!(matches!(history, Wallet::History { format: HistoryFormat::Json, .. }));
+
+        // Ensure an unknown format is rejected.
+        assert!(Wallet::try_parse_from(["snarkos", "history", "--token", "jwt", "-f", "xml"].iter()).is_err());
+    }
+}
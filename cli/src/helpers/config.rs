@@ -0,0 +1,252 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::{LogFormat, LogRotation};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+/// The file name of the node configuration, in the data directory.
+pub const CONFIG_FILE_NAME: &str = "snarkos.toml";
+
+/// The configuration of a node, as stored in `snarkos.toml`.
+/// Each setting is optional, and is overridden by the corresponding environment variable or command-line flag.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The network settings.
+    pub network: NetworkConfig,
+    /// The settings of the REST, gRPC, and metrics servers.
+    pub rest: RestConfig,
+    /// The storage settings.
+    pub storage: StorageConfig,
+    /// The prover (i.e. miner) settings.
+    pub prover: ProverConfig,
+    /// The logging settings.
+    pub logging: LoggingConfig,
+}
+
+/// The `[network]` section of the configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkConfig {
+    /// The network ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u16>,
+    /// The IP address and port for the node server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node: Option<SocketAddr>,
+    /// The IP addresses and ports of the peers to connect to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect: Option<Vec<SocketAddr>>,
+    /// The CDN to prefetch initial blocks from, or an empty string to disable it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cdn: Option<String>,
+    /// The unique ID of this node in development mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dev: Option<u16>,
+}
+
+/// The `[rest]` section of the configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RestConfig {
+    /// If `false`, the node will not initialize the REST server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// The IP address and port for the REST server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<SocketAddr>,
+    /// If `true`, the REST server will require a JWT token for the read-only routes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<bool>,
+    /// The maximum number of REST requests per second from each IP address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rps: Option<u32>,
+    /// The origins allowed to make cross-origin REST requests (any origin if empty).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cors_origins: Option<Vec<String>>,
+    /// The IP address and port for the gRPC server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grpc: Option<SocketAddr>,
+    /// The IP address and port for the Prometheus metrics server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<SocketAddr>,
+}
+
+/// The `[storage]` section of the configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    /// The path to a custom genesis block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genesis: Option<PathBuf>,
+    /// The path to an encrypted wallet file, whose records the node will track.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<PathBuf>,
+}
+
+/// The `[prover]` section of the configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProverConfig {
+    /// The account private key of the prover, which specifies this node as a prover.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+}
+
+/// The `[logging]` section of the configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// The verbosity of the node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<u8>,
+    /// The path to the file where logs will be stored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile: Option<PathBuf>,
+    /// The verbosity of the log file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile_verbosity: Option<u8>,
+    /// The size in megabytes past which the log file is rotated (0 for unbounded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile_max_size: Option<u64>,
+    /// The interval at which the log file is rotated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile_rotation: Option<LogRotation>,
+    /// The number of rotated log files that are retained.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile_max_files: Option<usize>,
+    /// The format of the logs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<LogFormat>,
+    /// The log filter directives per module, separated by commas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// If `false`, the node will not render the display.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<bool>,
+}
+
+impl Config {
+    /// Returns the default path of the configuration, in the given data directory.
+    pub fn default_path(data_dir: &Path) -> PathBuf {
+        data_dir.join(CONFIG_FILE_NAME)
+    }
+
+    /// Loads the configuration from the given path.
+    /// If `required` is `false`, a missing file is treated as an empty configuration.
+    pub fn load(path: &Path, required: bool) -> Result<Self> {
+        if !required && !path.exists() {
+            return Ok(Self::default());
+        }
+        let string = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read the configuration at '{}' - {e}", path.display()))?;
+        toml::from_str(&string).map_err(|e| anyhow!("Failed to parse the configuration at '{}' - {e}", path.display()))
+    }
+
+    /// Returns the configuration as a TOML string.
+    pub fn to_toml_string(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Saves the configuration to the given path, creating its parent directories if needed.
+    /// Note: On Unix, the file is only readable by its owner, as it may contain a private key.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::File::create(path)
+            .map_err(|e| anyhow!("Failed to write the configuration at '{}' - {e}", path.display()))?;
+        // Restrict the permissions before the contents are written, in case the file already existed.
+        #[cfg(target_family = "unix")]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+        std::io::Write::write_all(&mut &file, self.to_toml_string()?.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_config() {
+        let path = std::env::temp_dir().join("snarkos-test-config.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [network]
+            node = "0.0.0.0:4140"
+            connect = ["1.2.3.4:4130", "5.6.7.8:4130"]
+
+            [rest]
+            enabled = false
+            rps = 20
+
+            [logging]
+            verbosity = 3
+            format = "json"
+            logfile_rotation = "daily"
+            "#,
+        )
+        .unwrap();
+
+        let config = Config::load(&path, true).unwrap();
+        assert_eq!(config.network.node, Some("0.0.0.0:4140".parse().unwrap()));
+        assert_eq!(config.network.connect.as_ref().map(Vec::len), Some(2));
+        assert_eq!(config.network.cdn, None);
+        assert_eq!(config.rest.enabled, Some(false));
+        assert_eq!(config.rest.rps, Some(20));
+        assert_eq!(config.logging.verbosity, Some(3));
+        assert_eq!(config.logging.format, Some(LogFormat::Json));
+        assert_eq!(config.logging.logfile_rotation, Some(LogRotation::Daily));
+        assert_eq!(config.prover, ProverConfig::default());
+
+        // Ensure the configuration round-trips through TOML.
+        assert_eq!(toml::from_str::<Config>(&config.to_toml_string().unwrap()).unwrap(), config);
+
+        // Ensure an unknown setting is rejected.
+        std::fs::write(&path, "[network]\nport = 4130\n").unwrap();
+        assert!(Config::load(&path, true).is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        // Ensure a missing file is only rejected if it is required.
+        assert_eq!(Config::load(&path, false).unwrap(), Config::default());
+        assert!(Config::load(&path, true).is_err());
+    }
+
+    #[test]
+    fn test_save_config() {
+        let path = std::env::temp_dir().join("snarkos-test-save-config").join(CONFIG_FILE_NAME);
+        let config =
+            Config { prover: ProverConfig { private_key: Some("APrivateKey1xx".to_string()) }, ..Default::default() };
+
+        // Ensure the saved configuration is loaded back.
+        config.save(&path).unwrap();
+        assert_eq!(Config::load(&path, true).unwrap(), config);
+
+        // Ensure the configuration is only readable by its owner.
+        #[cfg(target_family = "unix")]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, bail, Result};
+use std::path::Path;
+
+/// The file name of the PID file of a node running in the background, in the data directory.
+pub const PID_FILE_NAME: &str = "snarkos.pid";
+/// The file name to which a node running in the background writes its standard error, in the data directory.
+pub const STDERR_FILE_NAME: &str = "snarkos.err";
+
+/// Returns the PID in the PID file at the given path, if the file exists.
+pub fn read_pid_file(path: &Path) -> Result<Option<u32>> {
+    match std::fs::read_to_string(path) {
+        Ok(string) => Ok(Some(string.trim().parse().map_err(|_| anyhow!("Invalid PID file at '{}'", path.display()))?)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => bail!("Failed to read the PID file at '{}' - {error}", path.display()),
+    }
+}
+
+/// The PID file of a running node, which the node holds an exclusive lock on until it exits,
+/// so that the PID file of a node that exited without removing it (e.g. on a crash) is never mistaken for a running node.
+#[cfg(target_family = "unix")]
+pub struct PidFile {
+    /// The path to the PID file.
+    path: std::path::PathBuf,
+    /// The open PID file, whose lock is released once it is closed.
+    _file: std::fs::File,
+}
+
+#[cfg(target_family = "unix")]
+impl PidFile {
+    /// Locks the PID file at the given path, and writes the PID of this process to it.
+    pub fn create(path: &Path) -> Result<Self> {
+        use std::io::Write;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Note: The file is only truncated once it is locked, so that the PID of a running node is never erased.
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(|e| anyhow!("Failed to open the PID file at '{}' - {e}", path.display()))?;
+        if !try_lock(&file)? {
+            bail!("snarkOS is already running (the PID file at '{}' is locked)", path.display());
+        }
+        file.set_len(0)?;
+        writeln!(file, "{}", std::process::id())?;
+        file.sync_all()?;
+        Ok(Self { path: path.to_path_buf(), _file: file })
+    }
+
+    /// Removes the PID file, and releases its lock.
+    pub fn remove(self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Attempts to lock the given file exclusively, and returns `false` if another open file holds the lock.
+#[cfg(target_family = "unix")]
+fn try_lock(file: &std::fs::File) -> Result<bool> {
+    use nix::{
+        errno::Errno,
+        fcntl::{flock, FlockArg},
+    };
+    use std::os::unix::io::AsRawFd;
+
+    match flock(file.as_raw_fd(), FlockArg::LockExclusiveNonblock) {
+        Ok(()) => Ok(true),
+        Err(Errno::EWOULDBLOCK) => Ok(false),
+        Err(error) => bail!("Failed to lock the PID file - {error}"),
+    }
+}
+
+/// Returns the PID in the PID file at the given path, if a running node holds its lock.
+/// Note: The PID file of a node that exited without removing it (e.g. on a crash) is not locked, and is ignored.
+#[cfg(target_family = "unix")]
+pub fn running_pid(path: &Path) -> Result<Option<u32>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => bail!("Failed to open the PID file at '{}' - {error}", path.display()),
+    };
+    // Note: If the lock is acquired, it is released once the file is closed.
+    match try_lock(&file)? {
+        true => Ok(None),
+        false => read_pid_file(path),
+    }
+}
+
+/// Returns `true` if a process with the given PID is running.
+#[cfg(target_family = "unix")]
+pub fn is_running(pid: u32) -> bool {
+    use nix::{errno::Errno, sys::signal::kill, unistd::Pid};
+    // Note: A process that is owned by another user cannot be signaled, but is still running.
+    !matches!(kill(Pid::from_raw(pid as i32), None), Err(Errno::ESRCH))
+}
+
+/// Restarts this process in the background, without the `--daemon` flag and without the display,
+/// with its standard input and output discarded, and its standard error appended to the given file.
+/// Waits for the new process to write its PID to the given PID file, and returns it.
+#[cfg(target_family = "unix")]
+pub fn daemonize(pid_file: &Path, stderr_file: &Path) -> Result<u32> {
+    use std::{
+        os::unix::process::CommandExt,
+        process::{Command, Stdio},
+        time::{Duration, Instant},
+    };
+
+    // Ensure the node is not already running in the background.
+    if let Some(pid) = running_pid(pid_file)? {
+        bail!("snarkOS is already running in the background (PID {pid}), stop it with 'snarkos stop'");
+    }
+    if let Some(parent) = pid_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let stderr = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(stderr_file)
+        .map_err(|e| anyhow!("Failed to open '{}' - {e}", stderr_file.display()))?;
+
+    // Restart this process with the same arguments, except for the `--daemon` flag.
+    let args = std::env::args_os().skip(1).filter(|arg| arg != "--daemon");
+    let mut child = Command::new(std::env::current_exe()?)
+        .args(args)
+        .env("SNARKOS_NODISPLAY", "true")
+        // The process writes its own PID file, and removes it once it shuts down.
+        .env("SNARKOS_PID_FILE", pid_file)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(stderr)
+        // Detach the process from the process group of the terminal, so that it does not receive its signals.
+        .process_group(0)
+        .spawn()?;
+    let pid = child.id();
+
+    // Wait for the node to write its PID file, and ensure it did not exit immediately (e.g. on an invalid setting).
+    let start = Instant::now();
+    while running_pid(pid_file).ok().flatten() != Some(pid) {
+        if let Some(status) = child.try_wait()? {
+            bail!("snarkOS exited in the background ({status}), see '{}'", stderr_file.display());
+        }
+        if start.elapsed() > Duration::from_secs(10) {
+            bail!("snarkOS (PID {pid}) did not write its PID file at '{}'", pid_file.display());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    // Ensure the node did not exit right after writing its PID file.
+    std::thread::sleep(Duration::from_secs(1));
+    if let Some(status) = child.try_wait()? {
+        bail!("snarkOS exited in the background ({status}), see '{}'", stderr_file.display());
+    }
+    Ok(pid)
+}
+
+/// Signals the process with the given PID to shut down, and waits for it to exit, until the given timeout.
+#[cfg(target_family = "unix")]
+pub fn stop(pid: u32, timeout: std::time::Duration) -> Result<()> {
+    use nix::{
+        sys::signal::{kill, Signal},
+        unistd::Pid,
+    };
+    use std::time::{Duration, Instant};
+
+    kill(Pid::from_raw(pid as i32), Signal::SIGTERM).map_err(|e| anyhow!("Failed to signal PID {pid} - {e}"))?;
+    // Wait for the node to save its state and exit.
+    let start = Instant::now();
+    while is_running(pid) {
+        if start.elapsed() > timeout {
+            bail!("snarkOS (PID {pid}) did not stop within {} seconds", timeout.as_secs());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_pid_file() {
+        let path = std::env::temp_dir().join("snarkos-test-read-pid-file.pid");
+        // Ensure a missing PID file is not an error.
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(read_pid_file(&path).unwrap(), None);
+        // Ensure the PID is read, and an invalid PID is rejected.
+        std::fs::write(&path, "1234\n").unwrap();
+        assert_eq!(read_pid_file(&path).unwrap(), Some(1234));
+        std::fs::write(&path, "invalid").unwrap();
+        assert!(read_pid_file(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_pid_file() {
+        let path = std::env::temp_dir().join("snarkos-test-pid-file.pid");
+        // Ensure a PID file that is not locked (e.g. of a node that crashed) is ignored.
+        std::fs::write(&path, "1234\n").unwrap();
+        assert_eq!(running_pid(&path).unwrap(), None);
+
+        // Ensure the PID file is locked while it is held, and cannot be created twice.
+        let pid_file = PidFile::create(&path).unwrap();
+        assert_eq!(running_pid(&path).unwrap(), Some(std::process::id()));
+        assert!(PidFile::create(&path).is_err());
+
+        // Ensure the PID file is removed.
+        pid_file.remove();
+        assert!(!path.exists());
+        assert_eq!(running_pid(&path).unwrap(), None);
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_is_running() {
+        assert!(is_running(std::process::id()));
+    }
+}
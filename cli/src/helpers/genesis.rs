@@ -0,0 +1,187 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{
+    block::{Block, ConfirmedTransaction, Header, Transactions},
+    store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    Address,
+    FromBytes,
+    Network,
+    PrivateKey,
+    ToBytes,
+    VM,
+};
+
+use anyhow::{anyhow, ensure, Result};
+use rand::{CryptoRng, Rng};
+use std::path::Path;
+
+/// A builder of a genesis block, which allocates the starting supply of the network to the given addresses.
+/// Note: snarkVM only accepts a genesis block with the timestamp and the coinbase and proof targets of the network
+/// (`N::GENESIS_TIMESTAMP`, `N::GENESIS_COINBASE_TARGET`, and `N::GENESIS_PROOF_TARGET`), so they cannot be set.
+pub struct GenesisBuilder<N: Network> {
+    /// The private key of the beacon, which signs the genesis block.
+    private_key: PrivateKey<N>,
+    /// The number of microcredits allocated to each address.
+    allocations: Vec<(Address<N>, u64)>,
+}
+
+impl<N: Network> GenesisBuilder<N> {
+    /// Initializes a new builder, which allocates the starting supply to the beacon unless allocations are added.
+    pub fn new(private_key: PrivateKey<N>) -> Self {
+        Self { private_key, allocations: Vec::new() }
+    }
+
+    /// Allocates the given number of microcredits to the given address.
+    pub fn allocate(mut self, address: Address<N>, microcredits: u64) -> Self {
+        self.allocations.push((address, microcredits));
+        self
+    }
+
+    /// Returns the allocations of the genesis block, which must add up to the starting supply.
+    fn allocations(&self) -> Result<Vec<(Address<N>, u64)>> {
+        if self.allocations.is_empty() {
+            return Ok(vec![(Address::try_from(&self.private_key)?, N::STARTING_SUPPLY)]);
+        }
+        ensure!(
+            self.allocations.len() <= Block::<N>::NUM_GENESIS_TRANSACTIONS,
+            "A genesis block has at most {} allocations, found {}",
+            Block::<N>::NUM_GENESIS_TRANSACTIONS,
+            self.allocations.len()
+        );
+        ensure!(self.allocations.iter().all(|(_, amount)| *amount > 0), "Each allocation must be greater than zero");
+        let total = self
+            .allocations
+            .iter()
+            .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+            .ok_or_else(|| anyhow!("The allocations overflow"))?;
+        ensure!(
+            total == N::STARTING_SUPPLY,
+            "The allocations must add up to the starting supply of {} microcredits, found {total}",
+            N::STARTING_SUPPLY
+        );
+        Ok(self.allocations.clone())
+    }
+
+    /// Builds the genesis block, in which each transaction mints (part of) an allocation.
+    pub fn build<R: Rng + CryptoRng>(&self, rng: &mut R) -> Result<Block<N>> {
+        // Split the largest allocation in half, until there is one allocation for each genesis transaction.
+        let mut mints = self.allocations()?;
+        while mints.len() < Block::<N>::NUM_GENESIS_TRANSACTIONS {
+            let index = (0..mints.len()).max_by_key(|index| mints[*index].1).unwrap_or_default();
+            let (address, amount) = mints[index];
+            mints[index].1 = amount - amount / 2;
+            mints.insert(index + 1, (address, amount / 2));
+        }
+
+        // Mint each allocation in a transaction.
+        let vm = VM::from(ConsensusStore::<N, ConsensusMemory<N>>::open(None)?)?;
+        let transactions = mints
+            .iter()
+            .enumerate()
+            .map(|(index, (address, amount))| {
+                let inputs = [address.to_string(), format!("{amount}_u64")];
+                let transaction =
+                    vm.execute(&self.private_key, ("credits.aleo", "mint"), inputs.iter(), None, None, rng)?;
+                ConfirmedTransaction::accepted_execute(u32::try_from(index)?, transaction, vec![])
+            })
+            .collect::<Result<Transactions<N>>>()?;
+
+        // Construct the genesis block.
+        let header = Header::genesis(&transactions)?;
+        let block = Block::new(&self.private_key, N::BlockHash::default(), header, transactions, vec![], None, rng)?;
+        ensure!(block.is_genesis(), "Failed to build a genesis block");
+        Ok(block)
+    }
+}
+
+/// Returns `true` if the given path stores the genesis block as JSON, instead of as bytes.
+fn is_json(path: &Path) -> bool {
+    path.extension().map_or(false, |extension| extension == "json")
+}
+
+/// Loads the genesis block from the given path, as JSON if the path ends in `.json`, or as bytes otherwise.
+pub fn load_genesis<N: Network>(path: &Path) -> Result<Block<N>> {
+    let bytes =
+        std::fs::read(path).map_err(|e| anyhow!("Failed to read the genesis block at '{}' - {e}", path.display()))?;
+    let genesis: Block<N> = match is_json(path) {
+        true => serde_json::from_slice(&bytes)?,
+        false => Block::from_bytes_le(&bytes)?,
+    };
+    // Ensure the block is a genesis block.
+    ensure!(genesis.header().is_genesis(), "The block at '{}' is not a genesis block", path.display());
+    Ok(genesis)
+}
+
+/// Saves the genesis block to the given path, as JSON if the path ends in `.json`, or as bytes otherwise.
+pub fn save_genesis<N: Network>(genesis: &Block<N>, path: &Path) -> Result<()> {
+    let bytes = match is_json(path) {
+        true => serde_json::to_vec_pretty(genesis)?,
+        false => genesis.to_bytes_le()?,
+    };
+    std::fs::write(path, bytes).map_err(|e| anyhow!("Failed to write the genesis block to '{}' - {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkos_account::Account;
+    use snarkos_node::wallet::Wallet;
+    use snarkvm::prelude::{Ledger, TestRng, Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_save_and_load_genesis() {
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+
+        for extension in ["json", "bin"] {
+            let path = std::env::temp_dir().join(format!("snarkos-test-genesis-{}.{extension}", std::process::id()));
+            // Ensure the genesis block round-trips through the file.
+            save_genesis(&genesis, &path).unwrap();
+            assert_eq!(load_genesis::<CurrentNetwork>(&path).unwrap(), genesis);
+            std::fs::remove_file(&path).unwrap();
+        }
+        // Ensure a missing file is rejected.
+        let path = std::env::temp_dir().join(format!("snarkos-test-missing-genesis-{}.bin", std::process::id()));
+        assert!(load_genesis::<CurrentNetwork>(&path).is_err());
+    }
+
+    #[test]
+    fn test_genesis_builder() {
+        let rng = &mut TestRng::default();
+        let beacon = Account::<CurrentNetwork>::new(rng).unwrap();
+        let other = Account::<CurrentNetwork>::new(rng).unwrap();
+        let supply = CurrentNetwork::STARTING_SUPPLY;
+
+        // Ensure the allocations must add up to the starting supply.
+        let builder = || GenesisBuilder::new(*beacon.private_key());
+        assert!(builder().allocate(other.address(), supply - 1).build(rng).is_err());
+        assert!(builder().allocate(other.address(), supply).allocate(beacon.address(), 0).build(rng).is_err());
+
+        // Ensure the genesis block allocates the starting supply to each address, and is accepted by the ledger.
+        let genesis = builder()
+            .allocate(beacon.address(), supply - 1_000_000)
+            .allocate(other.address(), 1_000_000)
+            .build(rng)
+            .unwrap();
+        assert!(genesis.is_genesis());
+        assert_eq!(genesis.transactions().len(), Block::<CurrentNetwork>::NUM_GENESIS_TRANSACTIONS);
+        let wallet = Wallet::new(vec![beacon.clone(), other.clone()]);
+        wallet.add_block(&genesis).unwrap();
+        assert_eq!(wallet.balance_of(&beacon.address()), supply - 1_000_000);
+        assert_eq!(wallet.balance_of(&other.address()), 1_000_000);
+        assert!(Ledger::<CurrentNetwork, ConsensusMemory<_>>::load(genesis, None).is_ok());
+    }
+}
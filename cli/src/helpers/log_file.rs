@@ -0,0 +1,186 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The interval at which the log file is rotated, regardless of its size.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// The log file is only rotated once it exceeds the maximum size.
+    #[default]
+    Never,
+    /// The log file is rotated at the start of each hour (UTC).
+    Hourly,
+    /// The log file is rotated at the start of each day (UTC).
+    Daily,
+}
+
+impl LogRotation {
+    /// Returns the number of the rotation interval at the given time, or `None` if the log file is never rotated.
+    fn interval(&self, time: SystemTime) -> Option<u64> {
+        let seconds = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        match self {
+            Self::Never => None,
+            Self::Hourly => Some(seconds / 3600),
+            Self::Daily => Some(seconds / 86400),
+        }
+    }
+}
+
+/// The settings of the log file.
+#[derive(Clone, Debug)]
+pub struct LogFile {
+    /// The path to the log file.
+    pub path: PathBuf,
+    /// The verbosity of the log file, which is independent of the verbosity of the node.
+    pub verbosity: u8,
+    /// The size in bytes past which the log file is rotated, or `0` if its size is unbounded.
+    pub max_size: u64,
+    /// The interval at which the log file is rotated.
+    pub rotation: LogRotation,
+    /// The number of rotated log files that are retained.
+    pub max_files: usize,
+}
+
+/// A log file, which is rotated once it exceeds its maximum size, or once per rotation interval.
+/// The rotated files are renamed from `<path>.1` (the newest) to `<path>.<max_files>` (the oldest),
+/// and the oldest file is removed on each rotation, so the logs on disk stay bounded.
+pub struct RotatingFile {
+    /// The settings of the log file.
+    settings: LogFile,
+    /// The log file.
+    file: File,
+    /// The size of the log file in bytes.
+    size: u64,
+    /// The rotation interval of the log file, if it is rotated periodically.
+    interval: Option<u64>,
+}
+
+impl RotatingFile {
+    /// Opens the log file with the given settings, appending to it if it exists.
+    pub fn open(settings: LogFile) -> io::Result<Self> {
+        let file = Self::open_file(&settings.path)?;
+        let metadata = file.metadata()?;
+        // A log file that was last written in a previous interval is rotated on the first write.
+        let interval = settings.rotation.interval(metadata.modified().unwrap_or_else(|_| SystemTime::now()));
+        Ok(Self { settings, file, size: metadata.len(), interval })
+    }
+
+    /// Opens the file at the given path for appending.
+    fn open_file(path: &Path) -> io::Result<File> {
+        File::options().append(true).create(true).open(path)
+    }
+
+    /// Returns the path of the rotated log file with the given index.
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.settings.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    /// Rotates the log file, removing the oldest rotated file, and starts a new log file.
+    fn rotate(&mut self) -> io::Result<()> {
+        // Shift each rotated file by one index, so that the oldest file is overwritten.
+        for index in (1..=self.settings.max_files).rev() {
+            let path = match index {
+                1 => self.settings.path.clone(),
+                _ => self.rotated_path(index - 1),
+            };
+            if path.exists() {
+                std::fs::rename(path, self.rotated_path(index))?;
+            }
+        }
+        // If no rotated files are retained, discard the log file.
+        if self.settings.max_files == 0 {
+            std::fs::remove_file(&self.settings.path)?;
+        }
+        // Start a new log file.
+        self.file = Self::open_file(&self.settings.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    /// Writes the given buffer to the log file, rotating it first if necessary.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Check if the log file is full, or if a new rotation interval has started.
+        let max_size = self.settings.max_size;
+        let is_full = max_size > 0 && self.size > 0 && self.size + buf.len() as u64 > max_size;
+        let interval = self.settings.rotation.interval(SystemTime::now());
+        if is_full || interval != self.interval {
+            self.rotate()?;
+            self.interval = interval;
+        }
+
+        let num_bytes = self.file.write(buf)?;
+        self.size += num_bytes as u64;
+        Ok(num_bytes)
+    }
+
+    /// Flushes the log file.
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotating_file() {
+        let directory = std::env::temp_dir().join("snarkos-test-log-file");
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("snarkos.log");
+
+        let settings =
+            LogFile { path: path.clone(), verbosity: 2, max_size: 10, rotation: LogRotation::Never, max_files: 2 };
+        let mut file = RotatingFile::open(settings).unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+
+        // Ensure each line that exceeds the maximum size starts a new file, and only 2 rotated files are retained.
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fourth\n");
+        assert_eq!(std::fs::read_to_string(directory.join("snarkos.log.1")).unwrap(), "third\n");
+        assert_eq!(std::fs::read_to_string(directory.join("snarkos.log.2")).unwrap(), "second\n");
+        assert!(!directory.join("snarkos.log.3").exists());
+
+        // Ensure the log file is appended to when it is reopened.
+        let settings =
+            LogFile { path: path.clone(), verbosity: 2, max_size: 0, rotation: LogRotation::Never, max_files: 2 };
+        let mut file = RotatingFile::open(settings).unwrap();
+        file.write_all(b"fifth\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fourth\nfifth\n");
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_log_rotation_interval() {
+        let time = UNIX_EPOCH + std::time::Duration::from_secs(2 * 86400 + 5 * 3600 + 1);
+        assert_eq!(LogRotation::Never.interval(time), None);
+        assert_eq!(LogRotation::Hourly.interval(time), Some(2 * 24 + 5));
+        assert_eq!(LogRotation::Daily.interval(time), Some(2));
+    }
+}
@@ -20,6 +20,8 @@ pub enum LogWriter {
     Stdout(io::Stdout),
     /// Writes to a channel.
     Sender(mpsc::Sender<Vec<u8>>),
+    /// Writes to the recent logs, which are served by the REST API.
+    Recent,
 }
 
 impl LogWriter {
@@ -39,6 +41,10 @@ impl io::Write for LogWriter {
                 let _ = sender.try_send(log);
                 Ok(buf.len())
             }
+            Self::Recent => {
+                snarkos_node_rest::record_log_line(&String::from_utf8_lossy(buf));
+                Ok(buf.len())
+            }
         }
     }
 
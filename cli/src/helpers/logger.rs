@@ -12,52 +12,63 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::LogWriter;
+use crate::helpers::{LogFile, LogWriter, RotatingFile};
 
+use anyhow::Result;
 use crossterm::tty::IsTty;
-use std::{fs::File, io, path::Path};
+use serde::{Deserialize, Serialize};
+use std::{io, sync::Mutex};
 use tokio::sync::mpsc;
 use tracing_subscriber::{
+    fmt::MakeWriter,
     layer::{Layer, SubscriberExt},
+    reload,
     util::SubscriberInitExt,
     EnvFilter,
+    Registry,
 };
 
-/// Initializes the logger.
-pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile: P) -> mpsc::Receiver<Vec<u8>> {
-    match verbosity {
-        0 => std::env::set_var("RUST_LOG", "info"),
-        1 => std::env::set_var("RUST_LOG", "debug"),
-        2 | 3 | 4 => std::env::set_var("RUST_LOG", "trace"),
-        _ => std::env::set_var("RUST_LOG", "info"),
-    };
+/// The format of the logs.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines, with the fields of the spans as prefixes.
+    #[default]
+    Text,
+    /// One JSON object per line, with the fields of the event and its spans.
+    Json,
+}
 
-    // Filter out undesirable logs. (unfortunately EnvFilter cannot be cloned)
-    let [filter, filter2] = std::array::from_fn(|_| {
-        let filter = EnvFilter::from_default_env()
-            .add_directive("mio=off".parse().unwrap())
-            .add_directive("tokio_util=off".parse().unwrap())
-            .add_directive("hyper=off".parse().unwrap())
-            .add_directive("reqwest=off".parse().unwrap())
-            .add_directive("want=off".parse().unwrap())
-            .add_directive("warp=off".parse().unwrap());
-
-        if verbosity > 3 {
-            filter.add_directive("snarkos_node_tcp=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_tcp=off".parse().unwrap())
-        }
-    });
+/// Initializes the logger.
+/// The given filter directives (e.g. `snarkos_node_router=debug,snarkos_node_consensus=trace`) refine the verbosity per module.
+/// The log file has its own verbosity, and is rotated according to its settings.
+pub fn initialize_logger(
+    verbosity: u8,
+    nodisplay: bool,
+    log_format: LogFormat,
+    directives: &str,
+    logfile: LogFile,
+) -> Result<mpsc::Receiver<Vec<u8>>> {
+    std::env::set_var("RUST_LOG", log_level(verbosity));
+    let logfile_verbosity = logfile.verbosity;
+
+    // Filter out undesirable logs.
+    let filter = log_filter(EnvFilter::from_default_env(), verbosity, directives)?;
+    let filter2 = log_filter(EnvFilter::new(log_level(logfile_verbosity)), logfile_verbosity, directives)?;
+    let filter3 = log_filter(EnvFilter::new(log_level(verbosity)), verbosity, directives)?;
+    // Allow the filters to be replaced at runtime.
+    let (filter, filter_handle) = reload::Layer::new(filter);
+    let (filter2, filter2_handle) = reload::Layer::new(filter2);
+    let (filter3, filter3_handle) = reload::Layer::new(filter3);
 
     // Create the directories tree for a logfile if it doesn't exist.
-    let logfile_dir = logfile.as_ref().parent().expect("Root directory passed as a logfile");
+    let logfile_dir = logfile.path.parent().expect("Root directory passed as a logfile");
     if !logfile_dir.exists() {
         std::fs::create_dir_all(logfile_dir)
             .expect("Failed to create a directories: '{logfile_dir}', please check if user has permissions");
     }
-    // Create a file to write logs to.
-    let logfile =
-        File::options().append(true).create(true).open(logfile).expect("Failed to open the file for writing logs");
+    // Create a file to write logs to, which is rotated as it grows.
+    let logfile = RotatingFile::open(logfile).expect("Failed to open the file for writing logs");
 
     // Initialize the log channel.
     let (log_sender, log_receiver) = mpsc::channel(1024);
@@ -67,28 +78,75 @@ pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile
         true => None,
         false => Some(log_sender),
     };
+    let ansi = log_sender.is_none() && io::stdout().is_tty();
 
     // Initialize tracing.
     let _ = tracing_subscriber::registry()
-        .with(
+        .with(vec![
             // Add layer using LogWriter for stdout / terminal
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(log_sender.is_none() && io::stdout().is_tty())
-                .with_writer(move || LogWriter::new(&log_sender))
-                .with_target(verbosity > 2)
-                .with_filter(filter),
-        )
-        .with(
+            fmt_layer(log_format, ansi, verbosity > 2, move || LogWriter::new(&log_sender)).with_filter(filter).boxed(),
             // Add layer redirecting logs to the file
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(false)
-                .with_writer(logfile)
-                .with_target(verbosity > 2)
-                .with_filter(filter2),
-        )
+            fmt_layer(log_format, false, logfile_verbosity > 2, Mutex::new(logfile)).with_filter(filter2).boxed(),
+            // Add layer recording the recent logs, which are served by the REST API
+            fmt_layer(LogFormat::Text, false, false, || LogWriter::Recent).with_filter(filter3).boxed(),
+        ])
         .try_init();
 
-    log_receiver
+    // Register the handler to change the log filter at runtime, e.g. from the REST API.
+    let _ = snarkos_node_rest::register_log_filter_handler(Box::new(move |directive| {
+        // Note: EnvFilter cannot be cloned, so the directive is parsed once for each filter.
+        filter_handle.reload(log_filter(EnvFilter::try_new(directive)?, verbosity, "")?)?;
+        filter2_handle.reload(log_filter(EnvFilter::try_new(directive)?, logfile_verbosity, "")?)?;
+        filter3_handle.reload(log_filter(EnvFilter::try_new(directive)?, verbosity, "")?)?;
+        Ok(())
+    }));
+
+    Ok(log_receiver)
+}
+
+/// Returns the log level corresponding to the given verbosity.
+fn log_level(verbosity: u8) -> &'static str {
+    match verbosity {
+        0 => "info",
+        1 => "debug",
+        2..=4 => "trace",
+        _ => "info",
+    }
+}
+
+/// Returns a layer that formats the logs in the given format, and writes them to the given writer.
+fn fmt_layer<W>(log_format: LogFormat, ansi: bool, target: bool, writer: W) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::Layer::default().with_ansi(ansi).with_writer(writer).with_target(target);
+    match log_format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    }
+}
+
+/// Returns the given filter, with the undesirable logs filtered out, and the given directives added.
+fn log_filter(filter: EnvFilter, verbosity: u8, directives: &str) -> Result<EnvFilter> {
+    let filter = filter
+        .add_directive("mio=off".parse().unwrap())
+        .add_directive("tokio_util=off".parse().unwrap())
+        .add_directive("hyper=off".parse().unwrap())
+        .add_directive("reqwest=off".parse().unwrap())
+        .add_directive("want=off".parse().unwrap())
+        .add_directive("warp=off".parse().unwrap());
+
+    let mut filter = if verbosity > 3 {
+        filter.add_directive("snarkos_node_tcp=trace".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_tcp=off".parse().unwrap())
+    };
+
+    // Add the given directives last, so that they override the directives above.
+    for directive in directives.split(',').map(str::trim).filter(|directive| !directive.is_empty()) {
+        filter = filter.add_directive(directive.parse()?);
+    }
+    Ok(filter)
 }
 
 /// Returns the welcome message as a string.
@@ -116,3 +174,20 @@ pub fn welcome_message() -> String {
     output += &"👋 Welcome to Aleo! We thank you for running a node and supporting privacy.\n".bold();
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_filter() {
+        // Ensure the directives override the verbosity of the given modules.
+        let filter = log_filter(EnvFilter::new("info"), 2, "snarkos_node_router=debug, snarkos_node_tcp=warn").unwrap();
+        let filter = filter.to_string();
+        assert!(filter.contains("snarkos_node_router=debug"));
+        assert!(filter.contains("snarkos_node_tcp=warn"));
+        assert!(!filter.contains("snarkos_node_tcp=off"));
+        // Ensure an invalid directive is rejected.
+        assert!(log_filter(EnvFilter::new("info"), 2, "snarkos_node_router=loud").is_err());
+    }
+}
@@ -15,6 +15,18 @@
 mod bech32m;
 pub use bech32m::*;
 
+mod config;
+pub use config::*;
+
+mod daemon;
+pub use daemon::*;
+
+mod genesis;
+pub use genesis::*;
+
+mod log_file;
+pub use log_file::*;
+
 mod log_writer;
 use log_writer::*;
 
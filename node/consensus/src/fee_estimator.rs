@@ -0,0 +1,84 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{block::Block, Network, ToBytes};
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use std::{collections::VecDeque, sync::Arc};
+
+/// The number of recent blocks used to estimate the fee rate.
+pub const NUM_FEE_ESTIMATION_BLOCKS: usize = 100;
+
+/// An estimator of the fee rate (in microcredits per byte) required for a transaction to be confirmed.
+#[derive(Clone, Debug, Default)]
+pub struct FeeEstimator {
+    /// The lowest fee rate confirmed in each recent block (that contains transactions), from oldest to newest.
+    min_fee_rates: Arc<RwLock<VecDeque<f64>>>,
+}
+
+impl FeeEstimator {
+    /// Initializes a new instance of the fee estimator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of recent blocks with transactions in the fee estimator.
+    pub fn num_blocks(&self) -> usize {
+        self.min_fee_rates.read().len()
+    }
+
+    /// Returns the estimated fee rate (in microcredits per byte) for a transaction to be confirmed
+    /// within the given number of blocks, or `None` if no transactions were recently confirmed.
+    ///
+    /// The estimate is the lowest fee rate that would have sufficed for inclusion in
+    /// at least one out of every `target_blocks` recent blocks.
+    pub fn estimate(&self, target_blocks: u32) -> Option<f64> {
+        let mut min_fee_rates = self.min_fee_rates.read().iter().copied().collect::<Vec<_>>();
+        if min_fee_rates.is_empty() {
+            return None;
+        }
+        min_fee_rates.sort_by(|a, b| a.total_cmp(b));
+
+        // Determine the number of blocks that must have accepted the fee rate.
+        let target_blocks = (target_blocks as usize).clamp(1, min_fee_rates.len());
+        let num_blocks = (min_fee_rates.len() + target_blocks - 1) / target_blocks;
+        Some(min_fee_rates[num_blocks - 1])
+    }
+
+    /// Adds the fee rates of the transactions in the given block to the fee estimator.
+    pub fn add_block<N: Network>(&self, block: &Block<N>) -> Result<()> {
+        let mut min_fee_rate = None::<f64>;
+        for confirmed in block.transactions().iter() {
+            let transaction = confirmed.transaction();
+            // Compute the fee rate of the transaction.
+            let fee_rate = *transaction.fee()? as f64 / transaction.to_bytes_le()?.len() as f64;
+            min_fee_rate = Some(min_fee_rate.map_or(fee_rate, |min_fee_rate| min_fee_rate.min(fee_rate)));
+        }
+        // Blocks without transactions do not carry information about the fee rate.
+        if let Some(min_fee_rate) = min_fee_rate {
+            self.insert_min_fee_rate(min_fee_rate);
+        }
+        Ok(())
+    }
+
+    /// Inserts the lowest fee rate of the latest block, removing the oldest block if the window is full.
+    pub(crate) fn insert_min_fee_rate(&self, min_fee_rate: f64) {
+        let mut min_fee_rates = self.min_fee_rates.write();
+        min_fee_rates.push_back(min_fee_rate);
+        while min_fee_rates.len() > NUM_FEE_ESTIMATION_BLOCKS {
+            min_fee_rates.pop_front();
+        }
+    }
+}
@@ -17,6 +17,12 @@
 #[macro_use]
 extern crate tracing;
 
+mod fee_estimator;
+pub use fee_estimator::*;
+
+mod limits;
+pub use limits::*;
+
 mod memory_pool;
 pub use memory_pool::*;
 
@@ -30,7 +36,17 @@ use snarkvm::prelude::{
     *,
 };
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use metrics::{gauge, histogram};
+use parking_lot::Mutex;
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
 
 #[derive(Clone)]
 pub struct Consensus<N: Network, C: ConsensusStorage<N>> {
@@ -38,6 +54,12 @@ pub struct Consensus<N: Network, C: ConsensusStorage<N>> {
     ledger: Ledger<N, C>,
     /// The memory pool.
     memory_pool: MemoryPool<N>,
+    /// The fee estimator.
+    fee_estimator: FeeEstimator,
+    /// The lock held while the ledger advances to the next block.
+    advance_lock: Arc<Mutex<()>>,
+    /// The boolean flag for whether consensus has shut down.
+    is_shut_down: Arc<AtomicBool>,
     /// The boolean flag for the development mode.
     #[allow(dead_code)]
     is_dev: bool,
@@ -46,7 +68,26 @@ pub struct Consensus<N: Network, C: ConsensusStorage<N>> {
 impl<N: Network, C: ConsensusStorage<N>> Consensus<N, C> {
     /// Initializes a new instance of consensus.
     pub fn new(ledger: Ledger<N, C>, is_dev: bool) -> Result<Self> {
-        Ok(Self { ledger, memory_pool: Default::default(), is_dev })
+        // Initialize the fee estimator from the recent blocks in the ledger.
+        // Note: A block that fails to be estimated is skipped, as the fee estimates are only advisory.
+        let fee_estimator = FeeEstimator::new();
+        let latest_height = ledger.latest_height();
+        let start_height = latest_height.saturating_sub(NUM_FEE_ESTIMATION_BLOCKS as u32 - 1);
+        for height in start_height..=latest_height {
+            if let Err(error) = ledger.get_block(height).and_then(|block| fee_estimator.add_block(&block)) {
+                warn!("Failed to add block {height} to the fee estimator - {error}");
+            }
+        }
+        // Initialize the metrics for the latest height.
+        gauge!(metrics::blocks::HEIGHT, latest_height as f64);
+        Ok(Self {
+            ledger,
+            memory_pool: Default::default(),
+            fee_estimator,
+            advance_lock: Default::default(),
+            is_shut_down: Default::default(),
+            is_dev,
+        })
     }
 
     /// Returns the ledger.
@@ -64,6 +105,11 @@ impl<N: Network, C: ConsensusStorage<N>> Consensus<N, C> {
         &self.memory_pool
     }
 
+    /// Returns the fee estimator.
+    pub const fn fee_estimator(&self) -> &FeeEstimator {
+        &self.fee_estimator
+    }
+
     /// Checks the given transaction is well-formed and unique.
     pub fn check_transaction_basic(&self, transaction: &Transaction<N>, rejected_id: Option<Field<N>>) -> Result<()> {
         self.ledger.check_transaction_basic(transaction, rejected_id)
@@ -71,7 +117,12 @@ impl<N: Network, C: ConsensusStorage<N>> Consensus<N, C> {
 
     /// Checks the given block is valid next block.
     pub fn check_next_block(&self, block: &Block<N>) -> Result<()> {
-        self.ledger.check_next_block(block)
+        let _span = debug_span!("check_block", height = block.height(), hash = %block.hash()).entered();
+        let timer = Instant::now();
+        let result = self.ledger.check_next_block(block);
+        // Record the time taken to validate the block.
+        histogram!(metrics::blocks::VALIDATION_TIME, timer.elapsed().as_secs_f64());
+        result
     }
 
     /// Adds the given unconfirmed transaction to the memory pool.
@@ -80,10 +131,22 @@ impl<N: Network, C: ConsensusStorage<N>> Consensus<N, C> {
         if self.memory_pool.contains_unconfirmed_transaction(transaction.id()) {
             bail!("Transaction is already in the memory pool.");
         }
+        // Ensure the transaction does not exceed the maximum transaction size (a local policy, not a consensus rule).
+        check_transaction_size(&transaction)?;
+        // Ensure the memory pool has room for the transaction.
+        if self.memory_pool.num_unconfirmed_transactions() >= self.memory_pool.max_unconfirmed_transactions() {
+            bail!("The memory pool is full.");
+        }
+        // Ensure the transaction pays at least the minimum fee.
+        let minimum_fee = self.memory_pool.minimum_fee();
+        if minimum_fee > 0 && *transaction.fee()? < minimum_fee {
+            bail!("Transaction '{}' pays less than the minimum fee of {minimum_fee} microcredits.", transaction.id());
+        }
         // Check that the transaction is well-formed and unique.
         self.check_transaction_basic(&transaction, None)?;
         // Insert the transaction to the memory pool.
         self.memory_pool.add_unconfirmed_transaction(&transaction);
+        self.update_memory_pool_metrics();
 
         Ok(())
     }
@@ -111,6 +174,7 @@ impl<N: Network, C: ConsensusStorage<N>> Consensus<N, C> {
 
         // Insert the solution to the memory pool.
         self.memory_pool.add_unconfirmed_solution(solution)?;
+        self.update_memory_pool_metrics();
 
         Ok(())
     }
@@ -145,15 +209,35 @@ impl<N: Network, C: ConsensusStorage<N>> Consensus<N, C> {
             self.memory_pool.candidate_solutions(self, latest_height, latest_proof_target, latest_coinbase_target)?;
 
         // Prepare the next block.
-        self.ledger.prepare_advance_to_next_block(private_key, transactions, prover_solutions, rng)
+        let block = self.ledger.prepare_advance_to_next_block(private_key, transactions, prover_solutions, rng)?;
+        // Ensure the block does not exceed the maximum block size.
+        // Note: The size limits are a local policy of this node, and not a consensus rule,
+        // so they are not enforced on the blocks of other nodes in `check_next_block`.
+        check_block_size(&block)?;
+        Ok(block)
     }
 
     /// Advances the ledger to the next block.
     pub fn advance_to_next_block(&self, block: &Block<N>) -> Result<()> {
+        // Attach the height and hash of the block to the logs.
+        let _span = info_span!("block", height = block.height(), hash = %block.hash()).entered();
+
+        // Hold the lock while the block is written, so a shutdown never interrupts the ledger mid-write.
+        let _lock = self.advance_lock.lock();
+        if self.is_shut_down.load(Ordering::SeqCst) {
+            bail!("Consensus has shut down");
+        }
+
         // Adds the next block to the ledger.
         let old_epoch = self.ledger.latest_epoch_number();
         self.ledger.advance_to_next_block(block)?;
 
+        // Add the fee rates of the confirmed transactions to the fee estimator.
+        // Note: The block is already in the ledger, so a failure to estimate its fees is not an error.
+        if let Err(error) = self.fee_estimator.add_block(block) {
+            warn!("Failed to add block {} to the fee estimator - {error}", block.height());
+        }
+
         // Clear the memory pool of unconfirmed transactions that are now invalid.
         self.memory_pool.clear_invalid_transactions(self);
 
@@ -166,6 +250,10 @@ impl<N: Network, C: ConsensusStorage<N>> Consensus<N, C> {
             self.memory_pool.clear_invalid_solutions(self);
         }
 
+        // Update the metrics.
+        gauge!(metrics::blocks::HEIGHT, block.height() as f64);
+        self.update_memory_pool_metrics();
+
         info!("Advanced to block {}", block.height());
         Ok(())
     }
@@ -176,6 +264,7 @@ impl<N: Network, C: ConsensusStorage<N>> Consensus<N, C> {
         self.memory_pool.clear_invalid_solutions(self);
         // Clear the memory pool of unconfirmed transactions that are now invalid.
         self.memory_pool.clear_invalid_transactions(self);
+        self.update_memory_pool_metrics();
         Ok(())
     }
 
@@ -185,6 +274,61 @@ impl<N: Network, C: ConsensusStorage<N>> Consensus<N, C> {
         self.memory_pool.clear_all_unconfirmed_solutions();
         // Clear the memory pool of unconfirmed transactions that are now invalid.
         self.memory_pool.clear_unconfirmed_transactions();
+        self.update_memory_pool_metrics();
         Ok(())
     }
+
+    /// Saves the unconfirmed transactions in the memory pool to the given path, one transaction per line.
+    pub fn save_memory_pool(&self, path: &Path) -> Result<()> {
+        let contents = self
+            .memory_pool
+            .unconfirmed_transactions()
+            .iter()
+            .map(|transaction| format!("{transaction}\n"))
+            .collect::<String>();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents)
+            .map_err(|e| anyhow!("Failed to save the memory pool to '{}' - {e}", path.display()))
+    }
+
+    /// Loads the first `MAX_RESTORED_TRANSACTIONS` unconfirmed transactions at the given path into the memory pool,
+    /// if the file exists. The loading stops early once consensus has shut down.
+    pub fn load_memory_pool(&self, path: &Path) -> Result<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to load the memory pool from '{}' - {e}", path.display()))?;
+        for line in contents.lines().take(MAX_RESTORED_TRANSACTIONS) {
+            if self.is_shut_down.load(Ordering::SeqCst) {
+                break;
+            }
+            // Skip the transactions that are malformed, or are no longer valid for the ledger.
+            match Transaction::<N>::from_str(line) {
+                Ok(transaction) => {
+                    if let Err(error) = self.add_unconfirmed_transaction(transaction) {
+                        debug!("Skipping a saved unconfirmed transaction - {error}");
+                    }
+                }
+                Err(error) => debug!("Skipping a malformed unconfirmed transaction - {error}"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Waits for the block that is being written to the ledger, if any, and stops the ledger from advancing.
+    pub fn shut_down(&self) {
+        let _lock = self.advance_lock.lock();
+        self.is_shut_down.store(true, Ordering::SeqCst);
+    }
+
+    /// Updates the metrics for the number of solutions and transactions in the memory pool.
+    fn update_memory_pool_metrics(&self) {
+        let num_transactions = self.memory_pool.num_unconfirmed_transactions();
+        let num_solutions = self.memory_pool.num_unconfirmed_solutions();
+        gauge!(metrics::consensus::UNCONFIRMED_TRANSACTIONS, num_transactions as f64);
+        gauge!(metrics::consensus::UNCONFIRMED_SOLUTIONS, num_solutions as f64);
+    }
 }
@@ -0,0 +1,65 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{
+    block::{Block, Transaction},
+    ensure,
+    Network,
+    Result,
+    ToBytes,
+};
+
+// Note: The size limits are a local policy of this node, and not a consensus rule.
+// They only apply to the transactions admitted to the memory pool and to the blocks proposed by this node.
+// The blocks received from other nodes are checked by `check_next_block`, which does not enforce them,
+// so a block with a larger transaction, or a larger block, is still accepted.
+
+/// The maximum size of a transaction admitted to the memory pool of this node, in bytes.
+pub const MAX_TRANSACTION_SIZE: usize = 128 * 1024; // 128 KiB
+/// The maximum size of a block proposed by this node, in bytes.
+pub const MAX_BLOCK_SIZE: usize = 4 * 1024 * 1024; // 4 MiB
+/// The size of a proposed block reserved for its header and coinbase solutions, in bytes.
+pub const BLOCK_SIZE_RESERVED: usize = 512 * 1024; // 512 KiB
+
+/// Returns the size of the given transaction, in bytes.
+pub fn transaction_size<N: Network>(transaction: &Transaction<N>) -> Result<usize> {
+    Ok(transaction.to_bytes_le()?.len())
+}
+
+/// Ensures the given transaction does not exceed the maximum transaction size of this node,
+/// returning its size in bytes.
+pub fn check_transaction_size<N: Network>(transaction: &Transaction<N>) -> Result<usize> {
+    let size = transaction_size(transaction)?;
+    ensure!(
+        size <= MAX_TRANSACTION_SIZE,
+        "Transaction '{}' is {size} bytes, exceeding the maximum of {MAX_TRANSACTION_SIZE} bytes",
+        transaction.id()
+    );
+    Ok(size)
+}
+
+/// Ensures the given block, and each of its transactions, does not exceed the maximum size of this node.
+/// Note: This is only checked on the blocks proposed by this node, and not on the blocks of other nodes.
+pub fn check_block_size<N: Network>(block: &Block<N>) -> Result<()> {
+    for confirmed in block.transactions().iter() {
+        check_transaction_size(confirmed.transaction())?;
+    }
+    let size = block.to_bytes_le()?.len();
+    ensure!(
+        size <= MAX_BLOCK_SIZE,
+        "Block {} is {size} bytes, exceeding the maximum of {MAX_BLOCK_SIZE} bytes",
+        block.height()
+    );
+    Ok(())
+}
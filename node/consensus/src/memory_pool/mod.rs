@@ -27,21 +27,64 @@ use snarkvm::prelude::{
 
 use anyhow::{anyhow, Result};
 use parking_lot::RwLock;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+/// The default maximum number of unconfirmed transactions in the memory pool.
+pub const MAX_UNCONFIRMED_TRANSACTIONS: usize = 1 << 16;
+/// The maximum number of saved unconfirmed transactions that are restored into the memory pool on startup,
+/// as each of them is verified again.
+pub const MAX_RESTORED_TRANSACTIONS: usize = 1 << 12;
 
 #[derive(Clone, Debug)]
 #[allow(clippy::type_complexity)]
 pub struct MemoryPool<N: Network> {
-    /// The pool of unconfirmed transactions.
-    unconfirmed_transactions: Arc<RwLock<HashMap<N::TransactionID, Transaction<N>>>>,
+    /// The pool of unconfirmed transactions and the time they were added.
+    unconfirmed_transactions: Arc<RwLock<HashMap<N::TransactionID, (Transaction<N>, Instant)>>>,
     /// The pool of unconfirmed solutions and their proof targets.
     unconfirmed_solutions: Arc<RwLock<HashMap<PuzzleCommitment<N>, (ProverSolution<N>, u64)>>>,
+    /// The minimum fee (in microcredits) for an unconfirmed transaction to be added to the pool.
+    minimum_fee: Arc<AtomicU64>,
+    /// The maximum number of unconfirmed transactions in the pool.
+    max_unconfirmed_transactions: Arc<AtomicUsize>,
 }
 
 impl<N: Network> MemoryPool<N> {
     /// Initializes a new instance of a memory pool.
     pub fn new() -> Self {
-        Self { unconfirmed_transactions: Default::default(), unconfirmed_solutions: Default::default() }
+        Self {
+            unconfirmed_transactions: Default::default(),
+            unconfirmed_solutions: Default::default(),
+            minimum_fee: Default::default(),
+            max_unconfirmed_transactions: Arc::new(AtomicUsize::new(MAX_UNCONFIRMED_TRANSACTIONS)),
+        }
+    }
+
+    /// Returns the minimum fee (in microcredits) for an unconfirmed transaction to be added to the memory pool.
+    pub fn minimum_fee(&self) -> u64 {
+        self.minimum_fee.load(Ordering::SeqCst)
+    }
+
+    /// Sets the minimum fee (in microcredits) for an unconfirmed transaction to be added to the memory pool.
+    pub fn set_minimum_fee(&self, minimum_fee: u64) {
+        self.minimum_fee.store(minimum_fee, Ordering::SeqCst);
+    }
+
+    /// Returns the maximum number of unconfirmed transactions in the memory pool.
+    pub fn max_unconfirmed_transactions(&self) -> usize {
+        self.max_unconfirmed_transactions.load(Ordering::SeqCst)
+    }
+
+    /// Sets the maximum number of unconfirmed transactions in the memory pool.
+    /// Note: Lowering the maximum does not evict the transactions that are already in the memory pool.
+    pub fn set_max_unconfirmed_transactions(&self, max_unconfirmed_transactions: usize) {
+        self.max_unconfirmed_transactions.store(max_unconfirmed_transactions, Ordering::SeqCst);
     }
 }
 
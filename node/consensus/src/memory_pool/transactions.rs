@@ -14,7 +14,7 @@
 
 use super::*;
 
-use std::collections::hash_map::Entry;
+use std::{collections::hash_map::Entry, time::Instant};
 
 impl<N: Network> MemoryPool<N> {
     /// Returns `true` if the given unconfirmed transaction exists in the memory pool.
@@ -29,6 +29,11 @@ impl<N: Network> MemoryPool<N> {
 
     /// Returns the unconfirmed transactions in the memory pool.
     pub fn unconfirmed_transactions(&self) -> Vec<Transaction<N>> {
+        self.unconfirmed_transactions.read().values().map(|(transaction, _)| transaction.clone()).collect::<Vec<_>>()
+    }
+
+    /// Returns the unconfirmed transactions in the memory pool, along with the time they were added.
+    pub fn unconfirmed_transactions_with_timestamps(&self) -> Vec<(Transaction<N>, Instant)> {
         self.unconfirmed_transactions.read().values().cloned().collect::<Vec<_>>()
     }
 
@@ -40,8 +45,9 @@ impl<N: Network> MemoryPool<N> {
         let mut transactions = Vec::new();
         let mut input_ids = Vec::new();
         let mut output_ids = Vec::new();
+        let mut block_size = crate::BLOCK_SIZE_RESERVED;
 
-        'outer: for transaction in self.unconfirmed_transactions.read().values() {
+        'outer: for (transaction, _) in self.unconfirmed_transactions.read().values() {
             // Ensure the transaction is not a fee transaction.
             if matches!(transaction, Transaction::Fee(..)) {
                 continue;
@@ -52,6 +58,12 @@ impl<N: Network> MemoryPool<N> {
                 continue;
             }
 
+            // Ensure the transaction fits in the block.
+            let size = match crate::check_transaction_size(transaction) {
+                Ok(size) if block_size + size <= crate::MAX_BLOCK_SIZE => size,
+                _ => continue,
+            };
+
             // Ensure the input IDs are unique.
             for input_id in transaction.input_ids() {
                 if input_ids.contains(&input_id) {
@@ -68,6 +80,7 @@ impl<N: Network> MemoryPool<N> {
             transactions.push(transaction.clone());
             input_ids.extend(transaction.input_ids());
             output_ids.extend(transaction.output_ids());
+            block_size += size;
         }
 
         transactions
@@ -82,7 +95,7 @@ impl<N: Network> MemoryPool<N> {
         match unconfirmed_transactions.entry(transaction.id()) {
             Entry::Vacant(entry) => {
                 // Add the transaction to the memory pool.
-                entry.insert(transaction.clone());
+                entry.insert((transaction.clone(), Instant::now()));
                 debug!("✉️  Added transaction '{}' to the memory pool", transaction.id());
                 true
             }
@@ -95,7 +108,7 @@ impl<N: Network> MemoryPool<N> {
 
     /// Clears the memory pool of unconfirmed transactions that are now invalid.
     pub fn clear_invalid_transactions<C: ConsensusStorage<N>>(&self, consensus: &Consensus<N, C>) {
-        self.unconfirmed_transactions.write().retain(|transaction_id, transaction| {
+        self.unconfirmed_transactions.write().retain(|transaction_id, (transaction, _)| {
             // Ensure the transaction is not a fee transaction.
             if matches!(transaction, Transaction::Fee(..)) {
                 trace!("Removed transaction '{transaction_id}' from the memory pool");
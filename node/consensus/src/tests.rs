@@ -349,6 +349,141 @@ fn test_ledger_execute() {
     assert!(consensus.add_unconfirmed_transaction(transaction).is_err());
 }
 
+#[test]
+#[traced_test]
+fn test_memory_pool_timestamps() {
+    let rng = &mut TestRng::default();
+
+    // Sample the genesis consensus.
+    let consensus = test_helpers::sample_genesis_consensus(rng);
+
+    // Add a transaction to the memory pool.
+    let transaction = crate::tests::test_helpers::sample_execution_transaction(rng);
+    consensus.add_unconfirmed_transaction(transaction.clone()).unwrap();
+
+    // Ensure the transaction is recorded with the time it was added.
+    let entries = consensus.memory_pool().unconfirmed_transactions_with_timestamps();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].0, transaction);
+    assert!(entries[0].1.elapsed().as_secs() < 60);
+    // Ensure the transaction is returned without its timestamp.
+    assert_eq!(consensus.memory_pool().unconfirmed_transactions(), vec![transaction]);
+}
+
+#[test]
+#[traced_test]
+fn test_memory_pool_limits() {
+    let rng = &mut TestRng::default();
+
+    // Sample the genesis consensus.
+    let consensus = test_helpers::sample_genesis_consensus(rng);
+    let transaction = crate::tests::test_helpers::sample_execution_transaction(rng);
+    let fee = *transaction.fee().unwrap();
+
+    // Ensure a transaction that pays less than the minimum fee is rejected.
+    consensus.memory_pool().set_minimum_fee(fee + 1);
+    assert!(consensus.add_unconfirmed_transaction(transaction.clone()).is_err());
+    consensus.memory_pool().set_minimum_fee(fee);
+
+    // Ensure a transaction is rejected when the memory pool is full.
+    consensus.memory_pool().set_max_unconfirmed_transactions(0);
+    assert!(consensus.add_unconfirmed_transaction(transaction.clone()).is_err());
+    consensus.memory_pool().set_max_unconfirmed_transactions(crate::MAX_UNCONFIRMED_TRANSACTIONS);
+
+    // Ensure the transaction is accepted once the limits allow it.
+    consensus.add_unconfirmed_transaction(transaction).unwrap();
+    assert_eq!(consensus.memory_pool().num_unconfirmed_transactions(), 1);
+}
+
+#[test]
+#[traced_test]
+fn test_save_and_load_memory_pool() {
+    let rng = &mut TestRng::default();
+    let path = std::env::temp_dir().join("snarkos-test-memory-pool.txt");
+
+    // Save the memory pool of a consensus with an unconfirmed transaction.
+    let consensus = test_helpers::sample_genesis_consensus(rng);
+    let transaction = crate::tests::test_helpers::sample_execution_transaction(rng);
+    consensus.add_unconfirmed_transaction(transaction.clone()).unwrap();
+    consensus.save_memory_pool(&path).unwrap();
+
+    // Ensure a fresh consensus loads the transaction back into its memory pool.
+    let other = test_helpers::sample_genesis_consensus(rng);
+    other.load_memory_pool(&path).unwrap();
+    assert_eq!(other.memory_pool().unconfirmed_transactions(), vec![transaction]);
+
+    // Ensure a consensus that has shut down stops loading the transactions.
+    let other = test_helpers::sample_genesis_consensus(rng);
+    other.shut_down();
+    other.load_memory_pool(&path).unwrap();
+    assert_eq!(other.memory_pool().num_unconfirmed_transactions(), 0);
+
+    // Ensure a missing file is skipped.
+    std::fs::remove_file(&path).unwrap();
+    assert!(other.load_memory_pool(&path).is_ok());
+}
+
+#[test]
+#[traced_test]
+fn test_shut_down() {
+    let rng = &mut TestRng::default();
+
+    // Ensure the ledger does not advance once consensus has shut down.
+    let consensus = test_helpers::sample_genesis_consensus(rng);
+    let block = consensus.propose_next_block(&test_helpers::sample_genesis_private_key(rng), rng).unwrap();
+    consensus.shut_down();
+    assert!(consensus.advance_to_next_block(&block).is_err());
+    assert_eq!(consensus.ledger().latest_height(), 0);
+}
+
+#[test]
+#[traced_test]
+fn test_transaction_and_block_size() {
+    let rng = &mut TestRng::default();
+
+    // Sample the genesis consensus, and a transaction.
+    let consensus = test_helpers::sample_genesis_consensus(rng);
+    let transaction = crate::tests::test_helpers::sample_execution_transaction(rng);
+
+    // Ensure the transaction size is its serialized size, within the maximum transaction size.
+    let size = crate::transaction_size(&transaction).unwrap();
+    assert_eq!(size, transaction.to_bytes_le().unwrap().len());
+    assert_eq!(crate::check_transaction_size(&transaction).unwrap(), size);
+    assert!(size <= crate::MAX_TRANSACTION_SIZE);
+
+    // Ensure the genesis block is within the maximum block size.
+    crate::check_block_size(&consensus.ledger.get_block(0).unwrap()).unwrap();
+}
+
+#[test]
+fn test_fee_estimator() {
+    let fee_estimator = crate::FeeEstimator::new();
+    assert_eq!(fee_estimator.estimate(1), None);
+
+    // Insert the lowest fee rates of 4 blocks.
+    for min_fee_rate in [4.0, 1.0, 3.0, 2.0] {
+        fee_estimator.insert_min_fee_rate(min_fee_rate);
+    }
+    assert_eq!(fee_estimator.num_blocks(), 4);
+
+    // Confirming in the next block requires a fee rate that every recent block accepted.
+    assert_eq!(fee_estimator.estimate(0), Some(4.0));
+    assert_eq!(fee_estimator.estimate(1), Some(4.0));
+    // Confirming within 2 or 3 blocks requires a fee rate that at least half or a third of the recent blocks accepted.
+    assert_eq!(fee_estimator.estimate(2), Some(2.0));
+    assert_eq!(fee_estimator.estimate(3), Some(2.0));
+    // Confirming within 4 or more blocks requires a fee rate that any recent block accepted.
+    assert_eq!(fee_estimator.estimate(4), Some(1.0));
+    assert_eq!(fee_estimator.estimate(100), Some(1.0));
+
+    // Ensure the oldest blocks are removed, once the window is full.
+    for _ in 0..crate::NUM_FEE_ESTIMATION_BLOCKS {
+        fee_estimator.insert_min_fee_rate(5.0);
+    }
+    assert_eq!(fee_estimator.num_blocks(), crate::NUM_FEE_ESTIMATION_BLOCKS);
+    assert_eq!(fee_estimator.estimate(100), Some(5.0));
+}
+
 #[test]
 #[traced_test]
 fn test_ledger_execute_many() {
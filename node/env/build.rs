@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Returns the trimmed output of the given git command, if it succeeds.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    match output.status.success() {
+        true => Some(String::from_utf8(output.stdout).ok()?.trim().to_string()),
+        false => None,
+    }
+}
+
+/// Returns the given UNIX timestamp (in seconds) as an RFC 3339 string in UTC.
+fn to_rfc3339(timestamp: u64) -> String {
+    let (days, seconds) = (timestamp / 86400, timestamp % 86400);
+    // Convert the days since the UNIX epoch into a civil date (http://howardhinnant.github.io/date_algorithms.html).
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+fn main() {
+    // Embed the git commit of the build, if it is built from a git checkout.
+    let commit = git(&["rev-parse", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=SNARKOS_GIT_COMMIT={commit}");
+
+    // Embed the build timestamp, honoring `SOURCE_DATE_EPOCH` for reproducible builds.
+    let timestamp = match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(epoch) => epoch.parse().expect("Invalid SOURCE_DATE_EPOCH"),
+        Err(_) => SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default(),
+    };
+    println!("cargo:rustc-env=SNARKOS_BUILD_TIMESTAMP={}", to_rfc3339(timestamp));
+
+    // Re-run upon a new commit, or a change to the reproducible build timestamp.
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={git_dir}/HEAD");
+        println!("cargo:rerun-if-changed={git_dir}/refs");
+    }
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+}
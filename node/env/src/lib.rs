@@ -20,6 +20,33 @@ use serde::Serialize;
 // Contains the environment information.
 pub static ENV_INFO: OnceCell<EnvInfo> = OnceCell::new();
 
+/// The version of snarkOS.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// The git commit that snarkOS was built from, or `unknown` if it was not built from a git checkout.
+pub const GIT_COMMIT: &str = env!("SNARKOS_GIT_COMMIT");
+/// The time at which snarkOS was built, as an RFC 3339 string.
+pub const BUILD_TIMESTAMP: &str = env!("SNARKOS_BUILD_TIMESTAMP");
+
+/// Returns the user agent of this node, which is announced to its peers during the handshake.
+pub fn user_agent() -> String {
+    format!("snarkOS/{VERSION} ({})", short_commit())
+}
+
+/// Returns the version of this node with its build metadata, as printed by `snarkos --version`.
+pub fn long_version() -> &'static str {
+    static LONG_VERSION: OnceCell<String> = OnceCell::new();
+    LONG_VERSION.get_or_init(|| {
+        let features = ENV_INFO.get().map(|info| info.features.join(", ")).unwrap_or_default();
+        let features = if features.is_empty() { "none".to_string() } else { features };
+        format!("{VERSION}\ncommit: {GIT_COMMIT}\nbuilt: {BUILD_TIMESTAMP}\nfeatures: {features}")
+    })
+}
+
+/// Returns the git commit, abbreviated to 7 characters.
+fn short_commit() -> &'static str {
+    GIT_COMMIT.get(..7).unwrap_or(GIT_COMMIT)
+}
+
 // Environment information.
 #[allow(dead_code)]
 #[derive(Debug, Serialize)]
@@ -31,10 +58,14 @@ pub struct EnvInfo {
     repo: String,
     branch: String,
     commit: String,
+    build_timestamp: String,
+    features: Vec<String>,
+    user_agent: String,
 }
 
 impl EnvInfo {
-    pub fn register() {
+    /// Registers the environment information, with the given feature flags that the binary was built with.
+    pub fn register(features: &[&str]) {
         // A helper function to extract command output.
         fn command(args: &[&str]) -> String {
             let mut output = String::from_utf8(
@@ -75,13 +106,16 @@ impl EnvInfo {
 
         // Collect the information.
         let env_info = EnvInfo {
-            package: env::var("CARGO_PKG_VERSION").unwrap_or_default(),
+            package: VERSION.to_string(),
             host,
             rustc,
             args,
-            repo: env::var("CARGO_PKG_REPOSITORY").unwrap_or_default(),
+            repo: env!("CARGO_PKG_REPOSITORY").to_string(),
             branch: command(&["git", "branch", "--show-current"]),
-            commit: command(&["git", "rev-parse", "HEAD"]),
+            commit: GIT_COMMIT.to_string(),
+            build_timestamp: BUILD_TIMESTAMP.to_string(),
+            features: features.iter().map(ToString::to_string).collect(),
+            user_agent: user_agent(),
         };
 
         // Set the static containing the information.
@@ -0,0 +1,21 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Use the vendored `protoc`, so the build does not depend on a system installation.
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    // Generate the server and client code for the service definition.
+    tonic_build::compile_protos("proto/snarkos.proto")?;
+    Ok(())
+}
@@ -0,0 +1,180 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![forbid(unsafe_code)]
+
+#[macro_use]
+extern crate tracing;
+
+mod service;
+
+/// The code generated from the service definition in `proto/snarkos.proto`.
+pub mod proto {
+    tonic::include_proto!("snarkos");
+}
+
+use snarkos_node_consensus::Consensus;
+use snarkos_node_rest::{verify_jwt, JwtError, Permission};
+use snarkos_node_router::Routing;
+use snarkvm::prelude::{block::Block, store::ConsensusStorage, Ledger, Network};
+
+use anyhow::Result;
+use core::time::Duration;
+use parking_lot::Mutex;
+use std::{net::SocketAddr, sync::Arc};
+use tokio::{sync::broadcast, task::JoinHandle};
+use tonic::{Request, Status};
+
+/// The maximum number of new blocks buffered for each subscriber.
+const BLOCK_CHANNEL_CAPACITY: usize = 64;
+
+/// A gRPC API server for the ledger.
+#[derive(Clone)]
+pub struct Grpc<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
+    /// The consensus module.
+    consensus: Option<Consensus<N, C>>,
+    /// The ledger.
+    ledger: Ledger<N, C>,
+    /// The node (routing).
+    routing: Arc<R>,
+    /// The sender of the blocks added to the ledger.
+    block_sender: broadcast::Sender<Block<N>>,
+    /// The server handles.
+    handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+}
+
+impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Grpc<N, C, R> {
+    /// Initializes a new instance of the server.
+    /// Each request must carry a JWT token issued by this node, with at least read-only permission.
+    pub fn start(
+        grpc_ip: SocketAddr,
+        consensus: Option<Consensus<N, C>>,
+        ledger: Ledger<N, C>,
+        routing: Arc<R>,
+    ) -> Result<Self> {
+        // Initialize the block sender.
+        let (block_sender, _) = broadcast::channel(BLOCK_CHANNEL_CAPACITY);
+        // Initialize the server.
+        let mut server = Self { consensus, ledger, routing, block_sender, handles: Default::default() };
+        // Spawn the block watcher.
+        server.spawn_block_watcher();
+        // Spawn the server.
+        server.spawn_server(grpc_ip);
+        // Return the server.
+        Ok(server)
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Grpc<N, C, R> {
+    /// Returns the ledger.
+    pub const fn ledger(&self) -> &Ledger<N, C> {
+        &self.ledger
+    }
+
+    /// Returns the handles.
+    pub const fn handles(&self) -> &Arc<Mutex<Vec<JoinHandle<()>>>> {
+        &self.handles
+    }
+}
+
+impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Grpc<N, C, R> {
+    /// Spawns a task that sends each block added to the ledger to the subscribers.
+    fn spawn_block_watcher(&mut self) {
+        let ledger = self.ledger.clone();
+        let block_sender = self.block_sender.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            let mut last_height = ledger.latest_height();
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+
+                let latest_height = ledger.latest_height();
+                for height in last_height + 1..=latest_height {
+                    match ledger.get_block(height) {
+                        // Note: Sending only fails if there are no subscribers, in which case the block is dropped.
+                        Ok(block) => {
+                            let _ = block_sender.send(block);
+                        }
+                        Err(error) => warn!("Failed to retrieve block {height} for the gRPC subscribers - {error}"),
+                    }
+                }
+                last_height = latest_height;
+            }
+        }));
+    }
+
+    fn spawn_server(&mut self, grpc_ip: SocketAddr) {
+        let service = proto::chain_server::ChainServer::with_interceptor(self.clone(), authenticate);
+
+        self.handles.lock().push(tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve(grpc_ip)
+                .await
+                .expect("couldn't start grpc server");
+        }))
+    }
+}
+
+/// Ensures the request carries a valid JWT token, with at least read-only permission.
+fn authenticate(request: Request<()>) -> Result<Request<()>, Status> {
+    let token = request
+        .metadata()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| Status::unauthenticated("missing JWT token"))?;
+
+    match verify_jwt(token, Permission::ReadOnly) {
+        Ok(()) => Ok(request),
+        Err(JwtError::Invalid) => Err(Status::unauthenticated("invalid JWT token")),
+        Err(JwtError::Expired) => Err(Status::unauthenticated("expired JWT token")),
+        Err(JwtError::InsufficientPermission) => Err(Status::permission_denied("insufficient JWT permission")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkos_node_rest::Claims;
+    use snarkvm::prelude::{Address, PrivateKey, TestRng, Testnet3};
+    use tonic::Code;
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns a request with the given `authorization` header, if any.
+    fn sample_request(authorization: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(authorization) = authorization {
+            request.metadata_mut().insert("authorization", authorization.parse().unwrap());
+        }
+        request
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let rng = &mut TestRng::default();
+        let address = Address::<CurrentNetwork>::try_from(PrivateKey::new(rng).unwrap()).unwrap();
+
+        // Ensure a request without a valid token is rejected.
+        assert_eq!(authenticate(sample_request(None)).unwrap_err().code(), Code::Unauthenticated);
+        assert_eq!(authenticate(sample_request(Some("Bearer invalid"))).unwrap_err().code(), Code::Unauthenticated);
+
+        // Ensure a read-only or admin token is accepted, but only as a bearer token.
+        for permission in [Permission::ReadOnly, Permission::Admin] {
+            let token = Claims::new(address, permission).to_jwt_string().unwrap();
+            assert!(authenticate(sample_request(Some(&format!("Bearer {token}")))).is_ok());
+            assert_eq!(authenticate(sample_request(Some(&token))).unwrap_err().code(), Code::Unauthenticated);
+        }
+    }
+}
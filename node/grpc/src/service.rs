@@ -0,0 +1,205 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{proto, proto::get_block_request, Grpc};
+use snarkos_node_messages::{Data, Message, UnconfirmedTransaction};
+use snarkos_node_router::Routing;
+use snarkvm::prelude::{
+    block::{Block, Transaction},
+    store::ConsensusStorage,
+    FromBytes,
+    Network,
+    ToBytes,
+};
+
+use core::{fmt::Display, pin::Pin};
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+use tonic::{Request, Response, Status};
+
+/// A stream of items sent to a subscriber.
+type SubscriptionStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> proto::chain_server::Chain for Grpc<N, C, R> {
+    type SubscribeBlocksStream = SubscriptionStream<proto::Block>;
+    type SubscribeTransactionsStream = SubscriptionStream<proto::Transaction>;
+
+    async fn get_latest_height(
+        &self,
+        _request: Request<proto::GetLatestHeightRequest>,
+    ) -> Result<Response<proto::GetLatestHeightResponse>, Status> {
+        Ok(Response::new(proto::GetLatestHeightResponse { height: self.ledger.latest_height() }))
+    }
+
+    async fn get_block(&self, request: Request<proto::GetBlockRequest>) -> Result<Response<proto::Block>, Status> {
+        let block = match request.into_inner().block {
+            Some(get_block_request::Block::Height(height)) => self.ledger.get_block(height).map_err(not_found)?,
+            Some(get_block_request::Block::Hash(hash)) => {
+                let hash = hash.parse::<N::BlockHash>().map_err(|_| invalid_argument("invalid block hash"))?;
+                self.ledger.get_block_by_hash(&hash).map_err(not_found)?
+            }
+            None => return Err(invalid_argument("missing block height or hash")),
+        };
+        Ok(Response::new(Self::block_to_proto(&block)?))
+    }
+
+    async fn get_transaction(
+        &self,
+        request: Request<proto::GetTransactionRequest>,
+    ) -> Result<Response<proto::Transaction>, Status> {
+        let id = request
+            .into_inner()
+            .id
+            .parse::<N::TransactionID>()
+            .map_err(|_| invalid_argument("invalid transaction ID"))?;
+        let transaction = self.ledger.get_transaction(id).map_err(not_found)?;
+        Ok(Response::new(Self::transaction_to_proto(&transaction)?))
+    }
+
+    async fn get_memory_pool(
+        &self,
+        _request: Request<proto::GetMemoryPoolRequest>,
+    ) -> Result<Response<proto::GetMemoryPoolResponse>, Status> {
+        let consensus = self.consensus.as_ref().ok_or_else(unavailable)?;
+        let transactions = consensus
+            .memory_pool()
+            .unconfirmed_transactions()
+            .iter()
+            .map(Self::transaction_to_proto)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Response::new(proto::GetMemoryPoolResponse { transactions }))
+    }
+
+    async fn broadcast_transaction(
+        &self,
+        request: Request<proto::BroadcastTransactionRequest>,
+    ) -> Result<Response<proto::BroadcastTransactionResponse>, Status> {
+        let transaction = Transaction::<N>::from_bytes_le(&request.into_inner().transaction)
+            .map_err(|error| invalid_argument(format!("invalid transaction - {error}")))?;
+
+        // If the consensus module is enabled, add the unconfirmed transaction to the memory pool.
+        if let Some(consensus) = &self.consensus {
+            consensus.add_unconfirmed_transaction(transaction.clone()).map_err(invalid_argument)?;
+        }
+
+        // Prepare the unconfirmed transaction message.
+        let transaction_id = transaction.id();
+        let message = Message::UnconfirmedTransaction(UnconfirmedTransaction {
+            transaction_id,
+            transaction: Data::Object(transaction),
+        });
+
+        // Broadcast the transaction.
+        self.routing.propagate(message, &[]);
+
+        Ok(Response::new(proto::BroadcastTransactionResponse { id: transaction_id.to_string() }))
+    }
+
+    async fn subscribe_blocks(
+        &self,
+        _request: Request<proto::SubscribeBlocksRequest>,
+    ) -> Result<Response<Self::SubscribeBlocksStream>, Status> {
+        Ok(Response::new(self.subscribe(|block| vec![Self::block_to_proto(block)])))
+    }
+
+    async fn subscribe_transactions(
+        &self,
+        _request: Request<proto::SubscribeTransactionsRequest>,
+    ) -> Result<Response<Self::SubscribeTransactionsStream>, Status> {
+        Ok(Response::new(self.subscribe(|block| {
+            block.transactions().iter().map(|confirmed| Self::transaction_to_proto(confirmed.transaction())).collect()
+        })))
+    }
+}
+
+impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Grpc<N, C, R> {
+    /// Returns a stream of the items produced by `to_items` for each block added to the ledger.
+    fn subscribe<T: 'static + Send>(
+        &self,
+        to_items: impl 'static + Send + Fn(&Block<N>) -> Vec<Result<T, Status>>,
+    ) -> SubscriptionStream<T> {
+        let mut block_receiver = self.block_sender.subscribe();
+        let (sender, receiver) = mpsc::channel(crate::BLOCK_CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            loop {
+                match block_receiver.recv().await {
+                    Ok(block) => {
+                        for item in to_items(&block) {
+                            // If the subscriber has disconnected, stop sending.
+                            if sender.send(item).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    // Skip the blocks that were missed by a slow subscriber.
+                    Err(RecvError::Lagged(num_skipped)) => {
+                        debug!("A gRPC subscriber skipped {num_skipped} blocks");
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+
+        Box::pin(ReceiverStream::new(receiver))
+    }
+
+    /// Returns the gRPC representation of the given block.
+    fn block_to_proto(block: &Block<N>) -> Result<proto::Block, Status> {
+        Ok(proto::Block {
+            height: block.height(),
+            hash: block.hash().to_string(),
+            previous_hash: block.previous_hash().to_string(),
+            timestamp: block.timestamp(),
+            transaction_ids: block.transaction_ids().map(|id| id.to_string()).collect(),
+            bytes: block.to_bytes_le().map_err(internal)?,
+        })
+    }
+
+    /// Returns the gRPC representation of the given transaction.
+    fn transaction_to_proto(transaction: &Transaction<N>) -> Result<proto::Transaction, Status> {
+        let transaction_type = match transaction {
+            Transaction::Deploy(..) => "deploy",
+            Transaction::Execute(..) => "execute",
+            Transaction::Fee(..) => "fee",
+        };
+        Ok(proto::Transaction {
+            id: transaction.id().to_string(),
+            r#type: transaction_type.to_string(),
+            fee: *transaction.fee().map_err(internal)?,
+            bytes: transaction.to_bytes_le().map_err(internal)?,
+        })
+    }
+}
+
+/// Returns an `invalid argument` status with the given message.
+fn invalid_argument(message: impl Display) -> Status {
+    Status::invalid_argument(message.to_string())
+}
+
+/// Returns a `not found` status with the given message.
+fn not_found(message: impl Display) -> Status {
+    Status::not_found(message.to_string())
+}
+
+/// Returns an `internal` status with the given message.
+fn internal(message: impl Display) -> Status {
+    Status::internal(message.to_string())
+}
+
+/// Returns an `unavailable` status for the routes that require the consensus module.
+fn unavailable() -> Status {
+    Status::unavailable("route isn't available for this node type")
+}
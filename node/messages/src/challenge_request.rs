@@ -16,6 +16,9 @@ use super::*;
 
 use bincode::Options;
 
+/// The maximum length of the user agent of a peer, in bytes.
+const MAXIMUM_USER_AGENT_LENGTH: usize = 256;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ChallengeRequest<N: Network> {
     pub version: u32,
@@ -23,6 +26,7 @@ pub struct ChallengeRequest<N: Network> {
     pub node_type: NodeType,
     pub address: Address<N>,
     pub nonce: u64,
+    pub user_agent: String,
 }
 
 impl<N: Network> MessageTrait for ChallengeRequest<N> {
@@ -37,7 +41,7 @@ impl<N: Network> MessageTrait for ChallengeRequest<N> {
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
         Ok(bincode::serialize_into(
             writer,
-            &(self.version, self.listener_port, self.node_type, self.address, self.nonce),
+            &(self.version, self.listener_port, self.node_type, self.address, self.nonce, &self.user_agent),
         )?)
     }
 
@@ -46,13 +50,51 @@ impl<N: Network> MessageTrait for ChallengeRequest<N> {
     fn deserialize(bytes: BytesMut) -> Result<Self> {
         let options =
             bincode::options().with_limit(MAXIMUM_MESSAGE_SIZE as u64).with_fixint_encoding().allow_trailing_bytes();
-        let (version, listener_port, node_type, address, nonce) = options.deserialize_from(&mut bytes.reader())?;
-        Ok(Self { version, listener_port, node_type, address, nonce })
+        let mut reader = bytes.reader();
+        let (version, listener_port, node_type, address, nonce) = options.deserialize_from(&mut reader)?;
+        // Note: The user agent is an optional trailing field, as it is not sent by older peers.
+        let user_agent: String = match reader.get_ref().has_remaining() {
+            true => options.deserialize_from(&mut reader)?,
+            false => String::new(),
+        };
+        if user_agent.len() > MAXIMUM_USER_AGENT_LENGTH {
+            bail!("Invalid 'ChallengeRequest' message (the user agent is too long)");
+        }
+        Ok(Self { version, listener_port, node_type, address, nonce, user_agent })
     }
 }
 
 impl<N: Network> ChallengeRequest<N> {
     pub fn new(listener_port: u16, node_type: NodeType, address: Address<N>, nonce: u64) -> Self {
-        Self { version: Message::<N>::VERSION, listener_port, node_type, address, nonce }
+        let user_agent = snarkos_node_env::user_agent();
+        Self { version: Message::<N>::VERSION, listener_port, node_type, address, nonce, user_agent }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{Group, TestRng, Testnet3, Uniform};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_deserialize_without_user_agent() {
+        let rng = &mut TestRng::default();
+        let address = Address::<CurrentNetwork>::new(Group::rand(rng));
+
+        // Ensure a challenge request of an older peer, without the user agent, is accepted.
+        let bytes = bincode::serialize(&(Message::<CurrentNetwork>::VERSION, 4133u16, NodeType::Client, address, 1u64))
+            .unwrap();
+        let request = ChallengeRequest::<CurrentNetwork>::deserialize(BytesMut::from(&bytes[..])).unwrap();
+        assert_eq!(request.address, address);
+        assert_eq!(request.nonce, 1);
+        assert!(request.user_agent.is_empty());
+
+        // Ensure the user agent round-trips.
+        let expected = ChallengeRequest::new(4133, NodeType::Client, address, 1);
+        let mut bytes = Vec::new();
+        expected.serialize(&mut bytes).unwrap();
+        assert_eq!(ChallengeRequest::deserialize(BytesMut::from(&bytes[..])).unwrap(), expected);
     }
 }
@@ -356,6 +356,7 @@ mod tests {
             node_type: NodeType::Client,
             address: Address::new(Group::rand(rng)),
             nonce: 0,
+            user_agent: "snarkOS/0.0.0 (0000000)".to_string(),
         })));
 
         assert_roundtrip(challenge_request);
@@ -19,13 +19,19 @@ pub use metrics::*;
 // Expose the names at the crate level for easy access.
 pub use names::*;
 
+pub use metrics_exporter_prometheus::BuildError;
+
+use std::net::SocketAddr;
+
 /// Initialises the metrics and returns a handle to the task running the metrics exporter.
-pub fn initialize() -> tokio::task::JoinHandle<()> {
+/// The exporter serves the metrics in the Prometheus text format at `http://{metrics_ip}/metrics`.
+/// Returns an error if the exporter cannot listen at the given address, or if the metrics are already initialised.
+pub fn initialize(metrics_ip: SocketAddr) -> Result<tokio::task::JoinHandle<()>, BuildError> {
     use metrics_exporter_prometheus::PrometheusBuilder;
 
     // Build the recorder and set as global.
-    let (recorder, exporter) = PrometheusBuilder::new().build().expect("can't build the prometheus exporter");
-    metrics::set_boxed_recorder(Box::new(recorder)).expect("can't set the prometheus exporter");
+    let (recorder, exporter) = PrometheusBuilder::new().with_http_listener(metrics_ip).build()?;
+    metrics::set_boxed_recorder(Box::new(recorder))?;
 
     // Spawn a dedicated task for the exporter on the runtime.
     let metrics_exporter_task = tokio::task::spawn(async move {
@@ -36,11 +42,14 @@ pub fn initialize() -> tokio::task::JoinHandle<()> {
     register_metrics();
 
     // Return the exporter's task handle to be tracked by the node's task handling.
-    metrics_exporter_task
+    Ok(metrics_exporter_task)
 }
 
 fn register_metrics() {
     for name in GAUGE_NAMES {
         register_gauge!(name);
     }
+    for name in HISTOGRAM_NAMES {
+        register_histogram!(name);
+    }
 }
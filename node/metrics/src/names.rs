@@ -12,10 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub const GAUGE_NAMES: [&str; 4] = [blocks::HEIGHT, peers::CONNECTED, peers::CANDIDATE, peers::RESTRICTED];
+pub const GAUGE_NAMES: [&str; 10] = [
+    blocks::HEIGHT,
+    consensus::UNCONFIRMED_TRANSACTIONS,
+    consensus::UNCONFIRMED_SOLUTIONS,
+    peers::CONNECTED,
+    peers::CANDIDATE,
+    peers::RESTRICTED,
+    network::SENT_BYTES,
+    network::RECEIVED_BYTES,
+    prover::HASHRATE,
+    storage::SIZE_BYTES,
+];
+
+pub const HISTOGRAM_NAMES: [&str; 1] = [blocks::VALIDATION_TIME];
 
 pub mod blocks {
     pub const HEIGHT: &str = "snarkos_blocks_height_total";
+    pub const VALIDATION_TIME: &str = "snarkos_blocks_validation_time_secs";
+}
+
+pub mod consensus {
+    pub const UNCONFIRMED_TRANSACTIONS: &str = "snarkos_consensus_unconfirmed_transactions_total";
+    pub const UNCONFIRMED_SOLUTIONS: &str = "snarkos_consensus_unconfirmed_solutions_total";
 }
 
 pub mod peers {
@@ -23,3 +42,16 @@ pub mod peers {
     pub const CANDIDATE: &str = "snarkos_peers_candidate_total";
     pub const RESTRICTED: &str = "snarkos_peers_restricted_total";
 }
+
+pub mod network {
+    pub const SENT_BYTES: &str = "snarkos_network_sent_bytes_total";
+    pub const RECEIVED_BYTES: &str = "snarkos_network_received_bytes_total";
+}
+
+pub mod prover {
+    pub const HASHRATE: &str = "snarkos_prover_hashrate";
+}
+
+pub mod storage {
+    pub const SIZE_BYTES: &str = "snarkos_storage_size_bytes";
+}
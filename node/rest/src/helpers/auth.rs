@@ -24,7 +24,7 @@ use axum::{
     RequestPartsExt,
     TypedHeader,
 };
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, encode, errors::ErrorKind, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 
@@ -40,11 +40,32 @@ fn jwt_secret() -> &'static Vec<u8> {
     })
 }
 
+/// The permission tier granted by a Json web token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Permission {
+    /// Grants access to the read-only routes.
+    ReadOnly,
+    /// Grants access to all routes, including the routes that change the state of the node.
+    Admin,
+}
+
+impl Permission {
+    /// Returns `true` if this permission grants access to routes requiring the given permission.
+    pub fn allows(&self, required: Permission) -> bool {
+        match self {
+            Self::ReadOnly => required == Self::ReadOnly,
+            Self::Admin => true,
+        }
+    }
+}
+
 /// The Json web token claims.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Claims {
     /// The subject (user).
     sub: String,
+    /// The permission tier.
+    perm: Permission,
     /// The UTC timestamp the token was issued at.
     iat: i64,
     /// Expiration time (as UTC timestamp).
@@ -52,11 +73,16 @@ pub struct Claims {
 }
 
 impl Claims {
-    pub fn new<N: Network>(address: Address<N>) -> Self {
+    pub fn new<N: Network>(address: Address<N>, permission: Permission) -> Self {
         let issued_at = OffsetDateTime::now_utc().unix_timestamp();
         let expiration = issued_at.saturating_add(EXPIRATION);
 
-        Self { sub: address.to_string(), iat: issued_at, exp: expiration }
+        Self { sub: address.to_string(), perm: permission, iat: issued_at, exp: expiration }
+    }
+
+    /// Returns the permission tier.
+    pub const fn permission(&self) -> Permission {
+        self.perm
     }
 
     /// Returns true if the token is expired.
@@ -70,7 +96,53 @@ impl Claims {
     }
 }
 
+/// Ensures the request carries a valid JWT token, with at least read-only permission.
 pub async fn auth_middleware<B>(request: Request<B>, next: Next<B>) -> Result<Response, Response>
+where
+    B: Send,
+{
+    authorize(request, next, Permission::ReadOnly).await
+}
+
+/// Ensures the request carries a valid JWT token, with admin permission.
+pub async fn admin_middleware<B>(request: Request<B>, next: Next<B>) -> Result<Response, Response>
+where
+    B: Send,
+{
+    authorize(request, next, Permission::Admin).await
+}
+
+/// The reason a JWT token is rejected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JwtError {
+    /// The token is malformed, or is not signed by this node.
+    Invalid,
+    /// The token is expired.
+    Expired,
+    /// The token does not grant the required permission.
+    InsufficientPermission,
+}
+
+/// Verifies the given JWT token was issued by this node, and grants the given permission.
+pub fn verify_jwt(token: &str, permission: Permission) -> Result<(), JwtError> {
+    let claims = decode::<Claims>(token, &DecodingKey::from_secret(jwt_secret()), &Validation::new(Algorithm::HS256))
+        .map_err(|error| match error.kind() {
+            ErrorKind::ExpiredSignature => JwtError::Expired,
+            _ => JwtError::Invalid,
+        })?
+        .claims;
+    // Check the expiration again, as the validation allows for some leeway.
+    if claims.is_expired() {
+        return Err(JwtError::Expired);
+    }
+    if !claims.permission().allows(permission) {
+        return Err(JwtError::InsufficientPermission);
+    }
+    Ok(())
+}
+
+/// Ensures the request carries a valid JWT token, granting the given permission.
+async fn authorize<B>(request: Request<B>, next: Next<B>, permission: Permission) -> Result<Response, Response>
 where
     B: Send,
 {
@@ -79,16 +151,14 @@ where
     let auth: TypedHeader<Authorization<Bearer>> =
         parts.extract().await.map_err(|_| StatusCode::UNAUTHORIZED.into_response())?;
 
-    match decode::<Claims>(auth.token(), &DecodingKey::from_secret(jwt_secret()), &Validation::new(Algorithm::HS256)) {
-        Ok(decoded) => {
-            let claims = decoded.claims;
-            if claims.is_expired() {
-                return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response());
-            }
+    match verify_jwt(auth.token(), permission) {
+        Ok(()) => (),
+        Err(JwtError::Invalid) => return Err(StatusCode::UNAUTHORIZED.into_response()),
+        Err(JwtError::Expired) => {
+            return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response());
         }
-
-        Err(_) => {
-            return Err(StatusCode::UNAUTHORIZED.into_response());
+        Err(JwtError::InsufficientPermission) => {
+            return Err((StatusCode::FORBIDDEN, "Insufficient JWT permission".to_owned()).into_response());
         }
     }
 
@@ -97,3 +167,93 @@ where
 
     Ok(next.run(request).await)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::header::AUTHORIZATION, middleware, routing::get, Router};
+    use snarkvm::prelude::{TestRng, Testnet3};
+    use tower::ServiceExt;
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns a JWT token with the given permission.
+    fn sample_token(permission: Permission) -> String {
+        let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(&mut TestRng::default()).unwrap()).unwrap();
+        Claims::new(address, permission).to_jwt_string().unwrap()
+    }
+
+    /// Returns a router with a read-only route and an admin route, laid out as in the REST server.
+    fn sample_router(rest_auth: bool) -> Router {
+        let admin_router =
+            Router::new().route("/admin", get(|| async {})).route_layer(middleware::from_fn(admin_middleware));
+        let read_router = Router::new().route("/read", get(|| async {}));
+        let read_router = match rest_auth {
+            true => read_router.route_layer(middleware::from_fn(auth_middleware)),
+            false => read_router,
+        };
+        read_router.merge(admin_router)
+    }
+
+    /// Returns the status of a request to the given route of the router, with the given JWT token.
+    async fn request_status(router: Router, uri: &str, token: Option<&str>) -> StatusCode {
+        let mut request = Request::get(uri);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        router.oneshot(request.body(Body::empty()).unwrap()).await.unwrap().status()
+    }
+
+    #[test]
+    fn test_permission_allows() {
+        assert!(Permission::ReadOnly.allows(Permission::ReadOnly));
+        assert!(!Permission::ReadOnly.allows(Permission::Admin));
+        assert!(Permission::Admin.allows(Permission::ReadOnly));
+        assert!(Permission::Admin.allows(Permission::Admin));
+    }
+
+    #[test]
+    fn test_verify_jwt() {
+        let read_only = sample_token(Permission::ReadOnly);
+        let admin = sample_token(Permission::Admin);
+        assert_eq!(verify_jwt(&read_only, Permission::ReadOnly), Ok(()));
+        assert_eq!(verify_jwt(&read_only, Permission::Admin), Err(JwtError::InsufficientPermission));
+        assert_eq!(verify_jwt(&admin, Permission::Admin), Ok(()));
+        assert_eq!(verify_jwt("invalid", Permission::ReadOnly), Err(JwtError::Invalid));
+
+        // Ensure an expired token is rejected.
+        let expired = Claims { sub: "aleo1xx".to_string(), perm: Permission::Admin, iat: 0, exp: 1 };
+        assert_eq!(verify_jwt(&expired.to_jwt_string().unwrap(), Permission::ReadOnly), Err(JwtError::Expired));
+    }
+
+    #[tokio::test]
+    async fn test_admin_middleware() {
+        let read_only = sample_token(Permission::ReadOnly);
+        let admin = sample_token(Permission::Admin);
+
+        // Ensure the admin route requires an admin token, whether or not the read-only routes require a token.
+        for rest_auth in [false, true] {
+            let router = sample_router(rest_auth);
+            assert_eq!(request_status(router.clone(), "/admin", None).await, StatusCode::UNAUTHORIZED);
+            assert_eq!(request_status(router.clone(), "/admin", Some("invalid")).await, StatusCode::UNAUTHORIZED);
+            assert_eq!(request_status(router.clone(), "/admin", Some(&read_only)).await, StatusCode::FORBIDDEN);
+            assert_eq!(request_status(router, "/admin", Some(&admin)).await, StatusCode::OK);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auth_middleware() {
+        let read_only = sample_token(Permission::ReadOnly);
+        let admin = sample_token(Permission::Admin);
+
+        // Ensure the read-only route is open without `--restauth`.
+        let router = sample_router(false);
+        assert_eq!(request_status(router, "/read", None).await, StatusCode::OK);
+
+        // Ensure the read-only route requires a read-only (or admin) token with `--restauth`.
+        let router = sample_router(true);
+        assert_eq!(request_status(router.clone(), "/read", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(request_status(router.clone(), "/read", Some(&read_only)).await, StatusCode::OK);
+        assert_eq!(request_status(router, "/read", Some(&admin)).await, StatusCode::OK);
+    }
+}
@@ -18,6 +18,7 @@ use axum::{
 };
 
 /// An enum of error handlers for the REST API server.
+#[derive(Debug)]
 pub struct RestError(pub String);
 
 impl IntoResponse for RestError {
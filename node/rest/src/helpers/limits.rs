@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, ensure, Result};
+use axum::http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    HeaderValue,
+    Method,
+};
+use governor::middleware::NoOpMiddleware;
+use tower_governor::{
+    governor::{GovernorConfig, GovernorConfigBuilder},
+    key_extractor::PeerIpKeyExtractor,
+};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// The maximum size of a request body, in bytes.
+pub const MAX_BODY_SIZE: usize = 10 * 1024 * 1024; // 10 MB
+
+/// The default number of requests per second permitted from each IP address.
+pub const DEFAULT_REST_RPS: u32 = 10;
+
+/// Returns the CORS layer, which allows the given origins, or any origin if no origins are given.
+pub fn cors_layer(origins: &[String]) -> Result<CorsLayer> {
+    let allow_origin = match origins.is_empty() {
+        true => AllowOrigin::from(Any),
+        false => AllowOrigin::list(
+            origins
+                .iter()
+                .map(|origin| HeaderValue::from_str(origin).map_err(|_| anyhow!("Invalid CORS origin '{origin}'")))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+    };
+    Ok(CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers([CONTENT_TYPE, AUTHORIZATION]))
+}
+
+/// Returns the rate limiter configuration, which permits `rest_rps` requests per second from each IP address.
+pub fn governor_config(rest_rps: u32) -> Result<GovernorConfig<PeerIpKeyExtractor, NoOpMiddleware>> {
+    ensure!(rest_rps > 0, "The REST rate limit must be at least 1 request per second");
+    // Replenish the requests evenly over each second, and allow a burst of up to one second of requests.
+    GovernorConfigBuilder::default()
+        .per_nanosecond(1_000_000_000 / rest_rps as u64)
+        .burst_size(rest_rps)
+        .finish()
+        .ok_or_else(|| anyhow!("Failed to configure the REST rate limit"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cors_layer() {
+        assert!(cors_layer(&[]).is_ok());
+        assert!(cors_layer(&["https://explorer.aleo.org".to_string(), "http://localhost:3000".to_string()]).is_ok());
+        assert!(cors_layer(&["invalid\norigin".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_governor_config() {
+        assert!(governor_config(DEFAULT_REST_RPS).is_ok());
+        assert!(governor_config(1).is_ok());
+        assert!(governor_config(0).is_err());
+    }
+}
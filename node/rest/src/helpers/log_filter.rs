@@ -0,0 +1,36 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::OnceCell;
+
+/// A handler that replaces the log filter of the node, given a filter directive (e.g. `debug` or `snarkos=trace`).
+pub type LogFilterHandler = Box<dyn Fn(&str) -> Result<()> + Send + Sync>;
+
+/// The log filter handler, registered by the logger of the node.
+static LOG_FILTER_HANDLER: OnceCell<LogFilterHandler> = OnceCell::new();
+
+/// Registers the handler used to change the log filter at runtime.
+/// Note: The handler can only be registered once.
+pub fn register_log_filter_handler(handler: LogFilterHandler) -> Result<()> {
+    LOG_FILTER_HANDLER.set(handler).map_err(|_| anyhow!("The log filter handler is already registered"))
+}
+
+/// Replaces the log filter of the node with the given filter directive.
+pub(crate) fn set_log_filter(directive: &str) -> Result<()> {
+    match LOG_FILTER_HANDLER.get() {
+        Some(handler) => handler(directive),
+        None => Err(anyhow!("The log filter cannot be changed at runtime for this node")),
+    }
+}
@@ -17,3 +17,12 @@ pub use auth::*;
 
 mod error;
 pub use error::*;
+
+mod limits;
+pub use limits::*;
+
+mod log_filter;
+pub use log_filter::*;
+
+mod recent_logs;
+pub use recent_logs::*;
@@ -0,0 +1,54 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+
+/// The maximum number of recent log lines that are retained.
+pub const MAX_RECENT_LOG_LINES: usize = 100;
+
+/// The recent log lines, recorded by the logger of the node.
+static RECENT_LOG_LINES: Lazy<Mutex<VecDeque<String>>> = Lazy::new(Default::default);
+
+/// Records the given log line, evicting the oldest line once `MAX_RECENT_LOG_LINES` are retained.
+pub fn record_log_line(line: &str) {
+    let mut lines = RECENT_LOG_LINES.lock();
+    if lines.len() >= MAX_RECENT_LOG_LINES {
+        lines.pop_front();
+    }
+    lines.push_back(line.trim_end().to_string());
+}
+
+/// Returns the recent log lines, from the oldest to the newest.
+pub(crate) fn recent_log_lines() -> Vec<String> {
+    RECENT_LOG_LINES.lock().iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_log_lines() {
+        for i in 0..MAX_RECENT_LOG_LINES + 5 {
+            record_log_line(&format!("line {i}\n"));
+        }
+        // Ensure only the most recent lines are retained, without their trailing newlines.
+        let lines = recent_log_lines();
+        assert_eq!(lines.len(), MAX_RECENT_LOG_LINES);
+        assert_eq!(lines[0], "line 5");
+        assert_eq!(lines[MAX_RECENT_LOG_LINES - 1], format!("line {}", MAX_RECENT_LOG_LINES + 4));
+    }
+}
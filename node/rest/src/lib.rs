@@ -20,11 +20,17 @@ extern crate tracing;
 mod helpers;
 pub use helpers::*;
 
+mod prover;
+pub use prover::*;
+
 mod routes;
 
+mod wallet;
+
 use snarkos_node_consensus::Consensus;
 use snarkos_node_messages::{Data, Message, UnconfirmedTransaction};
 use snarkos_node_router::Routing;
+use snarkos_node_wallet::Wallet;
 use snarkvm::{
     console::{program::ProgramID, types::Field},
     prelude::{cfg_into_iter, store::ConsensusStorage, Ledger, Network},
@@ -32,22 +38,46 @@ use snarkvm::{
 
 use anyhow::Result;
 use axum::{
+    error_handling::HandleErrorLayer,
     extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State},
-    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    http::{Request, StatusCode},
     middleware,
     middleware::Next,
     response::Response,
     routing::{get, post},
+    BoxError,
     Json,
 };
 use axum_extra::response::ErasedJson;
+use governor::middleware::NoOpMiddleware;
 use parking_lot::Mutex;
-use std::{net::SocketAddr, sync::Arc};
+use std::{marker::PhantomData, net::SocketAddr, sync::Arc};
 use tokio::task::JoinHandle;
-use tower_http::{
-    cors::{Any, CorsLayer},
-    trace::TraceLayer,
+use tower::{layer::layer_fn, ServiceBuilder};
+use tower_governor::{
+    errors::display_error,
+    governor::{Governor, GovernorConfig},
+    key_extractor::PeerIpKeyExtractor,
 };
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
+
+/// The minimum number of connected peers for the node to be ready to serve traffic.
+pub const READY_MINIMUM_PEERS: usize = 1;
+/// The maximum number of blocks the ledger may lag behind its peers for the node to be ready to serve traffic.
+pub const READY_MAXIMUM_BLOCKS_BEHIND: u32 = 10;
+
+/// The settings of a REST server.
+#[derive(Clone, Debug)]
+pub struct RestOptions {
+    /// The IP address and port for the REST server.
+    pub ip: SocketAddr,
+    /// If `true`, the read-only routes require a JWT token, in addition to the admin routes.
+    pub auth: bool,
+    /// The maximum number of requests per second from each IP address (the probes are not limited).
+    pub rps: u32,
+    /// The origins allowed to make cross-origin requests (any origin if empty).
+    pub cors_origins: Vec<String>,
+}
 
 /// A REST API server for the ledger.
 #[derive(Clone)]
@@ -58,22 +88,28 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
     ledger: Ledger<N, C>,
     /// The node (routing).
     routing: Arc<R>,
+    /// The wallet, if enabled.
+    wallet: Option<Wallet<N>>,
     /// The server handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
 
 impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
-    /// Initializes a new instance of the server.
+    /// Initializes a new instance of the server, with the given settings.
     pub fn start(
-        rest_ip: SocketAddr,
+        options: &RestOptions,
         consensus: Option<Consensus<N, C>>,
         ledger: Ledger<N, C>,
         routing: Arc<R>,
+        wallet: Option<Wallet<N>>,
     ) -> Result<Self> {
+        // Initialize the CORS policy and the rate limiter.
+        let cors = cors_layer(&options.cors_origins)?;
+        let governor_config = governor_config(options.rps)?;
         // Initialize the server.
-        let mut server = Self { consensus, ledger, routing, handles: Default::default() };
+        let mut server = Self { consensus, ledger, routing, wallet, handles: Default::default() };
         // Spawn the server.
-        server.spawn_server(rest_ip);
+        server.spawn_server(options.ip, options.auth, cors, governor_config);
         // Return the server.
         Ok(server)
     }
@@ -92,13 +128,54 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
-    fn spawn_server(&mut self, rest_ip: SocketAddr) {
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-            .allow_headers([CONTENT_TYPE]);
+    fn spawn_server(
+        &mut self,
+        rest_ip: SocketAddr,
+        rest_auth: bool,
+        cors: CorsLayer,
+        governor_config: GovernorConfig<PeerIpKeyExtractor, NoOpMiddleware>,
+    ) {
+        // The admin routes, which change the state of the node or expose its logs, always require an admin JWT token.
+        let admin_router = {
+            axum::Router::new()
 
-        let router = {
+            // POST ../peers/..
+            .route("/testnet3/peers/connect", post(Self::peers_connect))
+            .route("/testnet3/peers/disconnect", post(Self::peers_disconnect))
+            .route("/testnet3/peers/ban", post(Self::peers_ban))
+            .route("/testnet3/peers/unban", post(Self::peers_unban))
+
+            // GET and POST ../admin/..
+            .route("/testnet3/admin/config", get(Self::get_runtime_config))
+            .route("/testnet3/admin/logFilter", post(Self::admin_set_log_filter))
+            .route("/testnet3/admin/maxPeers", post(Self::admin_set_max_peers))
+            .route("/testnet3/admin/minimumFee", post(Self::admin_set_minimum_fee))
+            .route("/testnet3/admin/memoryPool/capacity", post(Self::admin_set_memory_pool_capacity))
+            .route("/testnet3/admin/bootstrapPeers", post(Self::admin_connect_bootstrap_peers))
+
+            // GET ../node/logs
+            .route("/testnet3/node/logs", get(Self::get_node_logs))
+
+            // GET and POST ../wallet/..
+            .route("/testnet3/wallet/balance", get(Self::get_wallet_balance))
+            .route("/testnet3/wallet/records", get(Self::get_wallet_records))
+            .route("/testnet3/wallet/history", get(Self::get_wallet_history))
+            .route("/testnet3/wallet/events", get(Self::get_wallet_events))
+            .route("/testnet3/wallet/labels", get(Self::get_wallet_labels).post(Self::wallet_label))
+            .route("/testnet3/wallet/rescan", get(Self::get_wallet_rescan).post(Self::wallet_rescan))
+            .route("/testnet3/wallet/import", post(Self::wallet_import))
+            .route("/testnet3/wallet/watch", post(Self::wallet_watch))
+            .route("/testnet3/wallet/send", post(Self::wallet_send))
+            .route("/testnet3/wallet/prepare", post(Self::wallet_prepare))
+            .route("/testnet3/wallet/release", post(Self::wallet_release))
+            .route("/testnet3/wallet/unlock", post(Self::wallet_unlock))
+            .route("/testnet3/wallet/lock", post(Self::wallet_lock))
+
+            // Require an admin JWT token.
+            .route_layer(middleware::from_fn(admin_middleware))
+        };
+
+        let read_router = {
             axum::Router::new()
 
             // GET ../latest/..
@@ -109,14 +186,21 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
             // GET ../block/..
             .route("/testnet3/block/:height_or_hash", get(Self::get_block))
+            .route("/testnet3/block/height/:height", get(Self::get_block_by_height))
             // The path param here is actually only the height, but the name must match the route
             // above, otherwise there'll be a conflict at runtime.
             .route("/testnet3/block/:height_or_hash/transactions", get(Self::get_block_transactions))
+            .route("/testnet3/block/:height_or_hash/confirmations", get(Self::get_block_confirmations))
 
             // GET and POST ../transaction/..
             .route("/testnet3/transaction/:id", get(Self::get_transaction))
+            .route("/testnet3/transaction/:id/confirmations", get(Self::get_transaction_confirmations))
             .route("/testnet3/transaction/broadcast", post(Self::transaction_broadcast))
 
+            // POST ../decode/..
+            .route("/testnet3/decode/transaction", post(Self::decode_transaction))
+            .route("/testnet3/decode/block", post(Self::decode_block))
+
             // GET ../find/..
             .route("/testnet3/find/blockHash/:tx_id", get(Self::find_block_hash))
             .route("/testnet3/find/transactionID/deployment/:program_id", get(Self::find_transaction_id_from_program_id))
@@ -127,6 +211,8 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .route("/testnet3/peers/count", get(Self::get_peers_count))
             .route("/testnet3/peers/all", get(Self::get_peers_all))
             .route("/testnet3/peers/all/metrics", get(Self::get_peers_all_metrics))
+            .route("/testnet3/peers/info", get(Self::get_peers_info))
+            .route("/testnet3/peers/banned", get(Self::get_peers_banned))
 
             // GET ../program/..
             .route("/testnet3/program/:id", get(Self::get_program))
@@ -136,11 +222,46 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             // GET misc endpoints.
             .route("/testnet3/blocks", get(Self::get_blocks))
             .route("/testnet3/height/:hash", get(Self::get_height))
+            .route("/testnet3/memoryPool/info", get(Self::get_memory_pool_info))
+            .route("/testnet3/memoryPool/raw", get(Self::get_raw_memory_pool))
             .route("/testnet3/memoryPool/transactions", get(Self::get_memory_pool_transactions))
+            .route("/testnet3/mempool", get(Self::get_memory_pool_transactions))
+            .route("/testnet3/fee/estimate/:target_blocks", get(Self::get_fee_estimate))
             .route("/testnet3/statePath/:commitment", get(Self::get_state_path_for_commitment))
             .route("/testnet3/beacons", get(Self::get_beacons))
             .route("/testnet3/node/address", get(Self::get_node_address))
+            .route("/testnet3/node/status", get(Self::get_node_status))
             .route("/testnet3/node/env", get(Self::get_env_info))
+        };
+
+        // If enabled, require a read-only (or admin) JWT token for the remaining routes.
+        let read_router = match rest_auth {
+            true => read_router.route_layer(middleware::from_fn(auth_middleware)),
+            false => read_router,
+        };
+
+        // The health and readiness probes never require a JWT token, and are not rate limited,
+        // so orchestrators can poll them.
+        let probe_router = {
+            axum::Router::new()
+
+            // GET /health and /ready
+            .route("/health", get(Self::get_health))
+            .route("/ready", get(Self::get_ready))
+        };
+
+        // Share the rate limiter across the routes, as the layer is cloned for each route.
+        let governor_config = Arc::new(governor_config);
+        let router = {
+            read_router.merge(admin_router)
+
+            // Limit the number of requests per second from each IP address, except for the probes.
+            .layer(
+                ServiceBuilder::new()
+                    .layer(HandleErrorLayer::new(|error: BoxError| async move { display_error(error) }))
+                    .layer(layer_fn(move |inner| Governor::new(inner, &governor_config))),
+            )
+            .merge(probe_router)
 
             // Pass in `Rest` to make things convenient.
             .with_state(self.clone())
@@ -151,9 +272,7 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             // Enable CORS.
             .layer(cors)
             // Cap body size at 10MB.
-            .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
-            // JWT auth.
-            // .layer(middleware::from_fn(auth_middleware))
+            .layer(DefaultBodyLimit::max(MAX_BODY_SIZE))
         };
 
         self.handles.lock().push(tokio::spawn(async move {
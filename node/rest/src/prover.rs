@@ -0,0 +1,372 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use serde::Serialize;
+use snarkvm::prelude::Address;
+
+/// The control handle of a prover, as exposed over the REST API.
+pub trait Proving<N: Network>: 'static + Send + Sync {
+    /// Returns `true` if the prover is generating solutions for the coinbase puzzle.
+    fn is_proving(&self) -> bool;
+
+    /// Resumes generating solutions for the coinbase puzzle.
+    fn start_proving(&self);
+
+    /// Pauses generating solutions for the coinbase puzzle.
+    fn stop_proving(&self);
+
+    /// Returns the address that is rewarded for the solutions of the prover.
+    fn reward_address(&self) -> Address<N>;
+
+    /// Sets the address that is rewarded for the solutions of the prover.
+    fn set_reward_address(&self, address: Address<N>);
+
+    /// Returns the number of solutions attempted per second, averaged over the recent past.
+    fn hashrate(&self) -> f64;
+}
+
+/// The `get_prover_status` response object.
+#[derive(Serialize)]
+pub(crate) struct ProverStatus<N: Network> {
+    /// `true` if the prover is generating solutions.
+    is_proving: bool,
+    /// The address that is rewarded for the solutions of the prover.
+    reward_address: Address<N>,
+    /// The number of solutions attempted per second.
+    hashrate: f64,
+}
+
+/// A REST API server for the prover.
+pub struct ProverRest<N: Network, P: Proving<N>> {
+    /// The prover.
+    prover: Arc<P>,
+    /// The server handles.
+    handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network, P: Proving<N>> Clone for ProverRest<N, P> {
+    fn clone(&self) -> Self {
+        Self { prover: self.prover.clone(), handles: self.handles.clone(), _phantom: PhantomData }
+    }
+}
+
+impl<N: Network, P: Proving<N>> ProverRest<N, P> {
+    /// Initializes a new instance of the server, with the given settings.
+    pub fn start(options: &RestOptions, prover: Arc<P>) -> Result<Self> {
+        // Initialize the CORS policy and the rate limiter.
+        let cors = cors_layer(&options.cors_origins)?;
+        let governor_config = governor_config(options.rps)?;
+        // Initialize the server.
+        let mut server = Self { prover, handles: Default::default(), _phantom: PhantomData };
+        // Spawn the server.
+        server.spawn_server(options.ip, options.auth, cors, governor_config);
+        // Return the server.
+        Ok(server)
+    }
+
+    /// Returns the handles.
+    pub const fn handles(&self) -> &Arc<Mutex<Vec<JoinHandle<()>>>> {
+        &self.handles
+    }
+}
+
+impl<N: Network, P: Proving<N>> ProverRest<N, P> {
+    fn spawn_server(
+        &mut self,
+        rest_ip: SocketAddr,
+        rest_auth: bool,
+        cors: CorsLayer,
+        governor_config: GovernorConfig<PeerIpKeyExtractor, NoOpMiddleware>,
+    ) {
+        let router = self.router(rest_auth, cors, governor_config);
+
+        self.handles.lock().push(tokio::spawn(async move {
+            axum::Server::bind(&rest_ip)
+                .serve(router.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .expect("couldn't start rest server");
+        }))
+    }
+
+    /// Returns the routes of the server.
+    fn router(
+        &self,
+        rest_auth: bool,
+        cors: CorsLayer,
+        governor_config: GovernorConfig<PeerIpKeyExtractor, NoOpMiddleware>,
+    ) -> axum::Router {
+        // The admin routes, which change the state of the prover or expose its logs, always require an admin JWT token.
+        let admin_router = {
+            axum::Router::new()
+
+            // POST ../prover/..
+            .route("/testnet3/prover/start", post(Self::prover_start))
+            .route("/testnet3/prover/stop", post(Self::prover_stop))
+            .route("/testnet3/prover/address", post(Self::prover_set_address))
+
+            // GET ../node/logs
+            .route("/testnet3/node/logs", get(Self::get_node_logs))
+
+            // Require an admin JWT token.
+            .route_layer(middleware::from_fn(admin_middleware))
+        };
+
+        let read_router = {
+            axum::Router::new()
+
+            // GET ../prover/..
+            .route("/testnet3/prover/status", get(Self::get_prover_status))
+            .route("/testnet3/prover/hashrate", get(Self::get_prover_hashrate))
+        };
+
+        // If enabled, require a read-only (or admin) JWT token for the remaining routes.
+        let read_router = match rest_auth {
+            true => read_router.route_layer(middleware::from_fn(auth_middleware)),
+            false => read_router,
+        };
+
+        // The health probe never requires a JWT token, and is not rate limited, so orchestrators can poll it.
+        let probe_router = axum::Router::new().route("/health", get(Self::get_health));
+
+        // Share the rate limiter across the routes, as the layer is cloned for each route.
+        let governor_config = Arc::new(governor_config);
+        read_router.merge(admin_router)
+
+            // Limit the number of requests per second from each IP address, except for the probe.
+            .layer(
+                ServiceBuilder::new()
+                    .layer(HandleErrorLayer::new(|error: BoxError| async move { display_error(error) }))
+                    .layer(layer_fn(move |inner| Governor::new(inner, &governor_config))),
+            )
+            .merge(probe_router)
+
+            // Pass in `ProverRest` to make things convenient.
+            .with_state(self.clone())
+            // Enable tower-http tracing.
+            .layer(TraceLayer::new_for_http())
+            // Custom logging.
+            .layer(middleware::from_fn(log_middleware))
+            // Enable CORS.
+            .layer(cors)
+            // Cap body size at 10MB.
+            .layer(DefaultBodyLimit::max(MAX_BODY_SIZE))
+    }
+}
+
+impl<N: Network, P: Proving<N>> ProverRest<N, P> {
+    // GET /health
+    pub(crate) async fn get_health() -> StatusCode {
+        StatusCode::OK
+    }
+
+    // GET /testnet3/prover/status
+    pub(crate) async fn get_prover_status(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(ProverStatus {
+            is_proving: rest.prover.is_proving(),
+            reward_address: rest.prover.reward_address(),
+            hashrate: rest.prover.hashrate(),
+        })
+    }
+
+    // GET /testnet3/prover/hashrate
+    pub(crate) async fn get_prover_hashrate(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.prover.hashrate())
+    }
+
+    // GET /testnet3/node/logs
+    pub(crate) async fn get_node_logs() -> ErasedJson {
+        ErasedJson::pretty(recent_log_lines())
+    }
+
+    // POST /testnet3/prover/start
+    pub(crate) async fn prover_start(State(rest): State<Self>) -> ErasedJson {
+        rest.prover.start_proving();
+        ErasedJson::pretty(rest.prover.is_proving())
+    }
+
+    // POST /testnet3/prover/stop
+    pub(crate) async fn prover_stop(State(rest): State<Self>) -> ErasedJson {
+        rest.prover.stop_proving();
+        ErasedJson::pretty(rest.prover.is_proving())
+    }
+
+    // POST /testnet3/prover/address
+    pub(crate) async fn prover_set_address(State(rest): State<Self>, Json(address): Json<Address<N>>) -> ErasedJson {
+        rest.prover.set_reward_address(address);
+        ErasedJson::pretty(rest.prover.reward_address())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{Body, HttpBody},
+        http::header::{AUTHORIZATION, CONTENT_TYPE},
+    };
+    use snarkvm::prelude::{PrivateKey, TestRng, Testnet3};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tower::ServiceExt;
+
+    type CurrentNetwork = Testnet3;
+
+    /// A prover that only records the state set over the REST API.
+    struct SampleProver {
+        is_proving: AtomicBool,
+        reward_address: parking_lot::RwLock<Address<CurrentNetwork>>,
+    }
+
+    impl Proving<CurrentNetwork> for SampleProver {
+        fn is_proving(&self) -> bool {
+            self.is_proving.load(Ordering::SeqCst)
+        }
+
+        fn start_proving(&self) {
+            self.is_proving.store(true, Ordering::SeqCst)
+        }
+
+        fn stop_proving(&self) {
+            self.is_proving.store(false, Ordering::SeqCst)
+        }
+
+        fn reward_address(&self) -> Address<CurrentNetwork> {
+            *self.reward_address.read()
+        }
+
+        fn set_reward_address(&self, address: Address<CurrentNetwork>) {
+            *self.reward_address.write() = address
+        }
+
+        fn hashrate(&self) -> f64 {
+            12.5
+        }
+    }
+
+    /// Returns a new address.
+    fn sample_address(rng: &mut TestRng) -> Address<CurrentNetwork> {
+        Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap()
+    }
+
+    /// Returns a JWT token with the given permission.
+    fn sample_token(rng: &mut TestRng, permission: Permission) -> String {
+        Claims::new(sample_address(rng), permission).to_jwt_string().unwrap()
+    }
+
+    /// Returns the server for a new prover, and its routes.
+    fn sample_server(rng: &mut TestRng, rest_auth: bool) -> (Arc<SampleProver>, axum::Router) {
+        let prover =
+            Arc::new(SampleProver { is_proving: AtomicBool::new(true), reward_address: sample_address(rng).into() });
+        let server = ProverRest { prover: prover.clone(), handles: Default::default(), _phantom: PhantomData };
+        let router = server.router(rest_auth, cors_layer(&[]).unwrap(), governor_config(DEFAULT_REST_RPS).unwrap());
+        (prover, router)
+    }
+
+    /// Sends the given request with the given JWT token and JSON body, and returns the status and body of the response.
+    async fn request(
+        router: &axum::Router,
+        request: axum::http::request::Builder,
+        token: Option<&str>,
+        body: Option<String>,
+    ) -> (StatusCode, String) {
+        let mut request = request.header(CONTENT_TYPE, "application/json");
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let mut request = request.body(body.map(Body::from).unwrap_or_else(Body::empty)).unwrap();
+        // The logging middleware and the rate limiter require the IP address of the peer.
+        request.extensions_mut().insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4130))));
+
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let mut body = response.into_body();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = body.data().await {
+            bytes.extend_from_slice(&chunk.unwrap());
+        }
+        (status, String::from_utf8(bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_prover_start_stop() {
+        let rng = &mut TestRng::default();
+        let (prover, router) = sample_server(rng, false);
+        let read_only = sample_token(rng, Permission::ReadOnly);
+        let admin = sample_token(rng, Permission::Admin);
+
+        // Ensure the prover is only stopped with an admin token.
+        let stop = || Request::post("/testnet3/prover/stop");
+        assert_eq!(request(&router, stop(), None, None).await.0, StatusCode::UNAUTHORIZED);
+        assert_eq!(request(&router, stop(), Some(&read_only), None).await.0, StatusCode::FORBIDDEN);
+        assert!(prover.is_proving());
+        assert_eq!(request(&router, stop(), Some(&admin), None).await, (StatusCode::OK, "false".to_string()));
+        assert!(!prover.is_proving());
+
+        // Ensure the prover is only started with an admin token.
+        let start = || Request::post("/testnet3/prover/start");
+        assert_eq!(request(&router, start(), Some(&read_only), None).await.0, StatusCode::FORBIDDEN);
+        assert!(!prover.is_proving());
+        assert_eq!(request(&router, start(), Some(&admin), None).await, (StatusCode::OK, "true".to_string()));
+        assert!(prover.is_proving());
+    }
+
+    #[tokio::test]
+    async fn test_prover_set_address() {
+        let rng = &mut TestRng::default();
+        let (prover, router) = sample_server(rng, false);
+        let admin = sample_token(rng, Permission::Admin);
+        let address = sample_address(rng);
+        let body = || Some(format!("\"{address}\""));
+
+        // Ensure the reward address is only set with an admin token.
+        let set_address = || Request::post("/testnet3/prover/address");
+        assert_eq!(request(&router, set_address(), None, body()).await.0, StatusCode::UNAUTHORIZED);
+        assert_ne!(prover.reward_address(), address);
+        assert_eq!(request(&router, set_address(), Some(&admin), body()).await, (StatusCode::OK, body().unwrap()));
+        assert_eq!(prover.reward_address(), address);
+
+        // Ensure an invalid address is rejected.
+        let invalid = Some("\"aleo1invalid\"".to_string());
+        assert!(request(&router, set_address(), Some(&admin), invalid).await.0.is_client_error());
+        assert_eq!(prover.reward_address(), address);
+    }
+
+    #[tokio::test]
+    async fn test_prover_status() {
+        let rng = &mut TestRng::default();
+        let read_only = sample_token(rng, Permission::ReadOnly);
+
+        for rest_auth in [false, true] {
+            let (prover, router) = sample_server(rng, rest_auth);
+            let token = rest_auth.then_some(read_only.as_str());
+
+            // Ensure the hashrate and the status are returned.
+            let (status, hashrate) = request(&router, Request::get("/testnet3/prover/hashrate"), token, None).await;
+            assert_eq!((status, hashrate.as_str()), (StatusCode::OK, "12.5"));
+            let (status, body) = request(&router, Request::get("/testnet3/prover/status"), token, None).await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(body.contains("\"is_proving\": true"));
+            assert!(body.contains(&format!("\"reward_address\": \"{}\"", prover.reward_address())));
+            assert!(body.contains("\"hashrate\": 12.5"));
+
+            // Ensure a token is only required with `--restauth`, except for the health probe.
+            let (status, _) = request(&router, Request::get("/testnet3/prover/status"), None, None).await;
+            assert_eq!(status == StatusCode::UNAUTHORIZED, rest_auth);
+            let (status, _) = request(&router, Request::get("/health"), None, None).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+    }
+}
@@ -17,7 +17,17 @@ use super::*;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use snarkos_node_env::ENV_INFO;
-use snarkvm::prelude::{block::Transaction, Identifier, Plaintext};
+use snarkos_node_messages::NodeType;
+use snarkvm::prelude::{
+    block::{Block, Transaction},
+    Address,
+    FromBytes,
+    Identifier,
+    Plaintext,
+    ToBytes,
+};
+
+use axum::response::IntoResponse;
 
 /// The `get_blocks` query object.
 #[derive(Deserialize, Serialize)]
@@ -28,6 +38,188 @@ pub(crate) struct BlockRange {
     end: u32,
 }
 
+/// The `get_memory_pool_info` response object.
+#[derive(Serialize)]
+pub(crate) struct MemoryPoolInfo {
+    /// The number of unconfirmed transactions.
+    size: usize,
+    /// The combined size of the unconfirmed transactions, in bytes.
+    bytes: usize,
+    /// The lowest fee among the unconfirmed transactions, in microcredits.
+    min_fee: u64,
+}
+
+/// The `get_raw_memory_pool` response object, for a single unconfirmed transaction.
+#[derive(Serialize)]
+pub(crate) struct MemoryPoolEntry<N: Network> {
+    /// The transaction ID.
+    id: N::TransactionID,
+    /// The transaction fee, in microcredits.
+    fee: u64,
+    /// The size of the transaction, in bytes.
+    size: usize,
+    /// The number of seconds since the transaction was added to the memory pool.
+    age: u64,
+}
+
+/// The `get_fee_estimate` response object.
+#[derive(Serialize)]
+pub(crate) struct FeeEstimate {
+    /// The number of blocks within which the transaction should be confirmed.
+    target_blocks: u32,
+    /// The estimated fee rate, in microcredits per byte.
+    fee_rate: f64,
+    /// The number of recent blocks the estimate is based on.
+    num_blocks: usize,
+}
+
+/// The `get_peers_info` response object, for a single connected peer.
+#[derive(Serialize)]
+pub(crate) struct PeerInfo<N: Network> {
+    /// The IP address of the peer, with the port set to the listener port.
+    ip: SocketAddr,
+    /// The Aleo address of the peer.
+    address: Address<N>,
+    /// The node type of the peer.
+    node_type: NodeType,
+    /// The message version of the peer.
+    version: u32,
+    /// The user agent of the peer.
+    user_agent: String,
+    /// `true` if this node initiated the connection to the peer.
+    is_outbound: bool,
+    /// The round-trip time of the last ping to the peer, in milliseconds.
+    latency_ms: Option<u64>,
+    /// The number of messages sent to the peer.
+    messages_sent: u64,
+    /// The number of bytes sent to the peer.
+    bytes_sent: u64,
+    /// The number of messages received from the peer.
+    messages_received: u64,
+    /// The number of bytes received from the peer.
+    bytes_received: u64,
+}
+
+/// The `get_node_status` response object.
+#[derive(Serialize)]
+pub(crate) struct NodeStatus<N: Network> {
+    /// The name of the network.
+    network: &'static str,
+    /// The node type of this node.
+    node_type: NodeType,
+    /// The latest block height in the ledger.
+    height: u32,
+    /// The latest block hash in the ledger.
+    hash: N::BlockHash,
+    /// The greatest block height advertised by the connected peers.
+    peer_height: u32,
+    /// `true` if the ledger has caught up to the greatest peer height.
+    is_synced: bool,
+    /// The sync progress, as a fraction of the greatest peer height (between `0.0` and `1.0`).
+    sync_progress: f64,
+    /// The latest coinbase target.
+    coinbase_target: u64,
+    /// The latest proof target.
+    proof_target: u64,
+    /// The number of connected peers.
+    peers: usize,
+    /// The number of unconfirmed transactions in the memory pool, if this node maintains one.
+    memory_pool_size: Option<usize>,
+}
+
+/// The `get_ready` response object.
+#[derive(Serialize)]
+pub(crate) struct Readiness {
+    /// `true` if the node is ready to serve traffic.
+    is_ready: bool,
+    /// `true` if the ledger storage is open and readable.
+    is_storage_open: bool,
+    /// The number of connected peers.
+    peers: usize,
+    /// The number of blocks the ledger lags behind the greatest peer height.
+    blocks_behind: u32,
+}
+
+/// The status of a block or transaction, relative to the current main chain.
+/// Note: there is no status for a block or transaction on a stale fork, as the ledger cannot tell it apart from
+/// an unknown one. The ledger only stores the blocks of the main chain, as a block is only added if it extends the
+/// latest block (see `check_next_block`), and blocks are never rolled back. So a block or transaction that is not
+/// on the main chain (nor in the memory pool) was never stored, and is reported as not found.
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ConfirmationStatus {
+    /// The block (or the block containing the transaction) is on the current main chain.
+    Confirmed,
+    /// The transaction is in the memory pool, and has not been included in a block.
+    Unconfirmed,
+}
+
+/// The `get_block_confirmations` and `get_transaction_confirmations` response object.
+#[derive(Serialize)]
+pub(crate) struct Confirmations<N: Network> {
+    /// The status relative to the current main chain.
+    status: ConfirmationStatus,
+    /// The hash of the block on the main chain, if confirmed.
+    block_hash: Option<N::BlockHash>,
+    /// The height of the block on the main chain, if confirmed.
+    height: Option<u32>,
+    /// The number of blocks on the main chain from the block up to (and including) the latest block.
+    confirmations: u32,
+}
+
+/// The `get_runtime_config` response object.
+#[derive(Serialize)]
+pub(crate) struct RuntimeConfig {
+    /// The maximum number of connected peers.
+    max_peers: usize,
+    /// The bootstrap peers, including those added at runtime.
+    bootstrap_peers: Vec<SocketAddr>,
+    /// The minimum fee (in microcredits) to add a transaction to the memory pool, if this node maintains one.
+    minimum_fee: Option<u64>,
+    /// The maximum number of unconfirmed transactions in the memory pool, if this node maintains one.
+    memory_pool_capacity: Option<usize>,
+}
+
+/// The `decode_transaction` response object.
+#[derive(Serialize)]
+pub(crate) struct DecodedTransaction<N: Network> {
+    /// The transaction, in its snarkVM JSON representation.
+    transaction: Transaction<N>,
+    /// The summary of the transaction.
+    #[serde(flatten)]
+    summary: TransactionSummary<N>,
+}
+
+/// The size, fee, and records of a transaction, which its JSON representation does not expose directly.
+#[derive(Serialize)]
+pub(crate) struct TransactionSummary<N: Network> {
+    /// The transaction ID.
+    id: N::TransactionID,
+    /// The transaction size, in bytes.
+    size: usize,
+    /// The transaction fee, in microcredits.
+    fee: u64,
+    /// The serial numbers of the input records.
+    serial_numbers: Vec<Field<N>>,
+    /// The commitments of the output records.
+    commitments: Vec<Field<N>>,
+    /// The size of the execution proof (or the deployment verifying keys and certificates), in bytes.
+    proof_size: usize,
+    /// The size of the fee proof, in bytes.
+    fee_proof_size: usize,
+}
+
+/// The `decode_block` response object.
+#[derive(Serialize)]
+pub(crate) struct DecodedBlock<N: Network> {
+    /// The block, in its snarkVM JSON representation.
+    block: Block<N>,
+    /// The block size, in bytes.
+    size: usize,
+    /// The summaries of the transactions in the block.
+    transactions: Vec<TransactionSummary<N>>,
+}
+
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     // GET /testnet3/latest/height
     pub(crate) async fn latest_height(State(rest): State<Self>) -> ErasedJson {
@@ -70,6 +262,14 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(block))
     }
 
+    // GET /testnet3/block/height/{height}
+    pub(crate) async fn get_block_by_height(
+        State(rest): State<Self>,
+        Path(height): Path<u32>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.ledger.get_block(height)?))
+    }
+
     // GET /testnet3/blocks?start={start_height}&end={end_height}
     pub(crate) async fn get_blocks(
         State(rest): State<Self>,
@@ -124,7 +324,50 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(rest.ledger.get_transaction(tx_id)?))
     }
 
+    // GET /testnet3/block/{height}/confirmations
+    // GET /testnet3/block/{blockHash}/confirmations
+    // Note: a block on a stale fork is never stored in the ledger, so it is reported as not found.
+    pub(crate) async fn get_block_confirmations(
+        State(rest): State<Self>,
+        Path(height_or_hash): Path<String>,
+    ) -> Result<Response, RestError> {
+        let hash = if let Ok(height) = height_or_hash.parse::<u32>() {
+            match height <= rest.ledger.latest_height() {
+                true => rest.ledger.get_hash(height)?,
+                false => return Ok((StatusCode::NOT_FOUND, format!("Block {height} does not exist")).into_response()),
+            }
+        } else {
+            height_or_hash
+                .parse::<N::BlockHash>()
+                .map_err(|_| RestError("invalid input, it is neither a block height nor a block hash".to_string()))?
+        };
+
+        match block_confirmations(&rest.ledger, hash)? {
+            Some(confirmations) => Ok(ErasedJson::pretty(confirmations).into_response()),
+            None => Ok((StatusCode::NOT_FOUND, format!("Block '{hash}' does not exist")).into_response()),
+        }
+    }
+
+    // GET /testnet3/transaction/{transactionID}/confirmations
+    // Note: a transaction only included in a block on a stale fork is never stored in the ledger,
+    // so it is reported as unconfirmed if it is in the memory pool, and as not found otherwise.
+    pub(crate) async fn get_transaction_confirmations(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<Response, RestError> {
+        let is_unconfirmed = rest
+            .consensus
+            .as_ref()
+            .map_or(false, |consensus| consensus.memory_pool().contains_unconfirmed_transaction(tx_id));
+
+        match transaction_confirmations(&rest.ledger, tx_id, is_unconfirmed)? {
+            Some(confirmations) => Ok(ErasedJson::pretty(confirmations).into_response()),
+            None => Ok((StatusCode::NOT_FOUND, format!("Transaction '{tx_id}' does not exist")).into_response()),
+        }
+    }
+
     // GET /testnet3/memoryPool/transactions
+    // GET /testnet3/mempool
     pub(crate) async fn get_memory_pool_transactions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
         match rest.consensus {
             Some(consensus) => Ok(ErasedJson::pretty(consensus.memory_pool().unconfirmed_transactions())),
@@ -132,6 +375,50 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         }
     }
 
+    // GET /testnet3/memoryPool/info
+    pub(crate) async fn get_memory_pool_info(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => {
+                let entries = Self::memory_pool_entries(&consensus)?;
+                Ok(ErasedJson::pretty(MemoryPoolInfo {
+                    size: entries.len(),
+                    bytes: entries.iter().map(|entry| entry.size).sum(),
+                    min_fee: entries.iter().map(|entry| entry.fee).min().unwrap_or(0),
+                }))
+            }
+            None => Err(RestError("route isn't available for this node type".to_string())),
+        }
+    }
+
+    // GET /testnet3/memoryPool/raw
+    pub(crate) async fn get_raw_memory_pool(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => Ok(ErasedJson::pretty(Self::memory_pool_entries(&consensus)?)),
+            None => Err(RestError("route isn't available for this node type".to_string())),
+        }
+    }
+
+    // GET /testnet3/fee/estimate/{targetBlocks}
+    pub(crate) async fn get_fee_estimate(
+        State(rest): State<Self>,
+        Path(target_blocks): Path<u32>,
+    ) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => {
+                let fee_estimator = consensus.fee_estimator();
+                match fee_estimator.estimate(target_blocks) {
+                    Some(fee_rate) => Ok(ErasedJson::pretty(FeeEstimate {
+                        target_blocks,
+                        fee_rate,
+                        num_blocks: fee_estimator.num_blocks(),
+                    })),
+                    None => Err(RestError("no transactions were confirmed in the recent blocks".to_string())),
+                }
+            }
+            None => Err(RestError("route isn't available for this node type".to_string())),
+        }
+    }
+
     // GET /testnet3/program/{programID}
     pub(crate) async fn get_program(
         State(rest): State<Self>,
@@ -187,6 +474,92 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         ErasedJson::pretty(rest.routing.router().connected_metrics())
     }
 
+    // GET /testnet3/peers/info
+    pub(crate) async fn get_peers_info(State(rest): State<Self>) -> ErasedJson {
+        let router = rest.routing.router();
+        let peers = router
+            .get_connected_peers()
+            .into_iter()
+            .map(|peer| {
+                let ((messages_sent, bytes_sent), (messages_received, bytes_received)) = router
+                    .get_connected_peer_stats(&peer.ip())
+                    .map(|stats| (stats.sent(), stats.received()))
+                    .unwrap_or_default();
+                PeerInfo {
+                    ip: peer.ip(),
+                    address: peer.address(),
+                    node_type: peer.node_type(),
+                    version: peer.version(),
+                    user_agent: peer.user_agent().to_string(),
+                    is_outbound: peer.is_outbound(),
+                    latency_ms: peer.latency().map(|latency| latency.as_millis() as u64),
+                    messages_sent,
+                    bytes_sent,
+                    messages_received,
+                    bytes_received,
+                }
+            })
+            .collect::<Vec<_>>();
+        ErasedJson::pretty(peers)
+    }
+
+    // GET /testnet3/peers/banned
+    pub(crate) async fn get_peers_banned(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.routing.router().banned_peers())
+    }
+
+    // GET /testnet3/node/status
+    pub(crate) async fn get_node_status(State(rest): State<Self>) -> ErasedJson {
+        let router = rest.routing.router();
+        // Retrieve the latest height, and the greatest height among the peers.
+        let height = rest.ledger.latest_height();
+        let peer_height = router.sync().latest_peer_height().unwrap_or(0).max(height);
+        // Compute the sync progress.
+        let sync_progress = match peer_height {
+            0 => 1.0,
+            _ => height as f64 / peer_height as f64,
+        };
+        ErasedJson::pretty(NodeStatus::<N> {
+            network: N::NAME,
+            node_type: router.node_type(),
+            height,
+            hash: rest.ledger.latest_hash(),
+            peer_height,
+            is_synced: height >= peer_height,
+            sync_progress,
+            coinbase_target: rest.ledger.latest_coinbase_target(),
+            proof_target: rest.ledger.latest_proof_target(),
+            peers: router.number_of_connected_peers(),
+            memory_pool_size: rest
+                .consensus
+                .as_ref()
+                .map(|consensus| consensus.memory_pool().num_unconfirmed_transactions()),
+        })
+    }
+
+    // GET /health
+    pub(crate) async fn get_health() -> StatusCode {
+        StatusCode::OK
+    }
+
+    // GET /ready
+    pub(crate) async fn get_ready(State(rest): State<Self>) -> (StatusCode, ErasedJson) {
+        let router = rest.routing.router();
+        // Ensure the latest block can be read from storage.
+        let height = rest.ledger.latest_height();
+        let is_storage_open = rest.ledger.get_hash(height).is_ok();
+        // Determine the number of connected peers, and how far the ledger lags behind them.
+        let peers = router.number_of_connected_peers();
+        let blocks_behind = router.sync().latest_peer_height().unwrap_or(0).saturating_sub(height);
+
+        let is_ready = is_storage_open && peers >= READY_MINIMUM_PEERS && blocks_behind <= READY_MAXIMUM_BLOCKS_BEHIND;
+        let status = match is_ready {
+            true => StatusCode::OK,
+            false => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        (status, ErasedJson::pretty(Readiness { is_ready, is_storage_open, peers, blocks_behind }))
+    }
+
     // GET /testnet3/node/address
     pub(crate) async fn get_node_address(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.routing.router().address())
@@ -229,6 +602,143 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(ENV_INFO.get()))
     }
 
+    // GET /testnet3/node/logs
+    pub(crate) async fn get_node_logs() -> ErasedJson {
+        ErasedJson::pretty(recent_log_lines())
+    }
+
+    // POST /testnet3/peers/connect
+    pub(crate) async fn peers_connect(
+        State(rest): State<Self>,
+        Json(peer_ip): Json<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        match rest.routing.router().connect(peer_ip) {
+            Some(_) => Ok(ErasedJson::pretty(peer_ip)),
+            None => Err(RestError(format!("Unable to connect to '{peer_ip}'"))),
+        }
+    }
+
+    // POST /testnet3/peers/disconnect
+    pub(crate) async fn peers_disconnect(
+        State(rest): State<Self>,
+        Json(peer_ip): Json<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        let router = rest.routing.router();
+        // Ensure the peer is connected.
+        if !router.is_connected(&peer_ip) {
+            return Err(RestError(format!("Peer '{peer_ip}' is not connected")));
+        }
+        router.disconnect(peer_ip);
+        Ok(ErasedJson::pretty(peer_ip))
+    }
+
+    // POST /testnet3/peers/ban
+    pub(crate) async fn peers_ban(State(rest): State<Self>, Json(peer_ip): Json<SocketAddr>) -> ErasedJson {
+        let router = rest.routing.router();
+        // Ban the peer, so that it is no longer accepted or dialed.
+        router.insert_banned_peer(peer_ip);
+        // Disconnect from the peer, if it is connected.
+        if router.is_connected(&peer_ip) {
+            router.disconnect(peer_ip);
+        }
+        ErasedJson::pretty(peer_ip)
+    }
+
+    // POST /testnet3/peers/unban
+    pub(crate) async fn peers_unban(
+        State(rest): State<Self>,
+        Json(peer_ip): Json<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        match rest.routing.router().remove_banned_peer(peer_ip) {
+            true => Ok(ErasedJson::pretty(peer_ip)),
+            false => Err(RestError(format!("Peer '{peer_ip}' is not banned"))),
+        }
+    }
+
+    // GET /testnet3/admin/config
+    pub(crate) async fn get_runtime_config(State(rest): State<Self>) -> ErasedJson {
+        let router = rest.routing.router();
+        ErasedJson::pretty(RuntimeConfig {
+            max_peers: router.max_connected_peers(),
+            bootstrap_peers: router.bootstrap_peers(),
+            minimum_fee: rest.consensus.as_ref().map(|consensus| consensus.memory_pool().minimum_fee()),
+            memory_pool_capacity: rest
+                .consensus
+                .as_ref()
+                .map(|consensus| consensus.memory_pool().max_unconfirmed_transactions()),
+        })
+    }
+
+    // POST /testnet3/admin/logFilter
+    pub(crate) async fn admin_set_log_filter(Json(directive): Json<String>) -> Result<ErasedJson, RestError> {
+        set_log_filter(&directive)?;
+        info!("Changed the log filter to '{directive}'");
+        Ok(ErasedJson::pretty(directive))
+    }
+
+    // POST /testnet3/admin/maxPeers
+    pub(crate) async fn admin_set_max_peers(
+        State(rest): State<Self>,
+        Json(max_peers): Json<usize>,
+    ) -> Result<ErasedJson, RestError> {
+        rest.routing.router().set_max_connected_peers(max_peers)?;
+        info!("Changed the maximum number of peers to {max_peers}");
+        Ok(ErasedJson::pretty(max_peers))
+    }
+
+    // POST /testnet3/admin/minimumFee
+    pub(crate) async fn admin_set_minimum_fee(
+        State(rest): State<Self>,
+        Json(minimum_fee): Json<u64>,
+    ) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => {
+                consensus.memory_pool().set_minimum_fee(minimum_fee);
+                info!("Changed the minimum fee of the memory pool to {minimum_fee} microcredits");
+                Ok(ErasedJson::pretty(minimum_fee))
+            }
+            None => Err(RestError("route isn't available for this node type".to_string())),
+        }
+    }
+
+    // POST /testnet3/admin/memoryPool/capacity
+    pub(crate) async fn admin_set_memory_pool_capacity(
+        State(rest): State<Self>,
+        Json(capacity): Json<usize>,
+    ) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => {
+                consensus.memory_pool().set_max_unconfirmed_transactions(capacity);
+                info!("Changed the capacity of the memory pool to {capacity} transactions");
+                Ok(ErasedJson::pretty(capacity))
+            }
+            None => Err(RestError("route isn't available for this node type".to_string())),
+        }
+    }
+
+    // POST /testnet3/admin/bootstrapPeers
+    pub(crate) async fn admin_connect_bootstrap_peers(
+        State(rest): State<Self>,
+        Json(peer_ips): Json<Vec<SocketAddr>>,
+    ) -> ErasedJson {
+        let router = rest.routing.router();
+        // Add the bootstrap peers, so the heartbeat keeps a connection to them.
+        router.insert_bootstrap_peers(&peer_ips);
+        // Attempt to connect to the bootstrap peers, returning the peer IPs that are being dialed.
+        let connecting = peer_ips.into_iter().filter(|peer_ip| router.connect(*peer_ip).is_some()).collect::<Vec<_>>();
+        ErasedJson::pretty(connecting)
+    }
+
+    // POST /testnet3/decode/transaction
+    pub(crate) async fn decode_transaction(Json(bytes): Json<String>) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(decode_transaction::<N>(&bytes)?))
+    }
+
+    // POST /testnet3/decode/block
+    pub(crate) async fn decode_block(Json(bytes): Json<String>) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(decode_block::<N>(&bytes)?))
+    }
+
     // POST /testnet3/transaction/broadcast
     pub(crate) async fn transaction_broadcast(
         State(rest): State<Self>,
@@ -253,3 +763,196 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(tx_id))
     }
 }
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Returns the fee, size, and age of each unconfirmed transaction in the memory pool.
+    fn memory_pool_entries(consensus: &Consensus<N, C>) -> Result<Vec<MemoryPoolEntry<N>>> {
+        consensus
+            .memory_pool()
+            .unconfirmed_transactions_with_timestamps()
+            .into_iter()
+            .map(|(transaction, timestamp)| {
+                Ok(MemoryPoolEntry {
+                    id: transaction.id(),
+                    fee: *transaction.fee()?,
+                    size: transaction.to_bytes_le()?.len(),
+                    age: timestamp.elapsed().as_secs(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the confirmations of the given block hash, or `None` if the block is not in the ledger.
+fn block_confirmations<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    hash: N::BlockHash,
+) -> Result<Option<Confirmations<N>>> {
+    if !ledger.contains_block_hash(&hash)? {
+        return Ok(None);
+    }
+    let height = ledger.get_height(&hash)?;
+    Ok(Some(Confirmations {
+        status: ConfirmationStatus::Confirmed,
+        block_hash: Some(hash),
+        height: Some(height),
+        confirmations: ledger.latest_height().saturating_sub(height) + 1,
+    }))
+}
+
+/// Returns the confirmations of the given transaction, or `None` if the transaction is neither in the ledger
+/// nor in the memory pool, as given by `is_unconfirmed`.
+fn transaction_confirmations<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    tx_id: N::TransactionID,
+    is_unconfirmed: bool,
+) -> Result<Option<Confirmations<N>>> {
+    match ledger.find_block_hash(&tx_id)? {
+        Some(hash) => block_confirmations(ledger, hash),
+        None => Ok(is_unconfirmed.then_some(Confirmations {
+            status: ConfirmationStatus::Unconfirmed,
+            block_hash: None,
+            height: None,
+            confirmations: 0,
+        })),
+    }
+}
+
+/// Decodes the given hex string, with an optional `0x` prefix, into bytes.
+fn decode_hex(bytes: &str) -> Result<Vec<u8>, RestError> {
+    let bytes = bytes.trim();
+    hex::decode(bytes.strip_prefix("0x").unwrap_or(bytes))
+        .map_err(|error| RestError(format!("invalid input, it is not a valid hex string - {error}")))
+}
+
+/// Decodes the given transaction bytes, as a hex string.
+fn decode_transaction<N: Network>(bytes: &str) -> Result<DecodedTransaction<N>, RestError> {
+    let transaction = Transaction::<N>::from_bytes_le(&decode_hex(bytes)?)?;
+    let summary = transaction_summary(&transaction)?;
+    Ok(DecodedTransaction { transaction, summary })
+}
+
+/// Decodes the given block bytes, as a hex string.
+fn decode_block<N: Network>(bytes: &str) -> Result<DecodedBlock<N>, RestError> {
+    let bytes = decode_hex(bytes)?;
+    let block = Block::<N>::from_bytes_le(&bytes)?;
+    let transactions = block
+        .transactions()
+        .iter()
+        .map(|confirmed| transaction_summary(confirmed.transaction()))
+        .collect::<Result<_>>()?;
+    Ok(DecodedBlock { block, size: bytes.len(), transactions })
+}
+
+/// Returns the summary of the given transaction.
+fn transaction_summary<N: Network>(transaction: &Transaction<N>) -> Result<TransactionSummary<N>> {
+    let proof_size = match transaction {
+        Transaction::Deploy(_, _, deployment, _) => deployment
+            .verifying_keys()
+            .iter()
+            .map(|(_, (verifying_key, certificate))| {
+                Ok(verifying_key.to_bytes_le()?.len() + certificate.to_bytes_le()?.len())
+            })
+            .sum::<Result<usize>>()?,
+        Transaction::Execute(_, execution, _) => match execution.proof() {
+            Some(proof) => proof.to_bytes_le()?.len(),
+            None => 0,
+        },
+        Transaction::Fee(..) => 0,
+    };
+    let fee_proof_size = match transaction.fee_transition().as_ref().and_then(|fee| fee.proof()) {
+        Some(proof) => proof.to_bytes_le()?.len(),
+        None => 0,
+    };
+
+    Ok(TransactionSummary {
+        id: transaction.id(),
+        size: transaction.to_bytes_le()?.len(),
+        fee: *transaction.fee()?,
+        serial_numbers: transaction.serial_numbers().copied().collect(),
+        commitments: transaction.commitments().copied().collect(),
+        proof_size,
+        fee_proof_size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        PrivateKey,
+        TestRng,
+        Testnet3,
+        VM,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_confirmations() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the ledger with a genesis block.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis(&private_key, rng).unwrap();
+        let ledger = Ledger::<CurrentNetwork, ConsensusMemory<_>>::load(genesis.clone(), None).unwrap();
+
+        // Ensure the genesis block, and its transactions, are confirmed.
+        let confirmations = block_confirmations(&ledger, genesis.hash()).unwrap().unwrap();
+        assert!(matches!(confirmations.status, ConfirmationStatus::Confirmed));
+        assert_eq!((confirmations.height, confirmations.confirmations), (Some(0), 1));
+        let tx_id = genesis.transaction_ids().next().copied().unwrap();
+        let confirmations = transaction_confirmations(&ledger, tx_id, false).unwrap().unwrap();
+        assert_eq!(confirmations.block_hash, Some(genesis.hash()));
+
+        // Ensure an unknown block is not found.
+        let unknown_hash = Field::<CurrentNetwork>::from_u64(1).into();
+        assert!(block_confirmations(&ledger, unknown_hash).unwrap().is_none());
+
+        // Ensure an unknown transaction is only found if it is in the memory pool.
+        let unknown_id = Field::<CurrentNetwork>::from_u64(1).into();
+        assert!(transaction_confirmations(&ledger, unknown_id, false).unwrap().is_none());
+        let confirmations = transaction_confirmations(&ledger, unknown_id, true).unwrap().unwrap();
+        assert!(matches!(confirmations.status, ConfirmationStatus::Unconfirmed));
+        assert_eq!((confirmations.block_hash, confirmations.confirmations), (None, 0));
+    }
+
+    #[test]
+    fn test_decode() {
+        let rng = &mut TestRng::default();
+
+        // Sample a genesis block.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis(&private_key, rng).unwrap();
+        let block_bytes = genesis.to_bytes_le().unwrap();
+
+        // Ensure the block is decoded, with or without the `0x` prefix.
+        for hex in [hex::encode(&block_bytes), format!("0x{}", hex::encode(&block_bytes))] {
+            let decoded = decode_block::<CurrentNetwork>(&hex).unwrap();
+            assert_eq!(decoded.block, genesis);
+            assert_eq!(decoded.size, block_bytes.len());
+            assert_eq!(decoded.transactions.len(), genesis.transactions().len());
+        }
+
+        // Ensure the transaction is decoded, along with its summary.
+        let transaction = genesis.transactions().iter().next().unwrap().transaction();
+        let transaction_bytes = transaction.to_bytes_le().unwrap();
+        let decoded = decode_transaction::<CurrentNetwork>(&hex::encode(&transaction_bytes)).unwrap();
+        assert_eq!(&decoded.transaction, transaction);
+        assert_eq!(decoded.summary.id, transaction.id());
+        assert_eq!(decoded.summary.size, transaction_bytes.len());
+        assert_eq!(decoded.summary.fee, *transaction.fee().unwrap());
+        assert_eq!(decoded.summary.serial_numbers.len(), transaction.serial_numbers().count());
+        assert_eq!(decoded.summary.commitments.len(), transaction.commitments().count());
+
+        // Ensure invalid hex, and valid hex of invalid bytes, are rejected.
+        assert!(decode_block::<CurrentNetwork>("0xzz").is_err());
+        assert!(decode_transaction::<CurrentNetwork>("not hex").is_err());
+        assert!(decode_block::<CurrentNetwork>("0x00").is_err());
+        assert!(decode_transaction::<CurrentNetwork>(&hex::encode(&block_bytes)).is_err());
+        assert!(decode_block::<CurrentNetwork>(&hex::encode(&transaction_bytes)).is_err());
+    }
+}
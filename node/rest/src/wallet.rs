@@ -0,0 +1,407 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use snarkos_account::Account;
+use snarkos_node_wallet::{HistoryFormat, PartialTransaction, RecordSelection, RescanProgress, EVENT_CHANNEL_CAPACITY};
+use snarkvm::prelude::{Address, Plaintext, PrivateKey, Record, ViewKey};
+
+use axum::{
+    http::header::CONTENT_TYPE,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+};
+use core::{convert::Infallible, time::Duration};
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+
+/// The `get_wallet_balance` response object.
+#[derive(Serialize)]
+pub(crate) struct WalletBalance<N: Network> {
+    /// The total balance of the wallet, except the watch-only accounts, in microcredits.
+    balance: u64,
+    /// The balance of each account of the wallet, except the watch-only accounts, in microcredits.
+    accounts: IndexMap<Address<N>, u64>,
+    /// The total balance of the watch-only accounts, in microcredits.
+    watch_only_balance: u64,
+    /// The balance of each watch-only account, in microcredits.
+    watch_only: IndexMap<Address<N>, u64>,
+    /// The labels of the accounts of the wallet, by address, if they are in the address book.
+    labels: IndexMap<Address<N>, String>,
+}
+
+/// The `get_wallet_records` response object, for a single unspent record.
+#[derive(Serialize)]
+pub(crate) struct WalletRecordEntry<N: Network> {
+    /// The address of the account that owns the record.
+    owner: Address<N>,
+    /// The label of the account that owns the record, if it is in the address book.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    /// `true` if the account that owns the record is watch-only.
+    watch_only: bool,
+    /// The commitment of the record.
+    commitment: Field<N>,
+    /// The decrypted record.
+    record: Record<N, Plaintext<N>>,
+    /// The number of microcredits in the record.
+    microcredits: u64,
+    /// The ID of the transaction that created the record.
+    transaction_id: N::TransactionID,
+    /// The height of the block that created the record.
+    height: u32,
+    /// The number of confirmations of the record.
+    confirmations: u32,
+}
+
+/// The `get_wallet_history` query object.
+#[derive(Deserialize)]
+pub(crate) struct WalletHistory {
+    /// The format to export the transaction history in.
+    #[serde(default)]
+    format: HistoryFormat,
+}
+
+/// The `wallet_send` and `wallet_prepare` request object.
+#[derive(Deserialize)]
+#[serde(bound = "")]
+pub(crate) struct WalletSend<N: Network> {
+    /// The address of the recipient.
+    recipient: Address<N>,
+    /// The amount to transfer, in microcredits.
+    amount: u64,
+    /// The priority fee to pay, in microcredits.
+    #[serde(default)]
+    priority_fee: u64,
+    /// The strategy to select the record to spend.
+    #[serde(default)]
+    selection: RecordSelection,
+}
+
+/// The `wallet_send` response object.
+#[derive(Serialize)]
+pub(crate) struct WalletSent<N: Network> {
+    /// The ID of the transaction.
+    transaction_id: N::TransactionID,
+    /// The address of the recipient.
+    recipient: Address<N>,
+    /// The label of the recipient, if it is in the address book.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    /// The amount transferred, in microcredits.
+    amount: u64,
+}
+
+/// The `wallet_label` request object.
+#[derive(Deserialize)]
+#[serde(bound = "")]
+pub(crate) struct WalletLabel<N: Network> {
+    /// The address to label.
+    address: Address<N>,
+    /// The label of the address, or `None` to remove the address from the address book.
+    label: Option<String>,
+}
+
+/// The `wallet_unlock` request object.
+#[derive(Deserialize)]
+pub(crate) struct WalletUnlock {
+    /// The password of the wallet file.
+    password: String,
+    /// The number of seconds after which the wallet is locked again, if any.
+    timeout: Option<u64>,
+}
+
+/// The `wallet_import` request object.
+#[derive(Deserialize)]
+#[serde(bound = "")]
+pub(crate) struct WalletImport<N: Network> {
+    /// The private key of the account to import.
+    private_key: PrivateKey<N>,
+    /// The password of the wallet file, if the wallet is encrypted.
+    password: Option<String>,
+    /// The height from which to rescan the ledger for the records of the account.
+    #[serde(default)]
+    from_height: u32,
+}
+
+/// The `wallet_watch` request object.
+#[derive(Deserialize)]
+#[serde(bound = "")]
+pub(crate) struct WalletWatch<N: Network> {
+    /// The view key of the account to watch.
+    view_key: ViewKey<N>,
+    /// The height from which to rescan the ledger for the records of the account.
+    #[serde(default)]
+    from_height: u32,
+}
+
+/// The `wallet_rescan` request object.
+#[derive(Deserialize)]
+pub(crate) struct WalletRescan {
+    /// The height from which to rescan the ledger.
+    #[serde(default)]
+    from_height: u32,
+}
+
+impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /testnet3/wallet/balance
+    pub(crate) async fn get_wallet_balance(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?;
+        let balances = |addresses: Vec<Address<N>>| {
+            addresses.into_iter().map(|address| (address, wallet.balance_of(&address))).collect()
+        };
+        Ok(ErasedJson::pretty(WalletBalance {
+            balance: wallet.balance(),
+            accounts: balances(wallet.spendable_addresses()),
+            watch_only_balance: wallet.watch_only_balance(),
+            watch_only: balances(wallet.watch_only_addresses()),
+            labels: wallet.addresses().iter().filter_map(|address| Some((*address, wallet.label(address)?))).collect(),
+        }))
+    }
+
+    // GET /testnet3/wallet/records
+    pub(crate) async fn get_wallet_records(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?;
+        let latest_height = rest.ledger.latest_height();
+        let records = wallet
+            .unspent_records()
+            .into_iter()
+            .map(|record| WalletRecordEntry {
+                owner: record.owner(),
+                label: wallet.label(&record.owner()),
+                watch_only: wallet.is_watch_only(&record.owner()),
+                commitment: record.commitment(),
+                record: record.record().clone(),
+                microcredits: record.microcredits(),
+                transaction_id: record.transaction_id(),
+                height: record.height(),
+                confirmations: latest_height.saturating_sub(record.height()) + 1,
+            })
+            .collect::<Vec<_>>();
+        Ok(ErasedJson::pretty(records))
+    }
+
+    // POST /testnet3/wallet/send
+    pub(crate) async fn wallet_send(
+        State(rest): State<Self>,
+        Json(request): Json<WalletSend<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?.clone();
+        let ledger = rest.ledger.clone();
+        // Create the transaction, which is computationally expensive.
+        let wallet_ = wallet.clone();
+        let tx = tokio::task::spawn_blocking(move || {
+            let (recipient, amount, priority_fee) = (request.recipient, request.amount, request.priority_fee);
+            wallet_.transfer(&ledger, recipient, amount, priority_fee, request.selection, &mut rand::thread_rng())
+        })
+        .await
+        .map_err(|error| RestError(format!("failed to create the transaction - {error}")))??;
+
+        // If the consensus module is enabled, add the unconfirmed transaction to the memory pool.
+        if let Some(consensus) = &rest.consensus {
+            // Release the spent records if the transaction is rejected, as it is not going to be confirmed.
+            if let Err(error) = consensus.add_unconfirmed_transaction(tx.clone()) {
+                wallet.release_transaction(&tx);
+                return Err(error.into());
+            }
+        }
+
+        // Broadcast the transaction.
+        let tx_id = tx.id();
+        let message = Message::UnconfirmedTransaction(UnconfirmedTransaction {
+            transaction_id: tx_id,
+            transaction: Data::Object(tx),
+        });
+        rest.routing.propagate(message, &[]);
+
+        let label = wallet.label(&request.recipient);
+        match &label {
+            Some(label) => info!(
+                "Sent {} microcredits to '{label}' ({}) in transaction '{tx_id}'",
+                request.amount, request.recipient
+            ),
+            None => info!("Sent {} microcredits to '{}' in transaction '{tx_id}'", request.amount, request.recipient),
+        }
+        Ok(ErasedJson::pretty(WalletSent {
+            transaction_id: tx_id,
+            recipient: request.recipient,
+            label,
+            amount: request.amount,
+        }))
+    }
+
+    // POST /testnet3/wallet/prepare
+    pub(crate) async fn wallet_prepare(
+        State(rest): State<Self>,
+        Json(request): Json<WalletSend<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?;
+        let (recipient, amount, priority_fee) = (request.recipient, request.amount, request.priority_fee);
+        let partial = wallet.prepare_transfer(
+            &rest.ledger,
+            recipient,
+            amount,
+            priority_fee,
+            request.selection,
+            &mut rand::thread_rng(),
+        )?;
+        Ok(ErasedJson::pretty(partial))
+    }
+
+    // POST /testnet3/wallet/release
+    pub(crate) async fn wallet_release(
+        State(rest): State<Self>,
+        Json(partial): Json<PartialTransaction<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        rest.wallet()?.release_prepared_transfer(&partial);
+        Ok(ErasedJson::pretty(true))
+    }
+
+    // GET /testnet3/wallet/history?format={json|csv}
+    pub(crate) async fn get_wallet_history(
+        State(rest): State<Self>,
+        Query(query): Query<WalletHistory>,
+    ) -> Result<Response, RestError> {
+        let wallet = rest.wallet()?;
+        match query.format {
+            HistoryFormat::Json => Ok(ErasedJson::pretty(wallet.history()).into_response()),
+            HistoryFormat::Csv => {
+                let csv = wallet.export_history(HistoryFormat::Csv)?;
+                Ok(([(CONTENT_TYPE, "text/csv")], csv).into_response())
+            }
+        }
+    }
+
+    // GET /testnet3/wallet/events
+    pub(crate) async fn get_wallet_events(
+        State(rest): State<Self>,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, RestError> {
+        let mut event_receiver = rest.wallet()?.subscribe();
+        let (sender, receiver) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+
+        // Stream each event of the wallet to the subscriber, as a server-sent event.
+        tokio::spawn(async move {
+            loop {
+                match event_receiver.recv().await {
+                    Ok(event) => {
+                        let event = match Event::default().json_data(&event) {
+                            Ok(event) => event,
+                            Err(error) => {
+                                warn!("Failed to serialize a wallet event - {error}");
+                                continue;
+                            }
+                        };
+                        // If the subscriber has disconnected, stop sending.
+                        if sender.send(Ok(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                    // Skip the events that were missed by a slow subscriber.
+                    Err(RecvError::Lagged(num_skipped)) => debug!("A wallet subscriber skipped {num_skipped} events"),
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+
+        Ok(Sse::new(ReceiverStream::new(receiver)).keep_alive(KeepAlive::default()))
+    }
+
+    // GET /testnet3/wallet/labels
+    pub(crate) async fn get_wallet_labels(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.wallet()?.labels()))
+    }
+
+    // POST /testnet3/wallet/labels
+    pub(crate) async fn wallet_label(
+        State(rest): State<Self>,
+        Json(request): Json<WalletLabel<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?;
+        wallet.set_label(request.address, request.label.as_deref())?;
+        Ok(ErasedJson::pretty(wallet.labels()))
+    }
+
+    // POST /testnet3/wallet/unlock
+    pub(crate) async fn wallet_unlock(
+        State(rest): State<Self>,
+        Json(request): Json<WalletUnlock>,
+    ) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?;
+        wallet.unlock(&request.password, request.timeout.map(Duration::from_secs))?;
+        Ok(ErasedJson::pretty(wallet.is_locked()))
+    }
+
+    // GET /testnet3/wallet/rescan
+    pub(crate) async fn get_wallet_rescan(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let progress: Option<RescanProgress> = rest.wallet()?.rescan_progress();
+        Ok(ErasedJson::pretty(progress))
+    }
+
+    // POST /testnet3/wallet/import
+    pub(crate) async fn wallet_import(
+        State(rest): State<Self>,
+        Json(request): Json<WalletImport<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?;
+        let account = Account::try_from(request.private_key)?;
+        wallet.import(&account, request.password.as_deref(), &mut rand::thread_rng())?;
+        // Rescan the ledger in the background for the records of the account, unless no blocks were scanned yet.
+        if wallet.height().is_some() {
+            wallet.spawn_rescan(rest.ledger.clone(), request.from_height)?;
+        }
+        Ok(ErasedJson::pretty(account.address()))
+    }
+
+    // POST /testnet3/wallet/watch
+    pub(crate) async fn wallet_watch(
+        State(rest): State<Self>,
+        Json(request): Json<WalletWatch<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?;
+        let address = wallet.watch(request.view_key)?;
+        // Rescan the ledger in the background for the records of the account, unless no blocks were scanned yet.
+        if wallet.height().is_some() {
+            wallet.spawn_rescan(rest.ledger.clone(), request.from_height)?;
+        }
+        Ok(ErasedJson::pretty(address))
+    }
+
+    // POST /testnet3/wallet/rescan
+    pub(crate) async fn wallet_rescan(
+        State(rest): State<Self>,
+        Json(request): Json<WalletRescan>,
+    ) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?;
+        wallet.spawn_rescan(rest.ledger.clone(), request.from_height)?;
+        Ok(ErasedJson::pretty(wallet.rescan_progress()))
+    }
+
+    // POST /testnet3/wallet/lock
+    pub(crate) async fn wallet_lock(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let wallet = rest.wallet()?;
+        wallet.lock()?;
+        Ok(ErasedJson::pretty(wallet.is_locked()))
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Returns the wallet, or an error if the node was started without one.
+    fn wallet(&self) -> Result<&Wallet<N>, RestError> {
+        self.wallet.as_ref().ok_or_else(|| RestError("route isn't available without a wallet".to_string()))
+    }
+}
@@ -117,7 +117,7 @@ impl<N: Network> Router<N> {
 
         // If the handshake succeeded, announce it.
         if let Ok((ref peer_ip, _)) = handshake_result {
-            info!("Connected to '{peer_ip}'");
+            info!(peer = %peer_ip, "Connected to '{peer_ip}'");
         }
 
         handshake_result
@@ -182,7 +182,7 @@ impl<N: Network> Router<N> {
         framed.send(Message::ChallengeResponse(our_response)).await?;
 
         // Add the peer to the router.
-        self.insert_connected_peer(Peer::new(peer_ip, &peer_request), peer_addr);
+        self.insert_connected_peer(Peer::new(peer_ip, ConnectionSide::Responder, &peer_request), peer_addr);
 
         Ok((peer_ip, framed))
     }
@@ -253,7 +253,7 @@ impl<N: Network> Router<N> {
         );
 
         // Add the peer to the router.
-        self.insert_connected_peer(Peer::new(peer_ip, &peer_request), peer_addr);
+        self.insert_connected_peer(Peer::new(peer_ip, ConnectionSide::Initiator, &peer_request), peer_addr);
 
         Ok((peer_ip, framed))
     }
@@ -297,7 +297,7 @@ impl<N: Network> Router<N> {
         message: &ChallengeRequest<N>,
     ) -> Option<DisconnectReason> {
         // Retrieve the components of the challenge request.
-        let &ChallengeRequest { version, listener_port: _, node_type, address, nonce: _ } = message;
+        let &ChallengeRequest { version, listener_port: _, node_type, address, nonce: _, user_agent: _ } = message;
 
         // Ensure the message protocol version is not outdated.
         if version < Message::<N>::VERSION {
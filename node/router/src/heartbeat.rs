@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use crate::{Outbound, Router, REDUNDANCY_FACTOR};
+use metrics::gauge;
 use snarkos_node_messages::{DisconnectReason, Message, PeerRequest, PuzzleRequest};
+use snarkos_node_tcp::P2P;
 use snarkvm::prelude::Network;
 
 use colored::Colorize;
@@ -42,6 +44,7 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
     fn heartbeat(&self) {
         self.safety_check_minimum_number_of_peers();
         self.log_connected_peers();
+        self.update_metrics();
 
         // Remove any stale connected peers.
         self.remove_stale_connected_peers();
@@ -85,6 +88,19 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         }
     }
 
+    /// This function updates the metrics for the peers and the network traffic.
+    fn update_metrics(&self) {
+        let router = self.router();
+        gauge!(metrics::peers::CONNECTED, router.number_of_connected_peers() as f64);
+        gauge!(metrics::peers::CANDIDATE, router.number_of_candidate_peers() as f64);
+        gauge!(metrics::peers::RESTRICTED, router.number_of_restricted_peers() as f64);
+
+        let (_, sent_bytes) = router.tcp().stats().sent();
+        let (_, received_bytes) = router.tcp().stats().received();
+        gauge!(metrics::network::SENT_BYTES, sent_bytes as f64);
+        gauge!(metrics::network::RECEIVED_BYTES, received_bytes as f64);
+    }
+
     /// This function removes any connected peers that have not communicated within the predefined time.
     fn remove_stale_connected_peers(&self) {
         // Check if any connected peer is stale.
@@ -123,7 +139,7 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
 
         // Disconnect from the oldest connected peer, if one exists.
         if let Some(oldest) = oldest_peer {
-            info!("Disconnecting from '{oldest}' (periodic refresh of peers)");
+            info!(peer = %oldest, "Disconnecting from '{oldest}' (periodic refresh of peers)");
             let _ = self.send(oldest, Message::Disconnect(DisconnectReason::PeerRefresh.into()));
             // Disconnect from this peer.
             self.router().disconnect(oldest);
@@ -137,8 +153,9 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
     fn handle_connected_peers(&self) {
         // Obtain the number of connected peers.
         let num_connected = self.router().number_of_connected_peers();
-        // Compute the number of surplus peers.
-        let num_surplus = num_connected.saturating_sub(Self::MAXIMUM_NUMBER_OF_PEERS);
+        // Compute the number of surplus peers, as the maximum may have been lowered at runtime.
+        let max_connected = Self::MAXIMUM_NUMBER_OF_PEERS.min(self.router().max_connected_peers());
+        let num_surplus = num_connected.saturating_sub(max_connected);
         // Compute the number of deficit peers.
         let num_deficient = Self::MEDIAN_NUMBER_OF_PEERS.saturating_sub(num_connected);
 
@@ -174,7 +191,7 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
                     }
                 }
 
-                info!("Disconnecting from '{peer_ip}' (exceeded maximum connections)");
+                info!(peer = %peer_ip, "Disconnecting from '{peer_ip}' (exceeded maximum connections)");
                 self.send(peer_ip, Message::Disconnect(DisconnectReason::TooManyPeers.into()));
                 // Disconnect from this peer.
                 self.router().disconnect(peer_ip);
@@ -232,7 +249,7 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
             let rng = &mut OsRng;
             // Proceed to send disconnect requests to these bootstrap peers.
             for peer_ip in connected_bootstrap.into_iter().choose_multiple(rng, num_surplus) {
-                info!("Disconnecting from '{peer_ip}' (exceeded maximum bootstrap)");
+                info!(peer = %peer_ip, "Disconnecting from '{peer_ip}' (exceeded maximum bootstrap)");
                 self.send(peer_ip, Message::Disconnect(DisconnectReason::TooManyPeers.into()));
                 // Disconnect from this peer.
                 self.router().disconnect(peer_ip);
@@ -13,9 +13,13 @@
 // limitations under the License.
 
 use snarkos_node_messages::{ChallengeRequest, NodeType};
+use snarkos_node_tcp::ConnectionSide;
 use snarkvm::prelude::{Address, Network};
 
-use std::{net::SocketAddr, time::Instant};
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
 /// The state for each connected peer.
 #[derive(Clone, Debug)]
@@ -28,22 +32,34 @@ pub struct Peer<N: Network> {
     node_type: NodeType,
     /// The message version of the peer.
     version: u32,
+    /// The user agent of the peer (e.g. `snarkOS/2.1.6 (abcdef1)`).
+    user_agent: String,
+    /// The connection side of the peer (`Initiator` if the peer connected to this node).
+    side: ConnectionSide,
     /// The timestamp of the first message received from the peer.
     first_seen: Instant,
     /// The timestamp of the last message received from this peer.
     last_seen: Instant,
+    /// The timestamp of the last `Ping` sent to this peer, if it is awaiting a `Pong`.
+    ping_sent: Option<Instant>,
+    /// The round-trip time of the last `Ping` sent to this peer.
+    latency: Option<Duration>,
 }
 
 impl<N: Network> Peer<N> {
     /// Initializes a new instance of `Peer`.
-    pub fn new(listening_ip: SocketAddr, challenge_request: &ChallengeRequest<N>) -> Self {
+    pub fn new(listening_ip: SocketAddr, side: ConnectionSide, challenge_request: &ChallengeRequest<N>) -> Self {
         Self {
             peer_ip: listening_ip,
             address: challenge_request.address,
             node_type: challenge_request.node_type,
             version: challenge_request.version,
+            user_agent: challenge_request.user_agent.clone(),
+            side,
             first_seen: Instant::now(),
             last_seen: Instant::now(),
+            ping_sent: None,
+            latency: None,
         }
     }
 
@@ -87,6 +103,21 @@ impl<N: Network> Peer<N> {
         self.version
     }
 
+    /// Returns the user agent of the peer.
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Returns the connection side of the peer.
+    pub const fn side(&self) -> ConnectionSide {
+        self.side
+    }
+
+    /// Returns `true` if this node initiated the connection to the peer.
+    pub fn is_outbound(&self) -> bool {
+        self.side == ConnectionSide::Responder
+    }
+
     /// Returns the first seen timestamp of the peer.
     pub fn first_seen(&self) -> Instant {
         self.first_seen
@@ -96,6 +127,11 @@ impl<N: Network> Peer<N> {
     pub fn last_seen(&self) -> Instant {
         self.last_seen
     }
+
+    /// Returns the round-trip time of the last `Ping` sent to the peer, if a `Pong` was received.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
 }
 
 impl<N: Network> Peer<N> {
@@ -113,4 +149,16 @@ impl<N: Network> Peer<N> {
     pub fn set_last_seen(&mut self, last_seen: Instant) {
         self.last_seen = last_seen;
     }
+
+    /// Updates the timestamp of the last `Ping` sent to the peer.
+    pub fn set_ping_sent(&mut self, ping_sent: Instant) {
+        self.ping_sent = Some(ping_sent);
+    }
+
+    /// Updates the latency of the peer, using the timestamp of the `Pong` received in response to the last `Ping`.
+    pub fn set_pong_received(&mut self, pong_received: Instant) {
+        if let Some(ping_sent) = self.ping_sent.take() {
+            self.latency = Some(pong_received.saturating_duration_since(ping_sent));
+        }
+    }
 }
@@ -137,6 +137,11 @@ impl<N: Network> Sync<N> {
         self.locators.read().get(peer_ip).map(|locators| locators.latest_locator_height())
     }
 
+    /// Returns the greatest block height among all peers, if any peer has sent block locators.
+    pub fn latest_peer_height(&self) -> Option<u32> {
+        self.locators.read().values().map(|locators| locators.latest_locator_height()).max()
+    }
+
     /// Returns a map of peer height to peer IPs.
     /// e.g. `{{ 127 => \[peer1, peer2\], 128 => \[peer3\], 135 => \[peer4, peer5\] }}`
     pub fn get_peer_heights(&self) -> BTreeMap<u32, Vec<SocketAddr>> {
@@ -799,6 +804,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_latest_peer_height() {
+        let sync = sample_sync_at_height(0);
+        assert_eq!(sync.latest_peer_height(), None);
+
+        // Add peers at different heights.
+        sync.update_peer_locators(sample_peer_ip(1), sample_block_locators(10)).unwrap();
+        sync.update_peer_locators(sample_peer_ip(2), sample_block_locators(25)).unwrap();
+        sync.update_peer_locators(sample_peer_ip(3), sample_block_locators(15)).unwrap();
+        assert_eq!(sync.latest_peer_height(), Some(25));
+
+        // Remove the tallest peer.
+        sync.remove_peer(&sample_peer_ip(2));
+        assert_eq!(sync.latest_peer_height(), Some(15));
+    }
+
     #[test]
     fn test_get_canon_height() {
         for height in 0..100_002u32 {
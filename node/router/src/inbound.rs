@@ -181,10 +181,17 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
                 true => Ok(()),
                 false => bail!("Peer '{peer_ip}' sent an invalid ping"),
             },
-            Message::Pong(message) => match self.pong(peer_ip, message) {
-                true => Ok(()),
-                false => bail!("Peer '{peer_ip}' sent an invalid pong"),
-            },
+            Message::Pong(message) => {
+                // Update the latency of the peer, now that it responded to the last `Ping`.
+                if let Some(peer) = self.router().connected_peers.write().get_mut(&peer_ip) {
+                    peer.set_pong_received(Instant::now());
+                }
+                // Process the pong.
+                match self.pong(peer_ip, message) {
+                    true => Ok(()),
+                    false => bail!("Peer '{peer_ip}' sent an invalid pong"),
+                }
+            }
             Message::PuzzleRequest(..) => {
                 // Insert the puzzle request for the peer, and fetch the recent frequency.
                 let frequency = self.router().cache.insert_inbound_puzzle_request(peer_ip);
@@ -39,15 +39,26 @@ pub use routing::*;
 
 use snarkos_account::Account;
 use snarkos_node_messages::NodeType;
-use snarkos_node_tcp::{Config, Tcp};
+use snarkos_node_tcp::{Config, Stats, Tcp};
 use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 #[cfg(not(feature = "test"))]
 use core::str::FromStr;
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::{Mutex, RwLock};
-use std::{collections::HashSet, future::Future, net::SocketAddr, ops::Deref, sync::Arc, time::Instant};
+use std::{
+    collections::HashSet,
+    future::Future,
+    net::SocketAddr,
+    ops::Deref,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
 use tokio::task::JoinHandle;
 
 #[derive(Clone)]
@@ -76,6 +87,10 @@ pub struct InnerRouter<N: Network> {
     sync: Sync<N>,
     /// The set of trusted peers.
     trusted_peers: IndexSet<SocketAddr>,
+    /// The set of bootstrap peers added at runtime, in addition to the default bootstrap peers.
+    extra_bootstrap_peers: RwLock<IndexSet<SocketAddr>>,
+    /// The maximum number of connected peers, which may be lowered at runtime below the TCP connection limit.
+    max_connected_peers: AtomicUsize,
     /// The map of connected peer IPs to their peer handlers.
     connected_peers: RwLock<IndexMap<SocketAddr, Peer<N>>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
@@ -87,6 +102,8 @@ pub struct InnerRouter<N: Network> {
     candidate_peers: RwLock<IndexSet<SocketAddr>>,
     /// The set of restricted peer IPs.
     restricted_peers: RwLock<IndexMap<SocketAddr, Instant>>,
+    /// The set of banned peer IPs.
+    banned_peers: RwLock<IndexSet<SocketAddr>>,
     /// The spawned handles.
     handles: Mutex<Vec<JoinHandle<()>>>,
     /// The boolean flag for the development mode.
@@ -124,10 +141,13 @@ impl<N: Network> Router<N> {
             resolver: Default::default(),
             sync: Default::default(),
             trusted_peers: trusted_peers.iter().copied().collect(),
+            extra_bootstrap_peers: Default::default(),
+            max_connected_peers: AtomicUsize::new(max_peers as usize),
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
             candidate_peers: Default::default(),
             restricted_peers: Default::default(),
+            banned_peers: Default::default(),
             handles: Default::default(),
             is_dev,
         })))
@@ -276,16 +296,33 @@ impl<N: Network> Router<N> {
 
     /// Returns `true` if the given IP is restricted.
     pub fn is_restricted(&self, ip: &SocketAddr) -> bool {
-        self.restricted_peers
-            .read()
-            .get(ip)
-            .map(|time| time.elapsed().as_secs() < Self::RADIO_SILENCE_IN_SECS)
-            .unwrap_or(false)
+        self.is_banned(ip)
+            || self
+                .restricted_peers
+                .read()
+                .get(ip)
+                .map(|time| time.elapsed().as_secs() < Self::RADIO_SILENCE_IN_SECS)
+                .unwrap_or(false)
+    }
+
+    /// Returns `true` if the given IP is banned.
+    pub fn is_banned(&self, ip: &SocketAddr) -> bool {
+        self.banned_peers.read().contains(ip)
     }
 
     /// Returns the maximum number of connected peers.
     pub fn max_connected_peers(&self) -> usize {
-        self.tcp.config().max_connections as usize
+        self.max_connected_peers.load(Ordering::SeqCst)
+    }
+
+    /// Sets the maximum number of connected peers, which may not exceed the connection limit of the TCP stack.
+    pub fn set_max_connected_peers(&self, max_peers: usize) -> Result<()> {
+        let limit = self.tcp.config().max_connections as usize;
+        if max_peers > limit {
+            bail!("The maximum number of peers cannot exceed the connection limit of {limit}");
+        }
+        self.max_connected_peers.store(max_peers, Ordering::SeqCst);
+        Ok(())
     }
 
     /// Returns the number of connected peers.
@@ -333,6 +370,11 @@ impl<N: Network> Router<N> {
         self.connected_peers.read().values().cloned().collect()
     }
 
+    /// Returns the message and byte counters for the given peer IP, if the peer is connected.
+    pub fn get_connected_peer_stats(&self, ip: &SocketAddr) -> Option<Arc<Stats>> {
+        self.tcp.known_peers().get(self.resolve_to_ambiguous(ip)?)
+    }
+
     /// Returns the list of connected peers.
     pub fn connected_peers(&self) -> Vec<SocketAddr> {
         self.connected_peers.read().keys().copied().collect()
@@ -368,14 +410,30 @@ impl<N: Network> Router<N> {
         self.restricted_peers.read().keys().copied().collect()
     }
 
+    /// Returns the list of banned peers.
+    pub fn banned_peers(&self) -> Vec<SocketAddr> {
+        self.banned_peers.read().iter().copied().collect()
+    }
+
     /// Returns the list of trusted peers.
     pub fn trusted_peers(&self) -> &IndexSet<SocketAddr> {
         &self.trusted_peers
     }
 
     /// Returns the list of bootstrap peers.
-    #[cfg(not(feature = "test"))]
     pub fn bootstrap_peers(&self) -> Vec<SocketAddr> {
+        let mut bootstrap_peers = self.default_bootstrap_peers();
+        for peer_ip in self.extra_bootstrap_peers.read().iter() {
+            if !bootstrap_peers.contains(peer_ip) {
+                bootstrap_peers.push(*peer_ip);
+            }
+        }
+        bootstrap_peers
+    }
+
+    /// Returns the list of default bootstrap peers.
+    #[cfg(not(feature = "test"))]
+    fn default_bootstrap_peers(&self) -> Vec<SocketAddr> {
         if self.is_dev {
             // In development mode, connect to the dedicated local beacon.
             match self.node_type.is_beacon() {
@@ -399,9 +457,9 @@ impl<N: Network> Router<N> {
         }
     }
 
-    /// Returns the list of bootstrap peers.
+    /// Returns the list of default bootstrap peers.
     #[cfg(feature = "test")]
-    pub fn bootstrap_peers(&self) -> Vec<SocketAddr> {
+    fn default_bootstrap_peers(&self) -> Vec<SocketAddr> {
         vec![]
     }
 
@@ -443,6 +501,13 @@ impl<N: Network> Router<N> {
         self.candidate_peers.write().extend(eligible_peers);
     }
 
+    /// Inserts the given peer IPs into the bootstrap peers, which the node keeps a connection to.
+    pub fn insert_bootstrap_peers(&self, peers: &[SocketAddr]) {
+        // Compute the set of bootstrap peers, excluding the local IP.
+        let peers = peers.iter().filter(|peer_ip| !self.is_local_ip(peer_ip));
+        self.extra_bootstrap_peers.write().extend(peers);
+    }
+
     /// Inserts the given peer into the restricted peers.
     pub fn insert_restricted_peer(&self, peer_ip: SocketAddr) {
         // Remove this peer from the candidate peers, if it exists.
@@ -451,6 +516,19 @@ impl<N: Network> Router<N> {
         self.restricted_peers.write().insert(peer_ip, Instant::now());
     }
 
+    /// Inserts the given peer into the banned peers.
+    pub fn insert_banned_peer(&self, peer_ip: SocketAddr) {
+        // Remove this peer from the candidate peers, if it exists.
+        self.candidate_peers.write().remove(&peer_ip);
+        // Add the peer to the banned peers.
+        self.banned_peers.write().insert(peer_ip);
+    }
+
+    /// Removes the given peer from the banned peers, returning `true` if it was banned.
+    pub fn remove_banned_peer(&self, peer_ip: SocketAddr) -> bool {
+        self.banned_peers.write().remove(&peer_ip)
+    }
+
     /// Updates the connected peer with the given function.
     pub fn update_connected_peer<Fn: FnMut(&mut Peer<N>)>(
         &self,
@@ -493,6 +571,30 @@ impl<N: Network> Router<N> {
         self.candidate_peers.write().remove(&peer_ip);
     }
 
+    /// Saves the connected and candidate peers to the peer book at the given path, one peer IP per line.
+    pub fn save_peers(&self, path: &Path) -> Result<()> {
+        let mut peers = self.connected_peers().into_iter().collect::<IndexSet<_>>();
+        peers.extend(self.candidate_peers());
+        let contents = peers.iter().map(|peer_ip| format!("{peer_ip}\n")).collect::<String>();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents).map_err(|e| anyhow!("Failed to save the peers to '{}' - {e}", path.display()))
+    }
+
+    /// Loads the peers from the peer book at the given path into the candidate peers, if the peer book exists.
+    pub fn load_peers(&self, path: &Path) -> Result<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to load the peers from '{}' - {e}", path.display()))?;
+        // Skip the malformed lines, so a damaged peer book does not prevent the node from starting.
+        let peers = contents.lines().filter_map(|line| line.trim().parse::<SocketAddr>().ok()).collect::<Vec<_>>();
+        self.insert_candidate_peers(&peers);
+        Ok(())
+    }
+
     /// Spawns a task with the given future; it should only be used for long-running tasks.
     pub fn spawn<T: Future<Output = ()> + Send + 'static>(&self, future: T) {
         self.handles.lock().push(tokio::spawn(future));
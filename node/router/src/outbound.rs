@@ -18,7 +18,7 @@ use snarkos_node_tcp::protocols::Writing;
 use snarkvm::prelude::Network;
 use std::io;
 
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Instant};
 use tokio::sync::oneshot;
 
 pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
@@ -27,6 +27,10 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
 
     /// Sends a "Ping" message to the given peer.
     fn send_ping(&self, peer_ip: SocketAddr, block_locators: Option<BlockLocators<N>>) {
+        // Record the time the `Ping` is sent, in order to measure the latency of the peer.
+        if let Some(peer) = self.router().connected_peers.write().get_mut(&peer_ip) {
+            peer.set_ping_sent(Instant::now());
+        }
         self.send(peer_ip, Message::Ping(Ping::new(self.router().node_type(), block_locators)));
     }
 
@@ -150,6 +150,116 @@ async fn test_connect_with_handshake() {
     }
 }
 
+#[tokio::test]
+async fn test_connect_banned_with_handshake() {
+    // Create 2 routers.
+    let node0 = validator(0, 2).await;
+    let node1 = client(0, 2).await;
+    assert_eq!(node0.number_of_connected_peers(), 0);
+    assert_eq!(node1.number_of_connected_peers(), 0);
+
+    // Enable handshake protocol.
+    node0.enable_handshake().await;
+    node1.enable_handshake().await;
+
+    // Start listening.
+    node0.tcp().enable_listener().await.unwrap();
+    node1.tcp().enable_listener().await.unwrap();
+
+    // Ban node1 from node0.
+    node0.insert_banned_peer(node1.local_ip());
+    assert!(node0.is_banned(&node1.local_ip()));
+
+    {
+        // Connect node0 to node1.
+        assert!(node0.connect(node1.local_ip()).is_none());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node1);
+
+        // Check the router level.
+        assert_eq!(node0.number_of_connected_peers(), 0);
+        assert_eq!(node1.number_of_connected_peers(), 0);
+    }
+    {
+        // Connect node1 to node0.
+        node1.connect(node0.local_ip());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node1);
+
+        // Check the router level.
+        assert_eq!(node0.number_of_connected_peers(), 0);
+        assert_eq!(node1.number_of_connected_peers(), 0);
+    }
+    {
+        // Unban node1 and connect node0 to node1.
+        assert!(node0.remove_banned_peer(node1.local_ip()));
+        node0.connect(node1.local_ip());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node1);
+
+        // Check the router level.
+        assert_eq!(node0.number_of_connected_peers(), 1);
+        assert_eq!(node1.number_of_connected_peers(), 1);
+
+        // Check the connection direction.
+        assert!(node0.get_connected_peer(&node1.local_ip()).unwrap().is_outbound());
+        assert!(!node1.get_connected_peer(&node0.local_ip()).unwrap().is_outbound());
+        // Check the connection stats.
+        assert!(node0.get_connected_peer_stats(&node1.local_ip()).is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_connect_with_lowered_max_peers() {
+    // Create 2 routers.
+    let node0 = validator(0, 2).await;
+    let node1 = client(0, 2).await;
+
+    // Enable handshake protocol.
+    node0.enable_handshake().await;
+    node1.enable_handshake().await;
+
+    // Start listening.
+    node0.tcp().enable_listener().await.unwrap();
+    node1.tcp().enable_listener().await.unwrap();
+
+    // Ensure the maximum number of peers cannot exceed the TCP connection limit.
+    assert!(node0.set_max_connected_peers(3).is_err());
+    assert_eq!(node0.max_connected_peers(), 2);
+
+    {
+        // Lower the maximum number of peers, and connect node0 to node1.
+        node0.set_max_connected_peers(0).unwrap();
+        assert!(node0.connect(node1.local_ip()).is_none());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Check the router level.
+        assert_eq!(node0.number_of_connected_peers(), 0);
+        assert_eq!(node1.number_of_connected_peers(), 0);
+    }
+    {
+        // Raise the maximum number of peers, and connect node0 to node1 again.
+        node0.set_max_connected_peers(2).unwrap();
+        assert!(node0.connect(node1.local_ip()).is_some());
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // Check the router level.
+        assert_eq!(node0.number_of_connected_peers(), 1);
+        assert_eq!(node1.number_of_connected_peers(), 1);
+    }
+}
+
 #[ignore]
 #[tokio::test]
 async fn test_connect_simultaneously_with_handshake() {
@@ -0,0 +1,40 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+use common::*;
+
+use core::str::FromStr;
+use std::net::SocketAddr;
+
+#[tokio::test]
+async fn test_save_and_load_peers() {
+    let path = std::env::temp_dir().join("snarkos-test-peers.txt");
+
+    // Save the candidate peers of a router to the peer book.
+    let node = client(0, 1).await;
+    let peers = [SocketAddr::from_str("1.2.3.4:4130").unwrap(), SocketAddr::from_str("5.6.7.8:4130").unwrap()];
+    node.insert_candidate_peers(&peers);
+    node.save_peers(&path).unwrap();
+
+    // Ensure another router loads the peers from the peer book.
+    let other = client(0, 1).await;
+    assert_eq!(other.number_of_candidate_peers(), 0);
+    other.load_peers(&path).unwrap();
+    assert_eq!(other.candidate_peers(), node.candidate_peers());
+
+    // Ensure a missing peer book is skipped.
+    std::fs::remove_file(&path).unwrap();
+    assert!(other.load_peers(&path).is_ok());
+}
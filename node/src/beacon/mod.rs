@@ -14,9 +14,10 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{traits::NodeInterface, NodeOptions};
 use snarkos_account::Account;
 use snarkos_node_consensus::Consensus;
+use snarkos_node_grpc::Grpc;
 use snarkos_node_messages::{
     BeaconPropose,
     Data,
@@ -53,7 +54,7 @@ use anyhow::{bail, Result};
 use core::{str::FromStr, time::Duration};
 use parking_lot::{Mutex, RwLock};
 use std::{
-    net::SocketAddr,
+    path::PathBuf,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
@@ -74,6 +75,8 @@ pub struct Beacon<N: Network, C: ConsensusStorage<N>> {
     router: Router<N>,
     /// The REST server of the node.
     rest: Option<Rest<N, C, Self>>,
+    /// The gRPC server of the node.
+    grpc: Option<Grpc<N, C, Self>>,
     /// The time it to generate a block.
     block_generation_time: Arc<AtomicU64>,
     /// The unspent records.
@@ -82,20 +85,18 @@ pub struct Beacon<N: Network, C: ConsensusStorage<N>> {
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The shutdown signal.
     shutdown: Arc<AtomicBool>,
+    /// The directory of the node state.
+    storage_dir: PathBuf,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Beacon<N, C> {
-    /// Initializes a new beacon node.
-    pub async fn new(
-        node_ip: SocketAddr,
-        rest_ip: Option<SocketAddr>,
-        account: Account<N>,
-        trusted_peers: &[SocketAddr],
-        genesis: Block<N>,
-        cdn: Option<String>,
-        dev: Option<u16>,
-    ) -> Result<Self> {
+    /// Initializes a new beacon node, with the given settings.
+    pub async fn new(options: NodeOptions<N>, account: Account<N>, genesis: Block<N>) -> Result<Self> {
         let timer = timer!("Beacon::new");
+        let NodeOptions { node_ip, rest, grpc_ip, notify_hooks, wallet, trusted_peers, cdn, data_dir, dev } = options;
+
+        // Construct the directory of the node state.
+        let storage_dir = crate::storage_dir(&data_dir, N::ID, dev);
 
         // Initialize the ledger.
         let ledger = Ledger::load(genesis, dev)?;
@@ -126,11 +127,15 @@ impl<N: Network, C: ConsensusStorage<N>> Beacon<N, C> {
             node_ip,
             NodeType::Beacon,
             account.clone(),
-            trusted_peers,
+            &trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             dev.is_some(),
         )
         .await?;
+        // Load the peers that were saved on the last shutdown.
+        if let Err(error) = router.load_peers(&crate::peers_path(&storage_dir)) {
+            warn!("{error}");
+        }
         lap!(timer, "Initialize the router");
 
         // Initialize the node.
@@ -140,17 +145,43 @@ impl<N: Network, C: ConsensusStorage<N>> Beacon<N, C> {
             consensus: consensus.clone(),
             router,
             rest: None,
+            grpc: None,
             block_generation_time,
             unspent_records: Arc::new(RwLock::new(unspent_records)),
             handles: Default::default(),
             shutdown: Default::default(),
+            storage_dir,
         };
 
+        // Load the memory pool that was saved on the last shutdown, in the background, as it is verified again.
+        let consensus_clone = consensus.clone();
+        let memory_pool_path = crate::memory_pool_path(&node.storage_dir);
+        node.handles.lock().push(tokio::task::spawn_blocking(move || {
+            if let Err(error) = consensus_clone.load_memory_pool(&memory_pool_path) {
+                warn!("{error}");
+            }
+        }));
+        // Initialize the gRPC server.
+        if let Some(grpc_ip) = grpc_ip {
+            node.grpc = Some(Grpc::start(grpc_ip, Some(consensus.clone()), ledger.clone(), Arc::new(node.clone()))?);
+            lap!(timer, "Initialize gRPC server");
+        }
         // Initialize the REST server.
-        if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, Some(consensus), ledger, Arc::new(node.clone()))?);
+        if let Some(rest) = rest {
+            node.rest = Some(Rest::start(&rest, Some(consensus), ledger, Arc::new(node.clone()), wallet.clone())?);
             lap!(timer, "Initialize REST server");
         }
+        // Initialize the notification hooks.
+        if !notify_hooks.is_empty() {
+            node.handles.lock().push(notify_hooks.spawn(node.ledger.clone(), *node.view_key(), wallet.clone()));
+        }
+        // Initialize the wallet, and notify the wallet hook of its events.
+        if let Some(wallet) = wallet {
+            node.handles.lock().push(wallet.spawn(node.ledger.clone()));
+            if let Some(handle) = notify_hooks.spawn_wallet(&wallet) {
+                node.handles.lock().push(handle);
+            }
+        }
         // Initialize the routing.
         node.initialize_routing().await;
         // Initialize the block production.
@@ -173,12 +204,17 @@ impl<N: Network, C: ConsensusStorage<N>> Beacon<N, C> {
     pub fn rest(&self) -> &Option<Rest<N, C, Self>> {
         &self.rest
     }
+
+    /// Returns the gRPC server.
+    pub fn grpc(&self) -> &Option<Grpc<N, C, Self>> {
+        &self.grpc
+    }
 }
 
 #[async_trait]
 impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Beacon<N, C> {
     /// Shuts down the node.
-    async fn shut_down(&self) {
+    async fn shut_down(&self) -> Result<()> {
         info!("Shutting down...");
 
         // Shut down block production.
@@ -189,14 +225,23 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Beacon<N, C> {
         trace!("Shutting down the beacon...");
         self.handles.lock().iter().for_each(|handle| handle.abort());
 
-        // Shut down the router.
+        // Save the peers, and shut down the router.
+        trace!("Saving the peers...");
+        let peers = self.router.save_peers(&crate::peers_path(&self.storage_dir));
         self.router.shut_down().await;
 
-        // Shut down the ledger.
+        // Shut down the ledger, once the block in progress (if any) is written.
         trace!("Shutting down the ledger...");
-        // self.ledger.shut_down().await;
+        let consensus = self.consensus.clone();
+        tokio::task::spawn_blocking(move || consensus.shut_down()).await?;
 
+        // Save the memory pool.
+        trace!("Saving the memory pool...");
+        let memory_pool = self.consensus.save_memory_pool(&crate::memory_pool_path(&self.storage_dir));
+
+        peers.and(memory_pool)?;
         info!("Node has shut down.");
+        Ok(())
     }
 }
 
@@ -355,9 +400,10 @@ impl<N: Network, C: ConsensusStorage<N>> Beacon<N, C> {
                         };
                     }
                     // Log the next block.
+                    let (height, hash) = (next_block.height(), next_block.hash());
                     match serde_json::to_string_pretty(&next_block.header()) {
-                        Ok(header) => info!("Block {}: {header}", next_block.height()),
-                        Err(error) => info!("Block {}: (serde failed: {error})", next_block.height()),
+                        Ok(header) => info!(height, %hash, "Block {height}: {header}"),
+                        Err(error) => info!(height, %hash, "Block {height}: (serde failed: {error})"),
                     }
                 }
                 Err(error) => {
@@ -451,6 +497,7 @@ mod tests {
 
     use rand::SeedableRng;
     use rand_chacha::ChaChaRng;
+    use std::net::SocketAddr;
 
     type CurrentNetwork = Testnet3;
 
@@ -474,17 +521,25 @@ mod tests {
 
         println!("Initializing beacon node...");
 
-        let beacon = Beacon::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::new(
-            node,
-            Some(rest),
-            beacon_account,
-            &[],
-            genesis,
-            None,
+        let options = NodeOptions {
+            node_ip: node,
+            rest: Some(snarkos_node_rest::RestOptions {
+                ip: rest,
+                auth: false,
+                rps: snarkos_node_rest::DEFAULT_REST_RPS,
+                cors_origins: vec![],
+            }),
+            grpc_ip: None,
+            notify_hooks: Default::default(),
+            wallet: None,
+            trusted_peers: vec![],
+            cdn: None,
+            data_dir: std::env::temp_dir(),
             dev,
-        )
-        .await
-        .unwrap();
+        };
+        let beacon = Beacon::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::new(options, beacon_account, genesis)
+            .await
+            .unwrap();
 
         println!(
             "Loaded beacon node with {} blocks and {} records",
@@ -21,6 +21,7 @@ use snarkvm::prelude::{block::Header, coinbase::EpochChallenge, error};
 
 use std::{io, net::SocketAddr};
 use tokio::task::spawn_blocking;
+use tracing::Instrument;
 
 impl<N: Network, C: ConsensusStorage<N>> P2P for Beacon<N, C> {
     /// Returns a reference to the TCP instance.
@@ -105,10 +106,12 @@ impl<N: Network, C: ConsensusStorage<N>> Reading for Beacon<N, C> {
 
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
-        // Process the message. Disconnect if the peer violated the protocol.
-        if let Err(error) = self.inbound(peer_addr, message).await {
+        // Process the message, with the address of the peer attached to its logs.
+        let span = info_span!("inbound", peer = %peer_addr);
+        // Disconnect if the peer violated the protocol.
+        if let Err(error) = self.inbound(peer_addr, message).instrument(span).await {
             if let Some(peer_ip) = self.router().resolve_to_listener(&peer_addr) {
-                warn!("Disconnecting from '{peer_ip}' - {error}");
+                warn!(peer = %peer_ip, "Disconnecting from '{peer_ip}' - {error}");
                 self.send(peer_ip, Message::Disconnect(DisconnectReason::ProtocolViolation.into()));
                 // Disconnect from this peer.
                 self.router().disconnect(peer_ip);
@@ -168,7 +171,7 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Beacon<N, C> {
         while let Some(block) = self.router().sync().remove_block_response(latest_height + 1) {
             // Check the next block.
             if let Err(error) = self.consensus.check_next_block(&block) {
-                warn!("The next block ({}) is invalid - {error}", block.height());
+                warn!(height = block.height(), hash = %block.hash(), "The next block is invalid - {error}");
                 break;
             }
             // Attempt to advance to the next block.
@@ -14,7 +14,7 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{traits::NodeInterface, NodeOptions};
 use snarkos_account::Account;
 use snarkos_node_messages::{Message, NodeType, UnconfirmedSolution};
 use snarkos_node_router::{Heartbeat, Inbound, Outbound, Router, Routing};
@@ -32,7 +32,7 @@ use snarkvm::prelude::{
 use anyhow::Result;
 use core::marker::PhantomData;
 use parking_lot::RwLock;
-use std::{net::SocketAddr, sync::Arc};
+use std::{path::PathBuf, sync::Arc};
 
 /// A client node is a full node, capable of querying with the network.
 #[derive(Clone)]
@@ -47,29 +47,33 @@ pub struct Client<N: Network, C: ConsensusStorage<N>> {
     latest_epoch_challenge: Arc<RwLock<Option<EpochChallenge<N>>>>,
     /// The latest block header.
     latest_block_header: Arc<RwLock<Option<Header<N>>>>,
+    /// The directory of the node state.
+    storage_dir: PathBuf,
     /// PhantomData.
     _phantom: PhantomData<C>,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
-    /// Initializes a new client node.
-    pub async fn new(
-        node_ip: SocketAddr,
-        account: Account<N>,
-        trusted_peers: &[SocketAddr],
-        genesis: Block<N>,
-        dev: Option<u16>,
-    ) -> Result<Self> {
+    /// Initializes a new client node, with the given settings.
+    /// Note: A client does not support the REST and gRPC servers, the notification hooks, the wallet, or the CDN.
+    pub async fn new(options: NodeOptions<N>, account: Account<N>, genesis: Block<N>) -> Result<Self> {
+        let NodeOptions { node_ip, trusted_peers, data_dir, dev, .. } = options;
+        // Construct the directory of the node state.
+        let storage_dir = crate::storage_dir(&data_dir, N::ID, dev);
         // Initialize the node router.
         let router = Router::new(
             node_ip,
             NodeType::Client,
             account,
-            trusted_peers,
+            &trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             dev.is_some(),
         )
         .await?;
+        // Load the peers that were saved on the last shutdown.
+        if let Err(error) = router.load_peers(&crate::peers_path(&storage_dir)) {
+            warn!("{error}");
+        }
         // Load the coinbase puzzle.
         let coinbase_puzzle = CoinbasePuzzle::<N>::load()?;
         // Initialize the node.
@@ -79,6 +83,7 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
             coinbase_puzzle,
             latest_epoch_challenge: Default::default(),
             latest_block_header: Default::default(),
+            storage_dir,
             _phantom: PhantomData,
         };
         // Initialize the routing.
@@ -93,12 +98,16 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 #[async_trait]
 impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Client<N, C> {
     /// Shuts down the node.
-    async fn shut_down(&self) {
+    async fn shut_down(&self) -> Result<()> {
         info!("Shutting down...");
 
-        // Shut down the router.
+        // Save the peers, and shut down the router.
+        trace!("Saving the peers...");
+        let peers = self.router.save_peers(&crate::peers_path(&self.storage_dir));
         self.router.shut_down().await;
 
+        peers?;
         info!("Node has shut down.");
+        Ok(())
     }
 }
@@ -12,11 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use metrics::gauge;
 use snarkos_node_messages::{BlockLocators, CHECKPOINT_INTERVAL, NUM_RECENTS};
-use snarkvm::prelude::{store::ConsensusStorage, Ledger, Network};
+use snarkvm::prelude::{
+    store::{
+        helpers::rocksdb::{Database, RocksDB},
+        ConsensusStorage,
+    },
+    Ledger,
+    Network,
+};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use indexmap::IndexMap;
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 /// Returns the block locators for the given ledger.
 pub fn get_block_locators<N: Network, C: ConsensusStorage<N>>(ledger: &Ledger<N, C>) -> Result<BlockLocators<N>> {
@@ -50,3 +63,72 @@ pub fn log_clean_error(dev: Option<u16>) {
         None => error!("Storage corruption detected! Run `snarkos clean` to reset storage"),
     }
 }
+
+/// Flushes the ledger in RocksDB to disk, along with its write-ahead log.
+/// Note: The database is kept open by snarkVM until the process exits, so it is flushed instead of closed.
+pub fn flush_ledger<N: Network>(dev: Option<u16>) -> Result<()> {
+    let database = RocksDB::open(N::ID, dev)?;
+    database.flush()?;
+    database.flush_wal(true)?;
+    Ok(())
+}
+
+/// Returns the directory of the node state for the given network in the given data directory.
+/// Each network is nested in its own subdirectory, and each development node in its own subdirectory of that.
+pub fn storage_dir(data_dir: &Path, network: u16, dev: Option<u16>) -> PathBuf {
+    let path = match network {
+        3 => data_dir.join("testnet3"),
+        network => data_dir.join(format!("network-{network}")),
+    };
+    match dev {
+        Some(id) => path.join(format!("dev-{id}")),
+        None => path,
+    }
+}
+
+/// Returns the path to the peer book of the node in the given storage directory.
+pub fn peers_path(storage_dir: &Path) -> PathBuf {
+    storage_dir.join("peers")
+}
+
+/// Returns the path to the saved memory pool of the node in the given storage directory.
+pub fn memory_pool_path(storage_dir: &Path) -> PathBuf {
+    storage_dir.join("memory_pool")
+}
+
+/// Initializes the Prometheus metrics exporter at the given IP, and periodically records the storage size of the ledger.
+pub fn initialize_metrics<N: Network>(metrics_ip: SocketAddr, dev: Option<u16>) -> Result<()> {
+    // Start the metrics exporter.
+    metrics::initialize(metrics_ip)
+        .map_err(|error| anyhow!("Failed to start the metrics server at {metrics_ip} - {error}"))?;
+
+    // Construct the path to the ledger in storage.
+    let path = aleo_std::aleo_ledger_dir(N::ID, dev);
+    // Periodically record the storage size.
+    tokio::spawn(async move {
+        loop {
+            // Walk the ledger directory on a blocking thread, so that it does not stall the runtime.
+            let ledger_dir = path.clone();
+            if let Ok(size) = tokio::task::spawn_blocking(move || directory_size(&ledger_dir)).await {
+                gauge!(metrics::storage::SIZE_BYTES, size as f64);
+            }
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+    Ok(())
+}
+
+/// Returns the total size in bytes of the files in the given directory, or `0` if it does not exist.
+fn directory_size(path: &Path) -> u64 {
+    match std::fs::read_dir(path) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| match entry.metadata() {
+                Ok(metadata) if metadata.is_dir() => directory_size(&entry.path()),
+                Ok(metadata) => metadata.len(),
+                Err(_) => 0,
+            })
+            .sum(),
+        Err(_) => 0,
+    }
+}
@@ -20,12 +20,15 @@ extern crate async_trait;
 #[macro_use]
 extern crate tracing;
 
+pub use metrics;
 pub use snarkos_node_cdn as cdn;
 pub use snarkos_node_consensus as consensus;
+pub use snarkos_node_grpc as grpc;
 pub use snarkos_node_messages as messages;
 pub use snarkos_node_rest as rest;
 pub use snarkos_node_router as router;
 pub use snarkos_node_tcp as tcp;
+pub use snarkos_node_wallet as wallet;
 pub use snarkvm;
 
 mod beacon;
@@ -43,6 +46,9 @@ pub use validator::*;
 mod node;
 pub use node::*;
 
+mod notify;
+pub use notify::*;
+
 mod helpers;
 pub use helpers::*;
 
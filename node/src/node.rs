@@ -12,9 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{traits::NodeInterface, Beacon, Client, Prover, Validator};
+use crate::{on_shutdown, traits::NodeInterface, Beacon, Client, NotifyHooks, Prover, Validator};
 use snarkos_account::Account;
 use snarkos_node_messages::NodeType;
+use snarkos_node_rest::RestOptions;
+use snarkos_node_wallet::Wallet;
 use snarkvm::prelude::{
     block::Block,
     store::helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
@@ -25,7 +27,30 @@ use snarkvm::prelude::{
 };
 
 use anyhow::Result;
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+/// The settings of a node, besides its account and genesis block.
+/// Note: Each node type ignores the settings it does not support (e.g. a client does not run a REST server).
+pub struct NodeOptions<N: Network> {
+    /// The IP address and port for the node server.
+    pub node_ip: SocketAddr,
+    /// The settings of the REST server, if it is enabled.
+    pub rest: Option<RestOptions>,
+    /// The IP address and port for the gRPC server, if it is enabled.
+    pub grpc_ip: Option<SocketAddr>,
+    /// The hooks that are notified of new blocks, transactions, and wallet events.
+    pub notify_hooks: NotifyHooks,
+    /// The wallet, whose records the node tracks, if any.
+    pub wallet: Option<Wallet<N>>,
+    /// The IP addresses and ports of the peers to connect to.
+    pub trusted_peers: Vec<SocketAddr>,
+    /// The CDN to sync the ledger from, if any.
+    pub cdn: Option<String>,
+    /// The data directory, in which the state of the node is kept.
+    pub data_dir: PathBuf,
+    /// The unique ID of this node in development mode.
+    pub dev: Option<u16>,
+}
 
 pub enum Node<N: Network> {
     /// A beacon is a full node, capable of producing blocks.
@@ -39,54 +64,40 @@ pub enum Node<N: Network> {
 }
 
 impl<N: Network> Node<N> {
-    /// Initializes a new beacon node.
-    pub async fn new_beacon(
-        node_ip: SocketAddr,
-        rest_ip: Option<SocketAddr>,
-        account: Account<N>,
-        trusted_peers: &[SocketAddr],
-        genesis: Block<N>,
-        cdn: Option<String>,
-        dev: Option<u16>,
-    ) -> Result<Self> {
-        Ok(Self::Beacon(Arc::new(Beacon::new(node_ip, rest_ip, account, trusted_peers, genesis, cdn, dev).await?)))
+    /// Initializes a new beacon node, with the given settings.
+    pub async fn new_beacon(options: NodeOptions<N>, account: Account<N>, genesis: Block<N>) -> Result<Self> {
+        let dev = options.dev;
+        let beacon = Beacon::new(options, account, genesis).await?;
+        // Flush the ledger once the node has shut down.
+        on_shutdown(move || Self::flush_ledger(dev));
+        Ok(Self::Beacon(Arc::new(beacon)))
     }
 
-    /// Initializes a new validator node.
-    pub async fn new_validator(
-        node_ip: SocketAddr,
-        rest_ip: Option<SocketAddr>,
-        account: Account<N>,
-        trusted_peers: &[SocketAddr],
-        genesis: Block<N>,
-        cdn: Option<String>,
-        dev: Option<u16>,
-    ) -> Result<Self> {
-        Ok(Self::Validator(Arc::new(
-            Validator::new(node_ip, rest_ip, account, trusted_peers, genesis, cdn, dev).await?,
-        )))
+    /// Initializes a new validator node, with the given settings.
+    pub async fn new_validator(options: NodeOptions<N>, account: Account<N>, genesis: Block<N>) -> Result<Self> {
+        let dev = options.dev;
+        let validator = Validator::new(options, account, genesis).await?;
+        // Flush the ledger once the node has shut down.
+        on_shutdown(move || Self::flush_ledger(dev));
+        Ok(Self::Validator(Arc::new(validator)))
     }
 
-    /// Initializes a new prover node.
-    pub async fn new_prover(
-        node_ip: SocketAddr,
-        account: Account<N>,
-        trusted_peers: &[SocketAddr],
-        genesis: Block<N>,
-        dev: Option<u16>,
-    ) -> Result<Self> {
-        Ok(Self::Prover(Arc::new(Prover::new(node_ip, account, trusted_peers, genesis, dev).await?)))
+    /// Initializes a new prover node, with the given settings.
+    pub async fn new_prover(options: NodeOptions<N>, account: Account<N>, genesis: Block<N>) -> Result<Self> {
+        Ok(Self::Prover(Arc::new(Prover::new(options, account, genesis).await?)))
     }
 
-    /// Initializes a new client node.
-    pub async fn new_client(
-        node_ip: SocketAddr,
-        account: Account<N>,
-        trusted_peers: &[SocketAddr],
-        genesis: Block<N>,
-        dev: Option<u16>,
-    ) -> Result<Self> {
-        Ok(Self::Client(Arc::new(Client::new(node_ip, account, trusted_peers, genesis, dev).await?)))
+    /// Initializes a new client node, with the given settings.
+    pub async fn new_client(options: NodeOptions<N>, account: Account<N>, genesis: Block<N>) -> Result<Self> {
+        Ok(Self::Client(Arc::new(Client::new(options, account, genesis).await?)))
+    }
+
+    /// Flushes the ledger to disk, and logs the outcome.
+    fn flush_ledger(dev: Option<u16>) {
+        match crate::flush_ledger::<N>(dev) {
+            Ok(()) => trace!("Flushed the ledger"),
+            Err(error) => error!("Failed to flush the ledger - {error}"),
+        }
     }
 
     /// Returns the node type.
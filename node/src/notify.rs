@@ -0,0 +1,324 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_wallet::Wallet;
+use snarkvm::prelude::{
+    block::{Block, Transaction},
+    store::ConsensusStorage,
+    Ledger,
+    Network,
+    ViewKey,
+};
+
+use anyhow::{bail, Error, Result};
+use core::{str::FromStr, time::Duration};
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+
+/// A hook that is notified with the hash of a block or a transaction, or with an event of the wallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NotifyHook {
+    /// A shell command, in which each occurrence of `%s` is replaced with the hash (or the event),
+    /// as a single argument that must not be quoted in the command.
+    Command(String),
+    /// A webhook URL, to which the hash (or the event) is sent in a POST request.
+    Webhook(String),
+}
+
+impl FromStr for NotifyHook {
+    type Err = Error;
+
+    /// Parses a webhook URL if the string starts with `http://` or `https://`, and a shell command otherwise.
+    fn from_str(hook: &str) -> Result<Self> {
+        let hook = hook.trim();
+        if hook.is_empty() {
+            bail!("The notification hook must not be empty");
+        }
+        match hook.starts_with("http://") || hook.starts_with("https://") {
+            true => Ok(Self::Webhook(hook.to_string())),
+            false => Ok(Self::Command(hook.to_string())),
+        }
+    }
+}
+
+impl NotifyHook {
+    /// Notifies the hook with the given hash, without waiting for the notification to complete.
+    pub fn notify(&self, hash: String) {
+        let body = serde_json::json!({ "hash": hash });
+        self.notify_with(hash, body);
+    }
+
+    /// Notifies the hook with the given event, without waiting for the notification to complete.
+    /// A command receives the event as compact JSON in place of `%s`, and a webhook as the body of the request.
+    pub fn notify_event(&self, event: serde_json::Value) {
+        self.notify_with(event.to_string(), event);
+    }
+
+    /// Notifies the hook with the given argument for a command, or the given body for a webhook.
+    fn notify_with(&self, argument: String, body: serde_json::Value) {
+        let hook = self.clone();
+        tokio::spawn(async move {
+            if let Err(error) = hook.run(&argument, &body).await {
+                warn!("Failed to notify '{argument}' - {error}");
+            }
+        });
+    }
+
+    /// Runs the hook with the given argument for a command, or the given body for a webhook.
+    async fn run(&self, argument: &str, body: &serde_json::Value) -> Result<()> {
+        match self {
+            Self::Command(command) => {
+                // Pass the argument to the shell as a parameter, instead of pasting it into the command,
+                // so that it is neither split into words nor interpreted by the shell.
+                #[cfg(target_family = "windows")]
+                let status = tokio::process::Command::new("cmd")
+                    .args(["/V:ON", "/C", &command.replace("%s", "!SNARKOS_NOTIFY_ARG!")])
+                    .env("SNARKOS_NOTIFY_ARG", argument)
+                    .status()
+                    .await?;
+                #[cfg(not(target_family = "windows"))]
+                let status = tokio::process::Command::new("sh")
+                    .args(["-c", &command.replace("%s", "\"$1\""), "sh", argument])
+                    .status()
+                    .await?;
+                if !status.success() {
+                    bail!("The command '{command}' exited with {status}");
+                }
+            }
+            Self::Webhook(url) => {
+                let response = reqwest::Client::new().post(url).json(body).send().await?;
+                if !response.status().is_success() {
+                    bail!("The webhook '{url}' responded with {}", response.status());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The hooks that are notified of new blocks, wallet-relevant transactions, and wallet events.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NotifyHooks {
+    /// The hook that is notified with the hash of each block added to the ledger.
+    pub block: Option<NotifyHook>,
+    /// The hook that is notified with the ID of each confirmed transaction that creates a record owned by the node,
+    /// or by the accounts of its wallet if it has one.
+    pub transaction: Option<NotifyHook>,
+    /// The hook that is notified with each event of the wallet of the node.
+    pub wallet: Option<NotifyHook>,
+}
+
+impl NotifyHooks {
+    /// Returns `true` if no block or transaction hooks are set.
+    pub fn is_empty(&self) -> bool {
+        self.block.is_none() && self.transaction.is_none()
+    }
+
+    /// Spawns a task that notifies the hooks of each block added to the given ledger, and of each transaction in it
+    /// that creates a record owned by the accounts of the given wallet, or by the given view key without a wallet.
+    pub fn spawn<N: Network, C: 'static + ConsensusStorage<N>>(
+        &self,
+        ledger: Ledger<N, C>,
+        view_key: ViewKey<N>,
+        wallet: Option<Wallet<N>>,
+    ) -> JoinHandle<()> {
+        let hooks = self.clone();
+        tokio::spawn(async move {
+            let mut last_height = ledger.latest_height();
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+
+                let latest_height = ledger.latest_height();
+                last_height = hooks.notify_blocks(
+                    last_height,
+                    latest_height,
+                    |height| ledger.get_block(height),
+                    &view_key,
+                    wallet.as_ref(),
+                );
+            }
+        })
+    }
+
+    /// Notifies the hooks of each block after `last_height`, up to `latest_height`, and returns the height
+    /// of the last block that was notified. If a block fails to be retrieved, the notifications stop before it,
+    /// so that it is retried next time, and the blocks are always notified in order.
+    fn notify_blocks<N: Network>(
+        &self,
+        last_height: u32,
+        latest_height: u32,
+        get_block: impl Fn(u32) -> Result<Block<N>>,
+        view_key: &ViewKey<N>,
+        wallet: Option<&Wallet<N>>,
+    ) -> u32 {
+        for height in last_height + 1..=latest_height {
+            let block = match get_block(height) {
+                Ok(block) => block,
+                Err(error) => {
+                    warn!("Failed to retrieve block {height} for the notification hooks, retrying - {error}");
+                    return height - 1;
+                }
+            };
+            if let Some(hook) = &self.block {
+                hook.notify(block.hash().to_string());
+            }
+            if let Some(hook) = &self.transaction {
+                for confirmed in block.transactions().iter() {
+                    if Self::is_wallet_relevant(confirmed.transaction(), view_key, wallet) {
+                        hook.notify(confirmed.transaction().id().to_string());
+                    }
+                }
+            }
+        }
+        latest_height.max(last_height)
+    }
+
+    /// Spawns a task that notifies the wallet hook of each event of the given wallet, if the hook is set.
+    pub fn spawn_wallet<N: Network>(&self, wallet: &Wallet<N>) -> Option<JoinHandle<()>> {
+        let hook = self.wallet.clone()?;
+        let mut event_receiver = wallet.subscribe();
+        Some(tokio::spawn(async move {
+            loop {
+                match event_receiver.recv().await {
+                    Ok(event) => match serde_json::to_value(&event) {
+                        Ok(event) => hook.notify_event(event),
+                        Err(error) => warn!("Failed to serialize a wallet event for the notification hook - {error}"),
+                    },
+                    Err(RecvError::Lagged(num_skipped)) => {
+                        warn!("The wallet notification hook skipped {num_skipped} events")
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        }))
+    }
+
+    /// Returns `true` if the given transaction creates a record owned by one of the accounts of the given wallet,
+    /// including the watch-only accounts, or by the given view key if there is no wallet.
+    fn is_wallet_relevant<N: Network>(
+        transaction: &Transaction<N>,
+        view_key: &ViewKey<N>,
+        wallet: Option<&Wallet<N>>,
+    ) -> bool {
+        transaction.records().any(|(_, record)| match wallet {
+            Some(wallet) => wallet.is_owner(record),
+            None => record.is_owner(view_key),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use snarkos_account::Account;
+    use snarkvm::prelude::{
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        PrivateKey,
+        TestRng,
+        Testnet3,
+        VM,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_notify_hook_from_str() {
+        assert_eq!(
+            NotifyHook::from_str("https://example.com/hook").unwrap(),
+            NotifyHook::Webhook("https://example.com/hook".to_string())
+        );
+        assert_eq!(
+            NotifyHook::from_str("http://127.0.0.1:8080").unwrap(),
+            NotifyHook::Webhook("http://127.0.0.1:8080".to_string())
+        );
+        assert_eq!(
+            NotifyHook::from_str(" echo %s >> blocks.log ").unwrap(),
+            NotifyHook::Command("echo %s >> blocks.log".to_string())
+        );
+        assert!(NotifyHook::from_str("").is_err());
+        assert!(NotifyHook::from_str("   ").is_err());
+    }
+
+    #[test]
+    fn test_notify_blocks() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let block = vm.genesis(&private_key, rng).unwrap();
+
+        // Retrieve each block, except block 3 on the first attempt.
+        let requested = Mutex::new(Vec::new());
+        let get_block = |height: u32| {
+            let mut requested = requested.lock();
+            requested.push(height);
+            match height == 3 && requested.iter().filter(|h| **h == 3).count() == 1 {
+                true => bail!("Block {height} is not available yet"),
+                false => Ok(block.clone()),
+            }
+        };
+        let hooks = NotifyHooks::default();
+
+        // Ensure the notifications stop at the block that fails to be retrieved, and resume from it.
+        assert_eq!(hooks.notify_blocks(1, 5, get_block, &view_key, None), 2);
+        assert_eq!(hooks.notify_blocks(2, 5, get_block, &view_key, None), 5);
+        assert_eq!(*requested.lock(), vec![2, 3, 3, 4, 5]);
+
+        // Ensure no block is retrieved if there are no new blocks.
+        assert_eq!(hooks.notify_blocks(5, 5, get_block, &view_key, None), 5);
+        assert_eq!(requested.lock().len(), 5);
+    }
+
+    #[test]
+    fn test_is_wallet_relevant() {
+        let rng = &mut TestRng::default();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let other = Account::<CurrentNetwork>::new(rng).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let block = vm.genesis(account.private_key(), rng).unwrap();
+        let is_relevant = |view_key, wallet: Option<&Wallet<CurrentNetwork>>| {
+            block
+                .transactions()
+                .iter()
+                .any(|confirmed| NotifyHooks::is_wallet_relevant(confirmed.transaction(), view_key, wallet))
+        };
+
+        // Ensure the records are relevant to the node account, without a wallet.
+        assert!(is_relevant(account.view_key(), None));
+        assert!(!is_relevant(other.view_key(), None));
+
+        // Ensure the accounts of the wallet, rather than the node account, decide the relevance.
+        let wallet = Wallet::new(vec![account.clone()]);
+        assert!(is_relevant(other.view_key(), Some(&wallet)));
+        let wallet = Wallet::new(vec![other.clone()]);
+        assert!(!is_relevant(account.view_key(), Some(&wallet)));
+    }
+
+    #[cfg(not(target_family = "windows"))]
+    #[tokio::test]
+    async fn test_notify_command() {
+        let path = std::env::temp_dir().join(format!("snarkos-test-notify-command-{}", std::process::id()));
+        let hook = NotifyHook::Command(format!("echo %s > {}", path.display()));
+
+        // Ensure the command receives the event as valid JSON, without the shell interpreting it.
+        let event = serde_json::json!({ "type": "record_received", "memo": "a  b; $(exit 1) `false` 'c' * | &" });
+        hook.run(&event.to_string(), &event).await.unwrap();
+        let received = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(received.trim_end()).unwrap(), event);
+
+        // Ensure a failing command is reported.
+        assert!(NotifyHook::Command("exit 1".to_string()).run("", &event).await.is_err());
+    }
+}
@@ -14,9 +14,11 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{traits::NodeInterface, NodeOptions};
+use metrics::gauge;
 use snarkos_account::Account;
 use snarkos_node_messages::{Data, Message, NodeType, UnconfirmedSolution};
+use snarkos_node_rest::{ProverRest, Proving};
 use snarkos_node_router::{Heartbeat, Inbound, Outbound, Router, Routing};
 use snarkos_node_tcp::{
     protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
@@ -26,6 +28,7 @@ use snarkvm::prelude::{
     block::{Block, Header},
     coinbase::{CoinbasePuzzle, EpochChallenge, ProverSolution},
     store::ConsensusStorage,
+    Address,
     Network,
 };
 
@@ -35,11 +38,13 @@ use core::{marker::PhantomData, time::Duration};
 use parking_lot::{Mutex, RwLock};
 use rand::{rngs::OsRng, CryptoRng, Rng};
 use std::{
-    net::SocketAddr,
+    collections::VecDeque,
+    path::PathBuf,
     sync::{
         atomic::{AtomicBool, AtomicU8, Ordering},
         Arc,
     },
+    time::Instant,
 };
 use tokio::task::JoinHandle;
 
@@ -60,39 +65,53 @@ pub struct Prover<N: Network, C: ConsensusStorage<N>> {
     puzzle_instances: Arc<AtomicU8>,
     /// The maximum number of puzzle instances.
     max_puzzle_instances: u8,
+    /// The flag indicating if the prover is generating solutions.
+    is_proving: Arc<AtomicBool>,
+    /// The address that is rewarded for the solutions of the prover.
+    reward_address: Arc<RwLock<Address<N>>>,
+    /// The timestamps of the recent puzzle iterations, used to compute the hashrate.
+    recent_iterations: Arc<Mutex<VecDeque<Instant>>>,
+    /// The REST server of the node.
+    rest: Option<ProverRest<N, Self>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The shutdown signal.
     shutdown: Arc<AtomicBool>,
+    /// The directory of the node state.
+    storage_dir: PathBuf,
     /// PhantomData.
     _phantom: PhantomData<C>,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
-    /// Initializes a new prover node.
-    pub async fn new(
-        node_ip: SocketAddr,
-        account: Account<N>,
-        trusted_peers: &[SocketAddr],
-        genesis: Block<N>,
-        dev: Option<u16>,
-    ) -> Result<Self> {
+    /// Initializes a new prover node, with the given settings.
+    /// Note: A prover does not support the gRPC server, the notification hooks, the wallet, or the CDN.
+    pub async fn new(options: NodeOptions<N>, account: Account<N>, genesis: Block<N>) -> Result<Self> {
+        let NodeOptions { node_ip, rest, trusted_peers, data_dir, dev, .. } = options;
+        // Construct the directory of the node state.
+        let storage_dir = crate::storage_dir(&data_dir, N::ID, dev);
+        // Retrieve the reward address.
+        let reward_address = account.address();
         // Initialize the node router.
         let router = Router::new(
             node_ip,
             NodeType::Prover,
             account,
-            trusted_peers,
+            &trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             dev.is_some(),
         )
         .await?;
+        // Load the peers that were saved on the last shutdown.
+        if let Err(error) = router.load_peers(&crate::peers_path(&storage_dir)) {
+            warn!("{error}");
+        }
         // Load the coinbase puzzle.
         let coinbase_puzzle = CoinbasePuzzle::<N>::load()?;
         // Compute the maximum number of puzzle instances.
         let max_puzzle_instances = num_cpus::get().saturating_sub(2).clamp(1, 6);
         // Initialize the node.
-        let node = Self {
+        let mut node = Self {
             router,
             genesis,
             coinbase_puzzle,
@@ -100,10 +119,19 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
             latest_block_header: Default::default(),
             puzzle_instances: Default::default(),
             max_puzzle_instances: u8::try_from(max_puzzle_instances)?,
+            is_proving: Arc::new(AtomicBool::new(true)),
+            reward_address: Arc::new(RwLock::new(reward_address)),
+            recent_iterations: Default::default(),
+            rest: None,
             handles: Default::default(),
             shutdown: Default::default(),
+            storage_dir,
             _phantom: Default::default(),
         };
+        // Initialize the REST server.
+        if let Some(rest) = rest {
+            node.rest = Some(ProverRest::start(&rest, Arc::new(node.clone()))?);
+        }
         // Initialize the routing.
         node.initialize_routing().await;
         // Initialize the coinbase puzzle.
@@ -113,12 +141,58 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         // Return the node.
         Ok(node)
     }
+
+    /// Returns the REST server.
+    pub fn rest(&self) -> &Option<ProverRest<N, Self>> {
+        &self.rest
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Proving<N> for Prover<N, C> {
+    /// Returns `true` if the prover is generating solutions for the coinbase puzzle.
+    fn is_proving(&self) -> bool {
+        self.is_proving.load(Ordering::Relaxed)
+    }
+
+    /// Resumes generating solutions for the coinbase puzzle.
+    fn start_proving(&self) {
+        if !self.is_proving.swap(true, Ordering::Relaxed) {
+            info!("Resumed the coinbase puzzle");
+        }
+    }
+
+    /// Pauses generating solutions for the coinbase puzzle.
+    fn stop_proving(&self) {
+        if self.is_proving.swap(false, Ordering::Relaxed) {
+            info!("Paused the coinbase puzzle");
+        }
+    }
+
+    /// Returns the address that is rewarded for the solutions of the prover.
+    fn reward_address(&self) -> Address<N> {
+        *self.reward_address.read()
+    }
+
+    /// Sets the address that is rewarded for the solutions of the prover.
+    fn set_reward_address(&self, address: Address<N>) {
+        info!("Updated the reward address to '{address}'");
+        *self.reward_address.write() = address;
+    }
+
+    /// Returns the number of solutions attempted per second, averaged over the recent past.
+    fn hashrate(&self) -> f64 {
+        let recent_iterations = self.recent_iterations.lock();
+        // Count the iterations that are within the hashrate window.
+        let num_iterations =
+            recent_iterations.iter().filter(|timestamp| timestamp.elapsed() <= Self::HASHRATE_WINDOW).count();
+        num_iterations as f64 / Self::HASHRATE_WINDOW.as_secs_f64()
+    }
 }
 
 #[async_trait]
 impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Prover<N, C> {
     /// Shuts down the node.
-    async fn shut_down(&self) {
+    async fn shut_down(&self) -> Result<()> {
         info!("Shutting down...");
 
         // Shut down the coinbase puzzle.
@@ -129,14 +203,21 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Prover<N, C> {
         trace!("Shutting down the prover...");
         self.handles.lock().iter().for_each(|handle| handle.abort());
 
-        // Shut down the router.
+        // Save the peers, and shut down the router.
+        trace!("Saving the peers...");
+        let peers = self.router.save_peers(&crate::peers_path(&self.storage_dir));
         self.router.shut_down().await;
 
+        peers?;
         info!("Node has shut down.");
+        Ok(())
     }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
+    /// The duration over which the hashrate is averaged.
+    const HASHRATE_WINDOW: Duration = Duration::from_secs(60);
+
     /// Initialize a new instance of the coinbase puzzle.
     async fn initialize_coinbase_puzzle(&self) {
         for _ in 0..self.max_puzzle_instances {
@@ -157,6 +238,13 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
                 continue;
             }
 
+            // If the prover was paused, then skip this iteration.
+            if !self.is_proving() {
+                trace!("Skipping an iteration of the coinbase puzzle (paused)");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+
             // If the number of instances of the coinbase puzzle exceeds the maximum, then skip this iteration.
             if self.num_puzzle_instances() > self.max_puzzle_instances {
                 // Sleep for a brief period of time.
@@ -224,10 +312,24 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         // Compute the prover solution.
         let result = self
             .coinbase_puzzle
-            .prove(epoch_challenge, self.address(), rng.gen(), Some(proof_target))
+            .prove(epoch_challenge, self.reward_address(), rng.gen(), Some(proof_target))
             .ok()
             .and_then(|solution| solution.to_target().ok().map(|solution_target| (solution_target, solution)));
 
+        // Record the iteration, and remove the iterations that are outside of the hashrate window.
+        {
+            let mut recent_iterations = self.recent_iterations.lock();
+            recent_iterations.push_back(Instant::now());
+            while recent_iterations
+                .front()
+                .map(|timestamp| timestamp.elapsed() > Self::HASHRATE_WINDOW)
+                .unwrap_or(false)
+            {
+                recent_iterations.pop_front();
+            }
+        }
+        // Update the metrics for the hashrate.
+        gauge!(metrics::prover::HASHRATE, self.hashrate());
         // Decrement the puzzle instances.
         self.decrement_puzzle_instances();
         // Return the result.
@@ -19,6 +19,7 @@ use snarkos_node_tcp::{Connection, ConnectionSide, Tcp};
 use snarkvm::prelude::{block::Transaction, Network};
 
 use std::{io, net::SocketAddr};
+use tracing::Instrument;
 
 impl<N: Network, C: ConsensusStorage<N>> P2P for Prover<N, C> {
     /// Returns a reference to the TCP instance.
@@ -94,10 +95,12 @@ impl<N: Network, C: ConsensusStorage<N>> Reading for Prover<N, C> {
 
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
-        // Process the message. Disconnect if the peer violated the protocol.
-        if let Err(error) = self.inbound(peer_addr, message).await {
+        // Process the message, with the address of the peer attached to its logs.
+        let span = info_span!("inbound", peer = %peer_addr);
+        // Disconnect if the peer violated the protocol.
+        if let Err(error) = self.inbound(peer_addr, message).instrument(span).await {
             if let Some(peer_ip) = self.router().resolve_to_listener(&peer_addr) {
-                warn!("Disconnecting from '{peer_addr}' - {error}");
+                warn!(peer = %peer_ip, "Disconnecting from '{peer_addr}' - {error}");
                 self.send(peer_ip, Message::Disconnect(DisconnectReason::ProtocolViolation.into()));
                 // Disconnect from this peer.
                 self.router().disconnect(peer_ip);
@@ -16,6 +16,18 @@ use snarkos_node_messages::NodeType;
 use snarkos_node_router::Routing;
 use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
 
+use anyhow::Result;
+use parking_lot::Mutex;
+
+/// The callbacks to run once the node has shut down on a signal, before the process exits.
+/// Note: The callbacks run in the reverse order of their registration.
+static SHUTDOWN_HOOKS: Mutex<Vec<Box<dyn FnOnce() + Send>>> = parking_lot::const_mutex(Vec::new());
+
+/// Registers a callback to run once the node has shut down on a signal, before the process exits.
+pub fn on_shutdown(hook: impl FnOnce() + Send + 'static) {
+    SHUTDOWN_HOOKS.lock().push(Box::new(hook));
+}
+
 #[async_trait]
 pub trait NodeInterface<N: Network>: Routing<N> {
     /// Returns the node type.
@@ -44,20 +56,44 @@ pub trait NodeInterface<N: Network>: Routing<N> {
     }
 
     /// Handles OS signals for the node to intercept and perform a clean shutdown.
-    /// Note: Only Ctrl-C is supported; it should work on both Unix-family systems and Windows.
+    /// Note: Ctrl-C is supported on both Unix-family systems and Windows, and SIGTERM on Unix-family systems.
     fn handle_signals(&self) {
         let node = self.clone();
         tokio::task::spawn(async move {
-            match tokio::signal::ctrl_c().await {
+            match shutdown_signal().await {
+                // Exit with a nonzero code only if the node failed to shut down cleanly.
                 Ok(()) => {
-                    node.shut_down().await;
-                    std::process::exit(0);
+                    let result = node.shut_down().await;
+                    // Run the shutdown hooks (e.g. to remove the PID file), even if the node failed to shut down.
+                    let hooks = std::mem::take(&mut *SHUTDOWN_HOOKS.lock());
+                    hooks.into_iter().rev().for_each(|hook| hook());
+                    match result {
+                        Ok(()) => std::process::exit(0),
+                        Err(error) => {
+                            error!("Failed to shut down cleanly - {error}");
+                            std::process::exit(1);
+                        }
+                    }
                 }
-                Err(error) => error!("tokio::signal::ctrl_c encountered an error: {}", error),
+                Err(error) => error!("Failed to listen for the shutdown signals - {error}"),
             }
         });
     }
 
     /// Shuts down the node.
-    async fn shut_down(&self);
+    async fn shut_down(&self) -> Result<()>;
+}
+
+/// Waits for Ctrl-C, or for SIGTERM on Unix-family systems.
+async fn shutdown_signal() -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result,
+            _ = sigterm.recv() => Ok(()),
+        }
+    }
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await
 }
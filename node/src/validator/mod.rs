@@ -14,9 +14,10 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{traits::NodeInterface, NodeOptions};
 use snarkos_account::Account;
 use snarkos_node_consensus::Consensus;
+use snarkos_node_grpc::Grpc;
 use snarkos_node_messages::{BlockRequest, Message, NodeType, PuzzleResponse, UnconfirmedSolution};
 use snarkos_node_rest::Rest;
 use snarkos_node_router::{Heartbeat, Inbound, Outbound, Router, Routing};
@@ -35,7 +36,7 @@ use snarkvm::prelude::{
 use anyhow::Result;
 use parking_lot::Mutex;
 use std::{
-    net::SocketAddr,
+    path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -55,23 +56,22 @@ pub struct Validator<N: Network, C: ConsensusStorage<N>> {
     router: Router<N>,
     /// The REST server of the node.
     rest: Option<Rest<N, C, Self>>,
+    /// The gRPC server of the node.
+    grpc: Option<Grpc<N, C, Self>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The shutdown signal.
     shutdown: Arc<AtomicBool>,
+    /// The directory of the node state.
+    storage_dir: PathBuf,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
-    /// Initializes a new validator node.
-    pub async fn new(
-        node_ip: SocketAddr,
-        rest_ip: Option<SocketAddr>,
-        account: Account<N>,
-        trusted_peers: &[SocketAddr],
-        genesis: Block<N>,
-        cdn: Option<String>,
-        dev: Option<u16>,
-    ) -> Result<Self> {
+    /// Initializes a new validator node, with the given settings.
+    pub async fn new(options: NodeOptions<N>, account: Account<N>, genesis: Block<N>) -> Result<Self> {
+        let NodeOptions { node_ip, rest, grpc_ip, notify_hooks, wallet, trusted_peers, cdn, data_dir, dev } = options;
+        // Construct the directory of the node state.
+        let storage_dir = crate::storage_dir(&data_dir, N::ID, dev);
         // Initialize the ledger.
         let ledger = Ledger::load(genesis, dev)?;
         // Initialize the CDN.
@@ -90,11 +90,15 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             node_ip,
             NodeType::Validator,
             account,
-            trusted_peers,
+            &trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             dev.is_some(),
         )
         .await?;
+        // Load the peers that were saved on the last shutdown.
+        if let Err(error) = router.load_peers(&crate::peers_path(&storage_dir)) {
+            warn!("{error}");
+        }
 
         // Initialize the node.
         let mut node = Self {
@@ -102,13 +106,38 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             consensus: consensus.clone(),
             router,
             rest: None,
+            grpc: None,
             handles: Default::default(),
             shutdown: Default::default(),
+            storage_dir,
         };
 
+        // Load the memory pool that was saved on the last shutdown, in the background, as it is verified again.
+        let consensus_clone = consensus.clone();
+        let memory_pool_path = crate::memory_pool_path(&node.storage_dir);
+        node.handles.lock().push(tokio::task::spawn_blocking(move || {
+            if let Err(error) = consensus_clone.load_memory_pool(&memory_pool_path) {
+                warn!("{error}");
+            }
+        }));
+        // Initialize the gRPC server.
+        if let Some(grpc_ip) = grpc_ip {
+            node.grpc = Some(Grpc::start(grpc_ip, Some(consensus.clone()), ledger.clone(), Arc::new(node.clone()))?);
+        }
         // Initialize the REST server.
-        if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, Some(consensus), ledger, Arc::new(node.clone()))?);
+        if let Some(rest) = rest {
+            node.rest = Some(Rest::start(&rest, Some(consensus), ledger, Arc::new(node.clone()), wallet.clone())?);
+        }
+        // Initialize the notification hooks.
+        if !notify_hooks.is_empty() {
+            node.handles.lock().push(notify_hooks.spawn(node.ledger.clone(), *node.view_key(), wallet.clone()));
+        }
+        // Initialize the wallet, and notify the wallet hook of its events.
+        if let Some(wallet) = wallet {
+            node.handles.lock().push(wallet.spawn(node.ledger.clone()));
+            if let Some(handle) = notify_hooks.spawn_wallet(&wallet) {
+                node.handles.lock().push(handle);
+            }
         }
         // Initialize the sync pool.
         node.initialize_sync()?;
@@ -129,12 +158,17 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
     pub fn rest(&self) -> &Option<Rest<N, C, Self>> {
         &self.rest
     }
+
+    /// Returns the gRPC server.
+    pub fn grpc(&self) -> &Option<Grpc<N, C, Self>> {
+        &self.grpc
+    }
 }
 
 #[async_trait]
 impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Validator<N, C> {
     /// Shuts down the node.
-    async fn shut_down(&self) {
+    async fn shut_down(&self) -> Result<()> {
         info!("Shutting down...");
 
         // Shut down the sync pool.
@@ -145,14 +179,23 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Validator<N, C> {
         trace!("Shutting down the validator...");
         self.handles.lock().iter().for_each(|handle| handle.abort());
 
-        // Shut down the router.
+        // Save the peers, and shut down the router.
+        trace!("Saving the peers...");
+        let peers = self.router.save_peers(&crate::peers_path(&self.storage_dir));
         self.router.shut_down().await;
 
-        // Shut down the ledger.
+        // Shut down the ledger, once the block in progress (if any) is written.
         trace!("Shutting down the ledger...");
-        // self.ledger.shut_down().await;
+        let consensus = self.consensus.clone();
+        tokio::task::spawn_blocking(move || consensus.shut_down()).await?;
+
+        // Save the memory pool.
+        trace!("Saving the memory pool...");
+        let memory_pool = self.consensus.save_memory_pool(&crate::memory_pool_path(&self.storage_dir));
 
+        peers.and(memory_pool)?;
         info!("Node has shut down.");
+        Ok(())
     }
 }
 
@@ -224,7 +267,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             }
             // Check the next block.
             if let Err(error) = self.consensus.check_next_block(&block) {
-                warn!("The next block ({}) is invalid - {error}", block.height());
+                warn!(height = block.height(), hash = %block.hash(), "The next block is invalid - {error}");
                 break;
             }
             // Attempt to advance to the next block.
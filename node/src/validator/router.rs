@@ -30,6 +30,7 @@ use snarkvm::prelude::{block::Transaction, coinbase::EpochChallenge, error, Netw
 
 use std::{io, net::SocketAddr, time::Duration};
 use tokio::task::spawn_blocking;
+use tracing::Instrument;
 
 impl<N: Network, C: ConsensusStorage<N>> P2P for Validator<N, C> {
     /// Returns a reference to the TCP instance.
@@ -114,10 +115,12 @@ impl<N: Network, C: ConsensusStorage<N>> Reading for Validator<N, C> {
 
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
-        // Process the message. Disconnect if the peer violated the protocol.
-        if let Err(error) = self.inbound(peer_addr, message).await {
+        // Process the message, with the address of the peer attached to its logs.
+        let span = info_span!("inbound", peer = %peer_addr);
+        // Disconnect if the peer violated the protocol.
+        if let Err(error) = self.inbound(peer_addr, message).instrument(span).await {
             if let Some(peer_ip) = self.router().resolve_to_listener(&peer_addr) {
-                warn!("Disconnecting from '{peer_ip}' - {error}");
+                warn!(peer = %peer_ip, "Disconnecting from '{peer_ip}' - {error}");
                 self.send(peer_ip, Message::Disconnect(DisconnectReason::ProtocolViolation.into()));
                 // Disconnect from this peer.
                 self.router().disconnect(peer_ip);
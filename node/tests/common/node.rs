@@ -14,59 +14,44 @@
 
 use crate::common::test_peer::sample_genesis_block;
 use snarkos_account::Account;
-use snarkos_node::{Beacon, Client, Prover, Validator};
+use snarkos_node::{Beacon, Client, NodeOptions, Prover, Validator};
 use snarkvm::prelude::{store::helpers::memory::ConsensusMemory, Testnet3 as CurrentNetwork};
 
 use std::str::FromStr;
 
+/// Returns the settings of a test node, without the REST server.
+fn options() -> NodeOptions<CurrentNetwork> {
+    NodeOptions {
+        node_ip: "127.0.0.1:0".parse().unwrap(),
+        rest: None,
+        grpc_ip: None,
+        notify_hooks: Default::default(),
+        wallet: None,
+        trusted_peers: vec![],
+        cdn: None, // No CDN.
+        data_dir: std::env::temp_dir(),
+        dev: None,
+    }
+}
+
+/// Returns the account of a test node.
+fn account() -> Account<CurrentNetwork> {
+    Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap()
+}
+
 pub async fn beacon() -> Beacon<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
-    Beacon::new(
-        "127.0.0.1:0".parse().unwrap(),
-        None,
-        Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
-        &[],
-        sample_genesis_block(), // Should load the current network's genesis block.
-        None,                   // No CDN.
-        None,
-    )
-    .await
-    .expect("couldn't create beacon instance")
+    // Should load the current network's genesis block.
+    Beacon::new(options(), account(), sample_genesis_block()).await.expect("couldn't create beacon instance")
 }
 
 pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
-    Client::new(
-        "127.0.0.1:0".parse().unwrap(),
-        Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
-        &[],
-        sample_genesis_block(),
-        None,
-    )
-    .await
-    .expect("couldn't create client instance")
+    Client::new(options(), account(), sample_genesis_block()).await.expect("couldn't create client instance")
 }
 
 pub async fn prover() -> Prover<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
-    Prover::new(
-        "127.0.0.1:0".parse().unwrap(),
-        Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
-        &[],
-        sample_genesis_block(),
-        None,
-    )
-    .await
-    .expect("couldn't create prover instance")
+    Prover::new(options(), account(), sample_genesis_block()).await.expect("couldn't create prover instance")
 }
 
 pub async fn validator() -> Validator<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
-    Validator::new(
-        "127.0.0.1:0".parse().unwrap(),
-        None,
-        Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
-        &[],
-        sample_genesis_block(), // Should load the current network's genesis block.
-        None,                   // No CDN.
-        None,
-    )
-    .await
-    .expect("couldn't create validator instance")
+    Validator::new(options(), account(), sample_genesis_block()).await.expect("couldn't create validator instance")
 }
@@ -0,0 +1,156 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// The maximum number of wallet events buffered for each subscriber.
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// An event of the wallet, for the records and the balances of its accounts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "", tag = "event", rename_all = "snake_case")]
+pub enum WalletEvent<N: Network> {
+    /// A record owned by an account of the wallet was confirmed in a block.
+    RecordReceived {
+        /// The address of the account that owns the record.
+        owner: Address<N>,
+        /// The commitment of the record.
+        commitment: Field<N>,
+        /// The number of microcredits in the record.
+        microcredits: u64,
+        /// The ID of the transaction that created the record.
+        transaction_id: N::TransactionID,
+        /// The height of the block that created the record.
+        height: u32,
+    },
+    /// A record owned by an account of the wallet was spent in a block.
+    RecordSpent {
+        /// The address of the account that owns the record.
+        owner: Address<N>,
+        /// The commitment of the record.
+        commitment: Field<N>,
+        /// The number of microcredits in the record.
+        microcredits: u64,
+        /// The ID of the transaction that spent the record.
+        transaction_id: N::TransactionID,
+        /// The height of the block that spent the record.
+        height: u32,
+    },
+    /// The balance of an account of the wallet changed in a block.
+    BalanceChanged {
+        /// The address of the account.
+        address: Address<N>,
+        /// The balance of the account, in microcredits.
+        balance: u64,
+        /// The balance of the account before the block, in microcredits.
+        previous_balance: u64,
+        /// The height of the block.
+        height: u32,
+    },
+}
+
+impl<N: Network> WalletEvent<N> {
+    /// Returns the event for the given record, which was confirmed in a block.
+    pub(crate) fn received(record: &WalletRecord<N>) -> Self {
+        Self::RecordReceived {
+            owner: record.owner(),
+            commitment: record.commitment(),
+            microcredits: record.microcredits(),
+            transaction_id: record.transaction_id(),
+            height: record.height(),
+        }
+    }
+
+    /// Returns the event for the given record, which was spent by the given transaction in the block at the given height.
+    pub(crate) fn spent(record: &WalletRecord<N>, transaction_id: N::TransactionID, height: u32) -> Self {
+        Self::RecordSpent {
+            owner: record.owner(),
+            commitment: record.commitment(),
+            microcredits: record.microcredits(),
+            transaction_id,
+            height,
+        }
+    }
+}
+
+impl<N: Network> Wallet<N> {
+    /// Returns a receiver of the events of the wallet, from the next event onwards.
+    pub fn subscribe(&self) -> broadcast::Receiver<WalletEvent<N>> {
+        self.events.subscribe()
+    }
+
+    /// Sends the events of the block at the given height to the subscribers, followed by an event for each account
+    /// whose balance changed, given the net change (in microcredits) of the balance of each account.
+    pub(crate) fn send_events(&self, events: Vec<WalletEvent<N>>, changes: BalanceChanges<N>, height: u32) {
+        let balance_events = changes.into_iter().filter(|(_, change)| *change != 0).map(|(address, change)| {
+            let balance = self.balance_of(&address);
+            let previous_balance = u64::try_from(balance as i128 - change).unwrap_or_default();
+            WalletEvent::BalanceChanged { address, balance, previous_balance, height }
+        });
+        for event in events.into_iter().chain(balance_events.collect::<Vec<_>>()) {
+            // Note: Sending only fails if there are no subscribers, in which case the event is dropped.
+            let _ = self.events.send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{TestRng, Testnet3, Uniform};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_send_events() {
+        let rng = &mut TestRng::default();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let address = account.address();
+        let wallet = Wallet::new(vec![account]);
+        let mut receiver = wallet.subscribe();
+
+        // Add a record of 100 microcredits, as if it was found in block 5.
+        let record = Record::from_str(&format!(
+            "{{ owner: {address}.private, microcredits: 100u64.private, _nonce: 0group.public }}"
+        ))
+        .unwrap();
+        let commitment = Field::from_u64(1);
+        let record = WalletRecord::new(address, commitment, commitment, record, Uniform::rand(rng), 5);
+        wallet.records.write().insert(commitment, record.clone());
+        wallet.send_events(vec![WalletEvent::received(&record)], IndexMap::from([(address, 100)]), 5);
+
+        // Ensure the record event is followed by the balance event.
+        assert_eq!(receiver.try_recv().unwrap(), WalletEvent::received(&record));
+        assert_eq!(receiver.try_recv().unwrap(), WalletEvent::BalanceChanged {
+            address,
+            balance: 100,
+            previous_balance: 0,
+            height: 5
+        });
+        assert!(receiver.try_recv().is_err());
+
+        // Ensure no balance event is sent if the balance did not change.
+        wallet.send_events(vec![], IndexMap::from([(address, 0)]), 6);
+        assert!(receiver.try_recv().is_err());
+
+        // Ensure the events are serialized with their name.
+        let event = serde_json::to_value(WalletEvent::spent(&record, record.transaction_id(), 7)).unwrap();
+        assert_eq!(event["event"], "record_spent");
+        assert_eq!(event["microcredits"], 100);
+        assert_eq!(event["height"], 7);
+    }
+}
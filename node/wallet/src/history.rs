@@ -0,0 +1,356 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use core::fmt::{self, Display, Formatter};
+use serde::{Deserialize, Serialize};
+use snarkvm::{
+    console::prelude::ToBits,
+    prelude::{block::Input, Literal, Transition, U16},
+};
+
+/// The header of the transaction history, when exported as CSV.
+const CSV_HEADER: &str = "transaction_id,height,timestamp,direction,account,counterparty,label,value,fee";
+
+/// The direction of a transaction in the transaction history of the wallet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    /// The transaction transfers credits to an account of the wallet.
+    Incoming,
+    /// The transaction transfers credits from an account of the wallet to another address.
+    Outgoing,
+    /// The transaction transfers credits between the accounts of the wallet (or only pays a fee).
+    #[serde(rename = "self")]
+    SelfTransfer,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Incoming => write!(f, "incoming"),
+            Self::Outgoing => write!(f, "outgoing"),
+            Self::SelfTransfer => write!(f, "self"),
+        }
+    }
+}
+
+/// The format to export the transaction history of the wallet in.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryFormat {
+    /// A JSON array of the transactions.
+    #[default]
+    Json,
+    /// A CSV table of the transactions, with a header row.
+    Csv,
+}
+
+impl FromStr for HistoryFormat {
+    type Err = anyhow::Error;
+
+    /// Parses the name of a history format.
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => bail!("Unknown history format '{format}' (expected json or csv)"),
+        }
+    }
+}
+
+impl Display for HistoryFormat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// A transaction in the transaction history of the wallet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "")]
+pub struct HistoryEntry<N: Network> {
+    /// The ID of the transaction.
+    transaction_id: N::TransactionID,
+    /// The height of the block that contains the transaction.
+    height: u32,
+    /// The timestamp of the block that contains the transaction.
+    timestamp: i64,
+    /// The direction of the transaction.
+    direction: Direction,
+    /// The account of the wallet that spent or received the credits.
+    account: Address<N>,
+    /// The address that the credits are transferred to, if the account of the wallet transferred them.
+    counterparty: Option<Address<N>>,
+    /// The label of the counterparty, if it is in the address book.
+    label: Option<String>,
+    /// The amount transferred, in microcredits.
+    value: u64,
+    /// The fee paid by the account of the wallet, in microcredits.
+    fee: u64,
+}
+
+impl<N: Network> HistoryEntry<N> {
+    /// Returns the entry of the given transaction, if it spends or creates the given records of the accounts,
+    /// as (owner, microcredits) pairs.
+    pub(crate) fn from_transaction(
+        block: &Block<N>,
+        transaction: &Transaction<N>,
+        view_keys: &IndexMap<Address<N>, ViewKey<N>>,
+        spent: &[(Address<N>, u64)],
+        created: &[(Address<N>, u64)],
+    ) -> Result<Option<Self>> {
+        let spent_total = spent.iter().map(|(_, microcredits)| microcredits).sum::<u64>();
+        let created_total = created.iter().map(|(_, microcredits)| microcredits).sum::<u64>();
+
+        let (direction, account, counterparty, value, fee) = match (spent.first(), created.first()) {
+            // The transaction does not involve the wallet.
+            (None, None) => return Ok(None),
+            // The transaction only creates records, so the sender is unknown.
+            (None, Some((account, _))) => (Direction::Incoming, *account, None, created_total, 0),
+            // The transaction spends records of an account, which is the caller of its transitions.
+            (Some((account, _)), _) => {
+                let fee = *transaction.fee()?;
+                let view_key =
+                    view_keys.get(account).ok_or_else(|| anyhow!("The address '{account}' is not in the wallet"))?;
+                let (counterparty, value) = match decrypt_transfer(transaction, view_key)? {
+                    Some((recipient, amount)) => (Some(recipient), amount),
+                    // Otherwise, the value is the amount spent that did not return to the wallet, except for the fee.
+                    None => (None, spent_total.saturating_sub(created_total).saturating_sub(fee)),
+                };
+                let direction = match counterparty {
+                    Some(counterparty) if view_keys.contains_key(&counterparty) => Direction::SelfTransfer,
+                    None if value == 0 => Direction::SelfTransfer,
+                    _ => Direction::Outgoing,
+                };
+                (direction, *account, counterparty, value, fee)
+            }
+        };
+
+        Ok(Some(Self {
+            transaction_id: transaction.id(),
+            height: block.height(),
+            timestamp: block.timestamp(),
+            direction,
+            account,
+            counterparty,
+            label: None,
+            value,
+            fee,
+        }))
+    }
+
+    /// Returns the ID of the transaction.
+    pub const fn transaction_id(&self) -> N::TransactionID {
+        self.transaction_id
+    }
+
+    /// Returns the height of the block that contains the transaction.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the timestamp of the block that contains the transaction.
+    pub const fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the direction of the transaction.
+    pub const fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns the account of the wallet that spent or received the credits.
+    pub const fn account(&self) -> Address<N> {
+        self.account
+    }
+
+    /// Returns the address that the credits are transferred to, if the account of the wallet transferred them.
+    pub const fn counterparty(&self) -> Option<Address<N>> {
+        self.counterparty
+    }
+
+    /// Returns the label of the counterparty, if it is in the address book.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Returns the amount transferred, in microcredits.
+    pub const fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Returns the fee paid by the account of the wallet, in microcredits.
+    pub const fn fee(&self) -> u64 {
+        self.fee
+    }
+
+    /// Returns the entry as a row of the CSV table.
+    fn to_csv_row(&self) -> String {
+        let counterparty = self.counterparty.map(|counterparty| counterparty.to_string()).unwrap_or_default();
+        let label = escape_csv(self.label.as_deref().unwrap_or_default());
+        format!(
+            "{},{},{},{},{},{counterparty},{label},{},{}",
+            self.transaction_id, self.height, self.timestamp, self.direction, self.account, self.value, self.fee
+        )
+    }
+}
+
+impl<N: Network> Wallet<N> {
+    /// Returns the transaction history of the wallet, from oldest to newest, with the labels of the counterparties.
+    pub fn history(&self) -> Vec<HistoryEntry<N>> {
+        let labels = self.labels.read();
+        let mut history = self.history.read().values().cloned().collect::<Vec<_>>();
+        // Order the transactions by height, as a rescan may find older transactions.
+        history.sort_by_key(|entry| entry.height);
+        for entry in &mut history {
+            entry.label = entry.counterparty.and_then(|counterparty| labels.get(&counterparty).cloned());
+        }
+        history
+    }
+
+    /// Exports the transaction history of the wallet in the given format.
+    pub fn export_history(&self, format: HistoryFormat) -> Result<String> {
+        let history = self.history();
+        match format {
+            HistoryFormat::Json => Ok(serde_json::to_string_pretty(&history)?),
+            HistoryFormat::Csv => Ok(std::iter::once(CSV_HEADER.to_string())
+                .chain(history.iter().map(|entry| entry.to_csv_row()))
+                .map(|row| row + "\n")
+                .collect()),
+        }
+    }
+}
+
+/// Returns the recipient and the amount of the transfer in the given transaction, if it is a transfer of credits
+/// from a record of the account with the given view key.
+fn decrypt_transfer<N: Network>(
+    transaction: &Transaction<N>,
+    view_key: &ViewKey<N>,
+) -> Result<Option<(Address<N>, u64)>> {
+    for transition in transaction.transitions() {
+        let is_transfer = matches!(
+            (transition.program_id().to_string().as_str(), transition.function_name().to_string().as_str()),
+            ("credits.aleo", "transfer_private" | "transfer_private_to_public")
+        );
+        if !is_transfer {
+            continue;
+        }
+        // Compute the transition view key as `view_key * tpk`, which matches the commitment only for the caller.
+        let tvk = (*transition.tpk() * **view_key).to_x_coordinate();
+        if N::hash_psd2(&[tvk])? != *transition.tcm() {
+            continue;
+        }
+        // Both transfers take the record, the recipient, and the amount as inputs.
+        match (decrypt_input(transition, tvk, 1)?, decrypt_input(transition, tvk, 2)?) {
+            (
+                Some(Plaintext::Literal(Literal::Address(recipient), _)),
+                Some(Plaintext::Literal(Literal::U64(amount), _)),
+            ) => return Ok(Some((recipient, *amount))),
+            _ => continue,
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the plaintext of the input at the given index of the transition, given its transition view key.
+fn decrypt_input<N: Network>(transition: &Transition<N>, tvk: Field<N>, index: u16) -> Result<Option<Plaintext<N>>> {
+    match transition.inputs().get(index as usize) {
+        Some(Input::Public(_, Some(plaintext))) => Ok(Some(plaintext.clone())),
+        Some(Input::Private(_, Some(ciphertext))) => {
+            // Compute the input view key as `Hash(function ID || tvk || index)`, as in the request of the transition.
+            let (program_id, function_name) = (transition.program_id(), transition.function_name());
+            let function_id = N::hash_bhp1024(
+                &(U16::<N>::new(N::ID), program_id.name(), program_id.network(), function_name).to_bits_le(),
+            )?;
+            let input_view_key = N::hash_psd4(&[function_id, tvk, Field::from_u16(index)])?;
+            Ok(Some(ciphertext.decrypt_symmetric(input_view_key)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns the given field as a CSV field, quoted if it contains a separator, a quote, or a line break.
+fn escape_csv(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{TestRng, Testnet3, Uniform};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_export_history() {
+        let rng = &mut TestRng::default();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let bob = Account::<CurrentNetwork>::new(rng).unwrap().address();
+        let wallet = Wallet::new(vec![account.clone()]);
+        wallet.set_label(bob, Some("Bob, Inc.")).unwrap();
+
+        // Add a transaction to each side of the history.
+        let (incoming, outgoing) = (Uniform::rand(rng), Uniform::rand(rng));
+        let entry = |transaction_id, height, direction, counterparty, value, fee| HistoryEntry {
+            transaction_id,
+            height,
+            timestamp: 1_690_000_000 + height as i64,
+            direction,
+            account: account.address(),
+            counterparty,
+            label: None,
+            value,
+            fee,
+        };
+        wallet.history.write().insert(outgoing, entry(outgoing, 7, Direction::Outgoing, Some(bob), 40, 3));
+        wallet.history.write().insert(incoming, entry(incoming, 5, Direction::Incoming, None, 100, 0));
+
+        // Ensure the history is ordered by height, with the labels of the counterparties.
+        let history = wallet.history();
+        assert_eq!(history.iter().map(|entry| entry.transaction_id()).collect::<Vec<_>>(), vec![incoming, outgoing]);
+        assert_eq!(history[0].label(), None);
+        assert_eq!(history[1].label(), Some("Bob, Inc."));
+
+        // Ensure the CSV export quotes the label.
+        let address = account.address();
+        assert_eq!(
+            wallet.export_history(HistoryFormat::Csv).unwrap(),
+            format!(
+                "{CSV_HEADER}\n{incoming},5,1690000005,incoming,{address},,,100,0\n\
+                 {outgoing},7,1690000007,outgoing,{address},{bob},\"Bob, Inc.\",40,3\n"
+            )
+        );
+
+        // Ensure the JSON export contains each transaction.
+        let json: serde_json::Value =
+            serde_json::from_str(&wallet.export_history(HistoryFormat::Json).unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 2);
+        assert_eq!(json[1]["direction"], "outgoing");
+        assert_eq!(json[1]["label"], "Bob, Inc.");
+    }
+
+    #[test]
+    fn test_escape_csv() {
+        assert_eq!(escape_csv("alice"), "alice");
+        assert_eq!(escape_csv("alice, bob"), "\"alice, bob\"");
+        assert_eq!(escape_csv("the \"alice\""), "\"the \"\"alice\"\"\"");
+    }
+}
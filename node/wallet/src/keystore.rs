@@ -0,0 +1,309 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_account::Account;
+use snarkvm::prelude::{Address, FromBytes, Network, PrivateKey, ToBytes, ViewKey};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, NewAead, Payload},
+    ChaCha20Poly1305,
+    Key,
+    Nonce,
+};
+use indexmap::IndexMap;
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+use std::{io::Write, path::Path};
+
+/// The version of the keystore format.
+const KEYSTORE_VERSION: u8 = 1;
+/// The number of bytes in the salt of the key derivation.
+const SALT_SIZE: usize = 16;
+/// The number of bytes in the nonce of the encryption.
+const NONCE_SIZE: usize = 12;
+
+/// The encrypted keystore of a wallet, as stored in the wallet file.
+///
+/// The private keys are encrypted (with ChaCha20-Poly1305) under a key derived from the password (with Argon2id),
+/// while the addresses and view keys are stored in plaintext, so the wallet can track its records while locked.
+/// A watch-only account only stores its view key, so the wallet tracks its records but cannot spend them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Keystore<N: Network> {
+    /// The version of the keystore format.
+    version: u8,
+    /// The salt of the key derivation, in hex.
+    salt: String,
+    /// The accounts of the keystore.
+    accounts: Vec<KeystoreAccount<N>>,
+    /// The labels of the address book, by address.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    labels: IndexMap<Address<N>, String>,
+}
+
+/// An account of the keystore, with its private key encrypted, unless it is watch-only.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+struct KeystoreAccount<N: Network> {
+    /// The address of the account.
+    address: Address<N>,
+    /// The view key of the account.
+    view_key: ViewKey<N>,
+    /// The encrypted private key of the account, or `None` if the account is watch-only.
+    #[serde(flatten)]
+    private_key: Option<EncryptedPrivateKey>,
+}
+
+/// An encrypted private key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct EncryptedPrivateKey {
+    /// The nonce of the encrypted private key, in hex.
+    nonce: String,
+    /// The encrypted private key, in hex.
+    ciphertext: String,
+}
+
+impl<N: Network> Keystore<N> {
+    /// Initializes a new keystore for the given accounts, encrypting their private keys with the given password.
+    pub fn new<R: Rng + CryptoRng>(accounts: &[Account<N>], password: &str, rng: &mut R) -> Result<Self> {
+        let salt: [u8; SALT_SIZE] = rng.gen();
+        let mut keystore =
+            Self { version: KEYSTORE_VERSION, salt: hex::encode(salt), accounts: Vec::new(), labels: IndexMap::new() };
+        let cipher = keystore.cipher(password)?;
+        for account in accounts {
+            keystore.accounts.push(KeystoreAccount::encrypt(&cipher, account, rng)?);
+        }
+        Ok(keystore)
+    }
+
+    /// Adds the given account to the keystore, encrypting its private key with the given password.
+    pub fn add_account<R: Rng + CryptoRng>(&mut self, account: &Account<N>, password: &str, rng: &mut R) -> Result<()> {
+        let address = account.address();
+        ensure!(self.accounts.iter().all(|a| a.address != address), "The address '{address}' is already in the wallet");
+        let cipher = self.cipher(password)?;
+        // Ensure the password is the password of the keystore, so all accounts are encrypted under the same key.
+        if let Some(existing) = self.accounts.iter().find(|account| account.private_key.is_some()) {
+            existing.decrypt(&cipher)?;
+        }
+        self.accounts.push(KeystoreAccount::encrypt(&cipher, account, rng)?);
+        Ok(())
+    }
+
+    /// Adds a watch-only account for the given view key to the keystore, and returns its address.
+    pub fn add_watch_only(&mut self, view_key: ViewKey<N>) -> Result<Address<N>> {
+        let address = Address::try_from(&view_key)?;
+        ensure!(self.accounts.iter().all(|a| a.address != address), "The address '{address}' is already in the wallet");
+        self.accounts.push(KeystoreAccount { address, view_key, private_key: None });
+        Ok(address)
+    }
+
+    /// Loads the keystore from the wallet file at the given path.
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes =
+            std::fs::read(path).map_err(|e| anyhow!("Failed to read the wallet file at '{}' - {e}", path.display()))?;
+        let keystore: Self = serde_json::from_slice(&bytes)?;
+        ensure!(keystore.version == KEYSTORE_VERSION, "Unsupported wallet file version ({})", keystore.version);
+        Ok(keystore)
+    }
+
+    /// Saves the keystore to the wallet file at the given path.
+    ///
+    /// The keystore is written to a temporary file, which is only readable by its owner on Unix, and is synced
+    /// to disk before it replaces the wallet file, so an interrupted save never leaves a corrupted wallet file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let bytes = serde_json::to_vec_pretty(self)?;
+        let file_name = path.file_name().ok_or_else(|| anyhow!("Invalid wallet file path '{}'", path.display()))?;
+        let mut temp_name = file_name.to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = path.with_file_name(temp_name);
+
+        let write = || -> std::io::Result<()> {
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(target_family = "unix")]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            let mut file = options.open(&temp_path)?;
+            // Restrict the permissions, in case the temporary file already existed.
+            #[cfg(target_family = "unix")]
+            file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+            file.write_all(&bytes)?;
+            file.sync_all()?;
+            std::fs::rename(&temp_path, path)
+        };
+        write().map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            anyhow!("Failed to write the wallet file to '{}' - {e}", path.display())
+        })
+    }
+
+    /// Returns the addresses and view keys of the accounts.
+    pub fn view_keys(&self) -> impl '_ + Iterator<Item = (Address<N>, ViewKey<N>)> {
+        self.accounts.iter().map(|account| (account.address, account.view_key))
+    }
+
+    /// Returns the addresses of the watch-only accounts.
+    pub fn watch_only(&self) -> impl '_ + Iterator<Item = Address<N>> {
+        self.accounts.iter().filter(|account| account.private_key.is_none()).map(|account| account.address)
+    }
+
+    /// Returns the labels of the address book, by address.
+    pub const fn labels(&self) -> &IndexMap<Address<N>, String> {
+        &self.labels
+    }
+
+    /// Replaces the labels of the address book.
+    pub fn set_labels(&mut self, labels: IndexMap<Address<N>, String>) {
+        self.labels = labels;
+    }
+
+    /// Decrypts the private keys of the accounts (except the watch-only accounts) with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<Vec<PrivateKey<N>>> {
+        let cipher = self.cipher(password)?;
+        self.accounts
+            .iter()
+            .filter(|account| account.private_key.is_some())
+            .map(|account| account.decrypt(&cipher))
+            .collect()
+    }
+
+    /// Returns the cipher for the key derived from the given password.
+    fn cipher(&self, password: &str) -> Result<ChaCha20Poly1305> {
+        let salt = hex::decode(&self.salt)?;
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(password.as_bytes(), &salt, &mut key)
+            .map_err(|e| anyhow!("Failed to derive the wallet key - {e}"))?;
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+    }
+}
+
+impl<N: Network> KeystoreAccount<N> {
+    /// Encrypts the private key of the given account, binding the ciphertext to its address.
+    fn encrypt<R: Rng + CryptoRng>(cipher: &ChaCha20Poly1305, account: &Account<N>, rng: &mut R) -> Result<Self> {
+        let nonce: [u8; NONCE_SIZE] = rng.gen();
+        let address = account.address();
+        let (private_key, aad) = (account.private_key().to_bytes_le()?, address.to_string());
+        let payload = Payload { msg: &private_key, aad: aad.as_bytes() };
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|_| anyhow!("Failed to encrypt the private key of '{address}'"))?;
+        let private_key = EncryptedPrivateKey { nonce: hex::encode(nonce), ciphertext: hex::encode(ciphertext) };
+        Ok(Self { address, view_key: *account.view_key(), private_key: Some(private_key) })
+    }
+
+    /// Decrypts the private key of the account.
+    fn decrypt(&self, cipher: &ChaCha20Poly1305) -> Result<PrivateKey<N>> {
+        let encrypted = match &self.private_key {
+            Some(encrypted) => encrypted,
+            None => bail!("The address '{}' is watch-only", self.address),
+        };
+        let nonce = hex::decode(&encrypted.nonce)?;
+        ensure!(nonce.len() == NONCE_SIZE, "Invalid nonce for the private key of '{}'", self.address);
+        let (ciphertext, aad) = (hex::decode(&encrypted.ciphertext)?, self.address.to_string());
+        let payload = Payload { msg: &ciphertext, aad: aad.as_bytes() };
+        let plaintext =
+            cipher.decrypt(Nonce::from_slice(&nonce), payload).map_err(|_| anyhow!("Incorrect wallet password"))?;
+        let private_key = PrivateKey::from_bytes_le(&plaintext)?;
+        // Ensure the private key belongs to the address.
+        ensure!(Address::try_from(&private_key)? == self.address, "Invalid private key for '{}'", self.address);
+        Ok(private_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{TestRng, Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_keystore() {
+        let rng = &mut TestRng::default();
+        let accounts = (0..2).map(|_| Account::<CurrentNetwork>::new(rng).unwrap()).collect::<Vec<_>>();
+
+        // Ensure the keystore round-trips through the wallet file.
+        let keystore = Keystore::new(&accounts, "password", rng).unwrap();
+        let path = std::env::temp_dir().join("snarkos-test-wallet.json");
+        keystore.save(&path).unwrap();
+        assert_eq!(Keystore::<CurrentNetwork>::load(&path).unwrap(), keystore);
+
+        // Ensure the wallet file is replaced, and is only readable by its owner.
+        #[cfg(target_family = "unix")]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            keystore.save(&path).unwrap();
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        assert!(!path.with_file_name("snarkos-test-wallet.json.tmp").exists());
+        std::fs::remove_file(&path).unwrap();
+
+        // Ensure the private keys are not stored in plaintext.
+        let file = serde_json::to_string(&keystore).unwrap();
+        assert!(accounts.iter().all(|account| !file.contains(&account.private_key().to_string())));
+
+        // Ensure the view keys are available without the password.
+        let view_keys = keystore.view_keys().collect::<Vec<_>>();
+        assert_eq!(view_keys, accounts.iter().map(|a| (a.address(), *a.view_key())).collect::<Vec<_>>());
+
+        // Ensure the private keys are only decrypted with the correct password.
+        let private_keys = keystore.decrypt("password").unwrap();
+        assert_eq!(private_keys, accounts.iter().map(|account| *account.private_key()).collect::<Vec<_>>());
+        assert!(keystore.decrypt("wrong password").is_err());
+    }
+
+    #[test]
+    fn test_add_account() {
+        let rng = &mut TestRng::default();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let mut keystore = Keystore::new(core::slice::from_ref(&account), "password", rng).unwrap();
+
+        // Ensure an account is only added with the password of the keystore.
+        let other = Account::<CurrentNetwork>::new(rng).unwrap();
+        assert!(keystore.add_account(&other, "wrong password", rng).is_err());
+        keystore.add_account(&other, "password", rng).unwrap();
+        assert_eq!(keystore.decrypt("password").unwrap(), vec![*account.private_key(), *other.private_key()]);
+
+        // Ensure an account is only added once.
+        assert!(keystore.add_account(&other, "password", rng).is_err());
+        assert_eq!(keystore.view_keys().count(), 2);
+    }
+
+    #[test]
+    fn test_add_watch_only() {
+        let rng = &mut TestRng::default();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let watched = Account::<CurrentNetwork>::new(rng).unwrap();
+        let mut keystore = Keystore::new(core::slice::from_ref(&account), "password", rng).unwrap();
+
+        // Ensure a watch-only account is added without the password, and only once.
+        assert_eq!(keystore.add_watch_only(*watched.view_key()).unwrap(), watched.address());
+        assert!(keystore.add_watch_only(*watched.view_key()).is_err());
+        assert!(keystore.add_account(&watched, "password", rng).is_err());
+
+        // Ensure the watch-only account round-trips through the wallet file, without a private key.
+        let path = std::env::temp_dir().join("snarkos-test-wallet-watch-only.json");
+        keystore.save(&path).unwrap();
+        let keystore = Keystore::<CurrentNetwork>::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(keystore.view_keys().count(), 2);
+        assert_eq!(keystore.watch_only().collect::<Vec<_>>(), vec![watched.address()]);
+
+        // Ensure only the private keys of the other accounts are decrypted.
+        assert_eq!(keystore.decrypt("password").unwrap(), vec![*account.private_key()]);
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The maximum number of characters in a label of the address book.
+pub const MAX_LABEL_LENGTH: usize = 64;
+
+impl<N: Network> Wallet<N> {
+    /// Returns the label of the given address, if it is in the address book.
+    pub fn label(&self, address: &Address<N>) -> Option<String> {
+        self.labels.read().get(address).cloned()
+    }
+
+    /// Returns the labels of the address book, by address.
+    pub fn labels(&self) -> IndexMap<Address<N>, String> {
+        self.labels.read().clone()
+    }
+
+    /// Sets the label of the given address, or removes the address from the address book if the label is `None`.
+    /// If the wallet is opened from a wallet file, the address book is stored in the wallet file.
+    pub fn set_label(&self, address: Address<N>, label: Option<&str>) -> Result<()> {
+        let mut labels = self.labels.write();
+        let mut updated = labels.clone();
+        match label.map(str::trim) {
+            Some(label) => {
+                ensure!(!label.is_empty(), "The label of '{address}' must not be empty");
+                ensure!(
+                    label.chars().count() <= MAX_LABEL_LENGTH,
+                    "The label of '{address}' must be at most {MAX_LABEL_LENGTH} characters"
+                );
+                // Ensure the label is unique, so it identifies a single address.
+                if let Some((other, _)) =
+                    labels.iter().find(|(other, other_label)| **other != address && *other_label == label)
+                {
+                    bail!("The label '{label}' is already used for '{other}'");
+                }
+                updated.insert(address, label.to_string());
+            }
+            None => {
+                updated.shift_remove(&address);
+            }
+        }
+
+        // Store the address book in the wallet file.
+        if let (Some(keystore), Some(path)) = (&self.keystore, &self.path) {
+            let mut keystore = keystore.write();
+            let mut updated_keystore = keystore.clone();
+            updated_keystore.set_labels(updated.clone());
+            updated_keystore.save(path)?;
+            *keystore = updated_keystore;
+        }
+        *labels = updated;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{TestRng, Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_labels() {
+        let rng = &mut TestRng::default();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let (alice, bob) = (Account::<CurrentNetwork>::new(rng).unwrap(), Account::<CurrentNetwork>::new(rng).unwrap());
+        let path = std::env::temp_dir().join("snarkos-test-wallet-labels.json");
+        Keystore::new(core::slice::from_ref(&account), "password", rng).unwrap().save(&path).unwrap();
+        let wallet = Wallet::<CurrentNetwork>::open(&path).unwrap();
+
+        // Ensure the labels are set while the wallet is locked.
+        wallet.set_label(alice.address(), Some(" alice ")).unwrap();
+        wallet.set_label(bob.address(), Some("bob")).unwrap();
+        assert_eq!(wallet.label(&alice.address()), Some("alice".to_string()));
+
+        // Ensure invalid and duplicate labels are rejected.
+        assert!(wallet.set_label(bob.address(), Some("  ")).is_err());
+        assert!(wallet.set_label(bob.address(), Some(&"b".repeat(MAX_LABEL_LENGTH + 1))).is_err());
+        assert!(wallet.set_label(bob.address(), Some("alice")).is_err());
+        assert_eq!(wallet.label(&bob.address()), Some("bob".to_string()));
+
+        // Ensure a label is removed.
+        wallet.set_label(bob.address(), None).unwrap();
+        assert_eq!(wallet.label(&bob.address()), None);
+
+        // Ensure the address book is stored in the wallet file.
+        let wallet = Wallet::<CurrentNetwork>::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(wallet.labels(), IndexMap::from([(alice.address(), "alice".to_string())]));
+    }
+}
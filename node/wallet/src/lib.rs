@@ -0,0 +1,729 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![forbid(unsafe_code)]
+
+#[macro_use]
+extern crate tracing;
+
+mod events;
+pub use events::*;
+
+mod history;
+pub use history::*;
+
+mod keystore;
+pub use keystore::*;
+
+mod labels;
+pub use labels::*;
+
+mod partial;
+pub use partial::*;
+
+mod record;
+pub use record::*;
+
+mod rescan;
+pub use rescan::*;
+
+mod selection;
+pub use selection::*;
+
+mod watch;
+
+use snarkos_account::Account;
+use snarkvm::prelude::{
+    block::{Block, Transaction},
+    store::ConsensusStorage,
+    Address,
+    Ciphertext,
+    Field,
+    GraphKey,
+    Ledger,
+    Network,
+    Plaintext,
+    PrivateKey,
+    Record,
+    Value,
+    ViewKey,
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use core::{str::FromStr, time::Duration};
+use indexmap::{map::Entry, IndexMap};
+use parking_lot::RwLock;
+use rand::{CryptoRng, Rng};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+use tokio::{sync::broadcast, task::JoinHandle};
+
+/// The private keys of the accounts of a wallet, by address.
+type PrivateKeyMap<N> = IndexMap<Address<N>, PrivateKey<N>>;
+
+/// The duration for which the records spent by a transaction of the wallet are reserved,
+/// unless the transaction is confirmed or the records are released first.
+pub const RESERVATION_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// The net changes of the balances of the accounts of a wallet (in microcredits), by address.
+type BalanceChanges<N> = IndexMap<Address<N>, i128>;
+
+/// A wallet, which tracks the records owned by its accounts, and whether they are spent.
+///
+/// A wallet opened from an encrypted wallet file starts locked: it tracks its records with the view keys,
+/// and only holds the private keys in memory while it is unlocked with the password.
+#[derive(Clone)]
+pub struct Wallet<N: Network> {
+    /// The view keys of the accounts, by address.
+    view_keys: Arc<RwLock<IndexMap<Address<N>, ViewKey<N>>>>,
+    /// The private keys of the accounts, by address, if the wallet is unlocked.
+    private_keys: Arc<RwLock<Option<PrivateKeyMap<N>>>>,
+    /// The addresses of the watch-only accounts, which the wallet tracks but cannot spend from.
+    watch_only: Arc<RwLock<HashSet<Address<N>>>>,
+    /// The time at which the wallet locks again, if it is unlocked with a timeout.
+    unlocked_until: Arc<RwLock<Option<Instant>>>,
+    /// The encrypted keystore of the wallet, if the wallet is opened from a wallet file.
+    keystore: Option<Arc<RwLock<Keystore<N>>>>,
+    /// The path of the wallet file, if the wallet is opened from a wallet file.
+    path: Option<PathBuf>,
+    /// The labels of the address book, by address.
+    labels: Arc<RwLock<IndexMap<Address<N>, String>>>,
+    /// The records owned by the accounts, by commitment.
+    records: Arc<RwLock<IndexMap<Field<N>, WalletRecord<N>>>>,
+    /// The commitments of the records owned by the accounts, by tag.
+    tags: Arc<RwLock<HashMap<Field<N>, Field<N>>>>,
+    /// The commitments of the records spent by the transactions of the wallet, with the time at which
+    /// their reservation expires, until the transactions are confirmed or the records are released.
+    pending: Arc<RwLock<HashMap<Field<N>, Instant>>>,
+    /// The transactions that spend or create the records of the accounts, by transaction ID.
+    history: Arc<RwLock<IndexMap<N::TransactionID, HistoryEntry<N>>>>,
+    /// The height of the latest block scanned by the wallet, if any.
+    height: Arc<RwLock<Option<u32>>>,
+    /// The progress of the rescan of the ledger, if the wallet is rescanning.
+    rescan: Arc<RwLock<Option<RescanProgress>>>,
+    /// The sender of the events of the wallet.
+    events: broadcast::Sender<WalletEvent<N>>,
+}
+
+impl<N: Network> Wallet<N> {
+    /// Initializes a new (unencrypted) wallet with the given accounts, which is always unlocked.
+    pub fn new(accounts: Vec<Account<N>>) -> Self {
+        let view_keys = accounts.iter().map(|account| (account.address(), *account.view_key())).collect();
+        let private_keys = accounts.iter().map(|account| (account.address(), *account.private_key())).collect();
+        Self::initialize(view_keys, Some(private_keys), None, None)
+    }
+
+    /// Opens the wallet from the encrypted wallet file at the given path, which is locked until it is unlocked.
+    pub fn open(path: &Path) -> Result<Self> {
+        let keystore = Keystore::load(path)?;
+        let view_keys = keystore.view_keys().collect();
+        Ok(Self::initialize(view_keys, None, Some(keystore), Some(path.to_path_buf())))
+    }
+
+    /// Initializes a new wallet with the given keys.
+    fn initialize(
+        view_keys: IndexMap<Address<N>, ViewKey<N>>,
+        private_keys: Option<PrivateKeyMap<N>>,
+        keystore: Option<Keystore<N>>,
+        path: Option<PathBuf>,
+    ) -> Self {
+        let watch_only = keystore.as_ref().map(|keystore| keystore.watch_only().collect()).unwrap_or_default();
+        let labels = keystore.as_ref().map(|keystore| keystore.labels().clone()).unwrap_or_default();
+        Self {
+            view_keys: Arc::new(RwLock::new(view_keys)),
+            private_keys: Arc::new(RwLock::new(private_keys)),
+            watch_only: Arc::new(RwLock::new(watch_only)),
+            unlocked_until: Default::default(),
+            keystore: keystore.map(|keystore| Arc::new(RwLock::new(keystore))),
+            path,
+            labels: Arc::new(RwLock::new(labels)),
+            records: Default::default(),
+            tags: Default::default(),
+            pending: Default::default(),
+            history: Default::default(),
+            height: Default::default(),
+            rescan: Default::default(),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Returns the addresses of the accounts of the wallet.
+    pub fn addresses(&self) -> Vec<Address<N>> {
+        self.view_keys.read().keys().copied().collect()
+    }
+
+    /// Returns `true` if the given record is owned by one of the accounts of the wallet,
+    /// including the watch-only accounts.
+    pub fn is_owner(&self, record: &Record<N, Ciphertext<N>>) -> bool {
+        self.view_keys.read().values().any(|view_key| record.is_owner(view_key))
+    }
+
+    /// Returns the height of the latest block scanned by the wallet, if any.
+    pub fn height(&self) -> Option<u32> {
+        *self.height.read()
+    }
+
+    /// Returns the records owned by the accounts, in the order they were found.
+    pub fn records(&self) -> Vec<WalletRecord<N>> {
+        self.records.read().values().cloned().collect()
+    }
+
+    /// Returns the unspent records owned by the accounts, in the order they were found.
+    pub fn unspent_records(&self) -> Vec<WalletRecord<N>> {
+        self.records.read().values().filter(|record| !record.is_spent()).cloned().collect()
+    }
+
+    /// Returns the balance of the wallet, as the number of microcredits in the unspent records
+    /// of its accounts, except the watch-only accounts.
+    pub fn balance(&self) -> u64 {
+        let watch_only = self.watch_only.read();
+        self.records
+            .read()
+            .values()
+            .filter(|record| !record.is_spent() && !watch_only.contains(&record.owner()))
+            .map(|record| record.microcredits())
+            .sum()
+    }
+
+    /// Returns the balance of the given address, as the number of microcredits in its unspent records.
+    pub fn balance_of(&self, address: &Address<N>) -> u64 {
+        self.records
+            .read()
+            .values()
+            .filter(|record| !record.is_spent() && record.owner() == *address)
+            .map(|record| record.microcredits())
+            .sum()
+    }
+}
+
+impl<N: Network> Wallet<N> {
+    /// Returns `true` if the wallet is locked, in which case its private keys are unavailable.
+    pub fn is_locked(&self) -> bool {
+        self.lock_if_expired();
+        self.private_keys.read().is_none()
+    }
+
+    /// Unlocks the wallet with the given password, until the optional timeout elapses.
+    pub fn unlock(&self, password: &str, timeout: Option<Duration>) -> Result<()> {
+        let keystore = match &self.keystore {
+            Some(keystore) => keystore,
+            None => bail!("The wallet is not encrypted"),
+        };
+        // Decrypt the private keys.
+        let private_keys = keystore
+            .read()
+            .decrypt(password)?
+            .into_iter()
+            .map(|private_key| Ok((Address::try_from(&private_key)?, private_key)))
+            .collect::<Result<PrivateKeyMap<N>>>()?;
+
+        let mut unlocked_until = self.unlocked_until.write();
+        *self.private_keys.write() = Some(private_keys);
+        *unlocked_until = timeout.map(|timeout| Instant::now() + timeout);
+        Ok(())
+    }
+
+    /// Locks the wallet, removing its private keys from memory.
+    pub fn lock(&self) -> Result<()> {
+        ensure!(self.keystore.is_some(), "The wallet is not encrypted");
+        let mut unlocked_until = self.unlocked_until.write();
+        *self.private_keys.write() = None;
+        *unlocked_until = None;
+        Ok(())
+    }
+
+    /// Returns the private key of the given address, if the wallet is unlocked.
+    pub fn private_key(&self, address: &Address<N>) -> Result<PrivateKey<N>> {
+        ensure!(!self.is_watch_only(address), "The address '{address}' is watch-only");
+        self.lock_if_expired();
+        match &*self.private_keys.read() {
+            Some(private_keys) => private_keys
+                .get(address)
+                .copied()
+                .ok_or_else(|| anyhow!("The address '{address}' is not in the wallet")),
+            None => bail!("The wallet is locked"),
+        }
+    }
+
+    /// Imports the given account into the wallet, and stores it in the wallet file if the wallet is encrypted,
+    /// in which case the password of the wallet is required.
+    ///
+    /// The wallet only finds the records of the account in the blocks it already scanned by rescanning them.
+    pub fn import<R: Rng + CryptoRng>(&self, account: &Account<N>, password: Option<&str>, rng: &mut R) -> Result<()> {
+        let address = account.address();
+        ensure!(!self.view_keys.read().contains_key(&address), "The address '{address}' is already in the wallet");
+
+        // Store the account in the wallet file.
+        if let (Some(keystore), Some(path)) = (&self.keystore, &self.path) {
+            let password = match password {
+                Some(password) => password,
+                None => bail!("The wallet password is required to import an account"),
+            };
+            let mut keystore = keystore.write();
+            let mut updated = keystore.clone();
+            updated.add_account(account, password, rng)?;
+            updated.save(path)?;
+            *keystore = updated;
+        }
+
+        // Add the private key if the wallet is unlocked, and the view key to track the records of the account.
+        if let Some(private_keys) = &mut *self.private_keys.write() {
+            private_keys.insert(address, *account.private_key());
+        }
+        self.view_keys.write().insert(address, *account.view_key());
+        info!("Imported '{address}' into the wallet");
+        Ok(())
+    }
+
+    /// Locks the wallet if its unlock timeout has elapsed.
+    fn lock_if_expired(&self) {
+        let mut unlocked_until = self.unlocked_until.write();
+        if unlocked_until.map_or(false, |unlocked_until| Instant::now() >= unlocked_until) {
+            *self.private_keys.write() = None;
+            *unlocked_until = None;
+            debug!("The wallet is locked, as its unlock timeout elapsed");
+        }
+    }
+}
+
+impl<N: Network> Wallet<N> {
+    /// Creates a transaction that privately transfers the given amount (in microcredits) to the recipient.
+    ///
+    /// The amount is spent from an unspent record of the wallet, chosen with the given selection strategy, and the fee
+    /// (including the priority fee) is paid from another record of the same account. The change of both records
+    /// returns to the account.
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer<C: ConsensusStorage<N>, R: Rng + CryptoRng>(
+        &self,
+        ledger: &Ledger<N, C>,
+        recipient: Address<N>,
+        amount: u64,
+        priority_fee: u64,
+        selection: RecordSelection,
+        rng: &mut R,
+    ) -> Result<Transaction<N>> {
+        ensure!(amount > 0, "The amount to transfer must be greater than zero");
+        // Select and reserve the records to spend, from the accounts the wallet can spend from.
+        let addresses = self.spendable_addresses();
+        let (input_record, fee_record) =
+            self.reserve_records(&addresses, amount, priority_fee, selection, RESERVATION_TIMEOUT, rng)?;
+
+        // Create the transaction.
+        let result = self.private_key(&input_record.owner()).and_then(|private_key| {
+            let inputs = [
+                Value::Record(input_record.record().clone()),
+                Value::from_str(&recipient.to_string())?,
+                Value::from_str(&format!("{amount}u64"))?,
+            ];
+            let fee = (fee_record.record().clone(), priority_fee);
+            ledger.vm().execute(
+                &private_key,
+                ("credits.aleo", "transfer_private"),
+                inputs.into_iter(),
+                Some(fee),
+                None,
+                rng,
+            )
+        });
+        // Release the records if the transaction could not be created.
+        if result.is_err() {
+            self.release_records(&[input_record.commitment(), fee_record.commitment()]);
+        }
+        result
+    }
+
+    /// Releases the reservation of the given records, so they can be spent by another transaction.
+    pub fn release_records(&self, commitments: &[Field<N>]) {
+        let mut pending = self.pending.write();
+        for commitment in commitments {
+            pending.remove(commitment);
+        }
+    }
+
+    /// Releases the reservation of the records spent by the given transaction, if it is not going to be confirmed
+    /// (e.g. as it was rejected by the memory pool).
+    pub fn release_transaction(&self, transaction: &Transaction<N>) {
+        let commitments = {
+            let tags = self.tags.read();
+            transaction.tags().filter_map(|tag| tags.get(tag).copied()).collect::<Vec<_>>()
+        };
+        self.release_records(&commitments);
+    }
+
+    /// Selects an unspent record of one of the given accounts for the amount, and another record of the same
+    /// account for the fee, and reserves both until the given timeout, so they are not selected again.
+    pub(crate) fn reserve_records<R: Rng>(
+        &self,
+        addresses: &[Address<N>],
+        amount: u64,
+        priority_fee: u64,
+        selection: RecordSelection,
+        timeout: Duration,
+        rng: &mut R,
+    ) -> Result<(WalletRecord<N>, WalletRecord<N>)> {
+        // Hold the locks until the records are reserved, so concurrent transfers never select the same records.
+        // Note: The records are locked before the reservations, in the same order as when a block is scanned.
+        let records = self.records.read();
+        let mut pending = self.pending.write();
+        // Remove the expired reservations.
+        let now = Instant::now();
+        pending.retain(|_, expires_at| *expires_at > now);
+
+        let candidates = records
+            .values()
+            .filter(|record| {
+                !record.is_spent() && record.microcredits() > 0 && !pending.contains_key(&record.commitment())
+            })
+            .collect::<Vec<_>>();
+
+        for address in addresses.iter().copied() {
+            let records = candidates.iter().copied().filter(|record| record.owner() == address).collect::<Vec<_>>();
+            // Select the record that covers the amount.
+            let input = selection.select(&records, amount, rng);
+            let input = match input {
+                Some(input) => input,
+                None => continue,
+            };
+            // Select the largest remaining record for the fee, as it must also cover the execution cost.
+            let fee = records
+                .iter()
+                .filter(|record| record.commitment() != input.commitment() && record.microcredits() >= priority_fee)
+                .max_by_key(|record| record.microcredits());
+            if let Some(fee) = fee {
+                pending.extend([(input.commitment(), now + timeout), (fee.commitment(), now + timeout)]);
+                return Ok((input.clone(), (*fee).clone()));
+            }
+        }
+        bail!("The wallet has no unspent records to transfer {amount} microcredits and pay the fee")
+    }
+}
+
+impl<N: Network> Wallet<N> {
+    /// Scans the given block for the records it creates for the accounts, and the records of the accounts it spends.
+    /// The block must be the next block after the latest block scanned by the wallet.
+    pub fn add_block(&self, block: &Block<N>) -> Result<()> {
+        let mut height = self.height.write();
+        // Ensure the block is the next block to scan.
+        let next_height = height.map_or(0, |height| height + 1);
+        ensure!(
+            block.height() == next_height,
+            "Expected block {next_height} in the wallet, found block {}",
+            block.height()
+        );
+
+        self.scan_block(block)?;
+
+        // Update the height of the latest block scanned by the wallet.
+        *height = Some(block.height());
+        Ok(())
+    }
+
+    /// Scans the given block for the records of the accounts, without checking its height,
+    /// and sends the resulting events to the subscribers.
+    fn scan_block(&self, block: &Block<N>) -> Result<()> {
+        let (events, changes) = self.scan_transactions(block)?;
+        self.send_events(events, changes, block.height());
+        Ok(())
+    }
+
+    /// Scans the transactions of the given block for the records of the accounts, and returns the events
+    /// of the block, with the net change (in microcredits) of the balance of each account.
+    fn scan_transactions(&self, block: &Block<N>) -> Result<(Vec<WalletEvent<N>>, BalanceChanges<N>)> {
+        let view_keys = self.view_keys.read();
+        let mut records = self.records.write();
+        let mut tags = self.tags.write();
+        let mut pending = self.pending.write();
+        let mut history = self.history.write();
+        let (mut events, mut changes) = (Vec::new(), BalanceChanges::new());
+
+        for confirmed in block.transactions().iter() {
+            let transaction = confirmed.transaction();
+            // The owners and amounts of the records spent and created by the transaction, for the history.
+            let (mut spent, mut created) = (Vec::new(), Vec::new());
+            // Mark the records spent by the transaction, as each spent record reveals its tag.
+            for tag in transaction.tags() {
+                if let Some(record) = tags.get(tag).and_then(|commitment| records.get_mut(commitment)) {
+                    // Only send an event the first time the record is spent, as a rescan scans the blocks again.
+                    if !record.is_spent() {
+                        events.push(WalletEvent::spent(record, transaction.id(), block.height()));
+                        *changes.entry(record.owner()).or_default() -= record.microcredits() as i128;
+                    }
+                    record.set_spent(block.height());
+                    pending.remove(&record.commitment());
+                    spent.push((record.owner(), record.microcredits()));
+                }
+            }
+            // Add the records created by the transaction for the accounts.
+            for (commitment, record) in transaction.records() {
+                if let Some((address, view_key)) = view_keys.iter().find(|(_, view_key)| record.is_owner(view_key)) {
+                    let record = record.decrypt(view_key)?;
+                    let tag = Record::<N, Plaintext<N>>::tag(GraphKey::try_from(view_key)?.sk_tag(), *commitment)?;
+                    tags.insert(tag, *commitment);
+                    // Keep the records found before, as a rescan scans the blocks again.
+                    let record = match records.entry(*commitment) {
+                        Entry::Occupied(entry) => entry.into_mut(),
+                        Entry::Vacant(entry) => {
+                            let record =
+                                WalletRecord::new(*address, *commitment, tag, record, transaction.id(), block.height());
+                            events.push(WalletEvent::received(&record));
+                            *changes.entry(record.owner()).or_default() += record.microcredits() as i128;
+                            entry.insert(record)
+                        }
+                    };
+                    created.push((record.owner(), record.microcredits()));
+                }
+            }
+            // Add the transaction to the history, replacing the entry found before, as a rescan may find more records.
+            if let Some(entry) = HistoryEntry::from_transaction(block, transaction, &view_keys, &spent, &created)? {
+                history.insert(transaction.id(), entry);
+            }
+        }
+        Ok((events, changes))
+    }
+
+    /// Scans the blocks in the given ledger, after the latest block scanned by the wallet.
+    pub fn sync<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>) -> Result<()> {
+        let next_height = self.height().map_or(0, |height| height + 1);
+        for height in next_height..=ledger.latest_height() {
+            self.add_block(&ledger.get_block(height)?)?;
+        }
+        Ok(())
+    }
+
+    /// Spawns a task that scans each block added to the given ledger, and locks the wallet when its timeout elapses.
+    pub fn spawn<C: 'static + ConsensusStorage<N>>(&self, ledger: Ledger<N, C>) -> JoinHandle<()> {
+        let wallet = self.clone();
+        tokio::spawn(async move {
+            loop {
+                // Scan the new blocks, without blocking the runtime.
+                let (wallet_, ledger_) = (wallet.clone(), ledger.clone());
+                match tokio::task::spawn_blocking(move || wallet_.sync(&ledger_)).await {
+                    Ok(Ok(())) => (),
+                    Ok(Err(error)) => warn!("Failed to scan the ledger for the wallet - {error}"),
+                    Err(error) => warn!("Failed to scan the ledger for the wallet - {error}"),
+                }
+                // Remove the private keys from memory once the unlock timeout elapses.
+                wallet.lock_if_expired();
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        TestRng,
+        Testnet3,
+        VM,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_lock_and_unlock() {
+        let rng = &mut TestRng::default();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+
+        // Store the account in an encrypted wallet file.
+        let path = std::env::temp_dir().join("snarkos-test-wallet-lock.json");
+        Keystore::new(core::slice::from_ref(&account), "password", rng).unwrap().save(&path).unwrap();
+
+        // Ensure the wallet starts locked, but tracks the account.
+        let wallet = Wallet::<CurrentNetwork>::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(wallet.is_locked());
+        assert_eq!(wallet.addresses(), vec![account.address()]);
+        assert!(wallet.private_key(&account.address()).is_err());
+
+        // Ensure the wallet is only unlocked with the correct password.
+        assert!(wallet.unlock("wrong password", None).is_err());
+        assert!(wallet.is_locked());
+        wallet.unlock("password", None).unwrap();
+        assert!(!wallet.is_locked());
+        assert_eq!(wallet.private_key(&account.address()).unwrap(), *account.private_key());
+
+        // Ensure the wallet locks again.
+        wallet.lock().unwrap();
+        assert!(wallet.is_locked());
+
+        // Ensure the wallet locks again once the timeout elapses.
+        wallet.unlock("password", Some(Duration::from_millis(50))).unwrap();
+        assert!(!wallet.is_locked());
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(wallet.is_locked());
+        assert!(wallet.private_key(&account.address()).is_err());
+
+        // Ensure an unencrypted wallet is always unlocked.
+        let wallet = Wallet::new(vec![account.clone()]);
+        assert!(!wallet.is_locked());
+        assert!(wallet.lock().is_err());
+        assert_eq!(wallet.private_key(&account.address()).unwrap(), *account.private_key());
+    }
+
+    #[test]
+    fn test_import() {
+        let rng = &mut TestRng::default();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let path = std::env::temp_dir().join("snarkos-test-wallet-import.json");
+        Keystore::new(core::slice::from_ref(&account), "password", rng).unwrap().save(&path).unwrap();
+        let wallet = Wallet::<CurrentNetwork>::open(&path).unwrap();
+
+        // Ensure the password is required to import an account into an encrypted wallet.
+        let other = Account::<CurrentNetwork>::new(rng).unwrap();
+        assert!(wallet.import(&other, None, rng).is_err());
+        assert!(wallet.import(&other, Some("wrong password"), rng).is_err());
+        wallet.import(&other, Some("password"), rng).unwrap();
+        assert_eq!(wallet.addresses(), vec![account.address(), other.address()]);
+        assert!(wallet.import(&other, Some("password"), rng).is_err());
+
+        // Ensure the account is stored in the wallet file.
+        let wallet = Wallet::<CurrentNetwork>::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(wallet.addresses(), vec![account.address(), other.address()]);
+        wallet.unlock("password", None).unwrap();
+        assert_eq!(wallet.private_key(&other.address()).unwrap(), *other.private_key());
+    }
+
+    #[test]
+    fn test_rescan() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the ledger, whose genesis block allocates the starting supply to the account.
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis(account.private_key(), rng).unwrap();
+        let ledger = Ledger::<CurrentNetwork, ConsensusMemory<_>>::load(genesis, None).unwrap();
+
+        // Scan the ledger with a wallet of another account.
+        let wallet = Wallet::new(vec![Account::<CurrentNetwork>::new(rng).unwrap()]);
+        wallet.sync(&ledger).unwrap();
+        assert!(wallet.records().is_empty());
+
+        // Ensure the records of an imported account are only found by a rescan.
+        wallet.import(&account, None, rng).unwrap();
+        assert!(wallet.records().is_empty());
+        wallet.rescan(&ledger, 0).unwrap();
+        assert!(wallet.balance_of(&account.address()) > 0);
+        assert_eq!(wallet.rescan_progress(), None);
+
+        // Ensure rescanning again does not duplicate the records.
+        let records = wallet.records();
+        wallet.rescan(&ledger, 0).unwrap();
+        assert_eq!(wallet.records(), records);
+
+        // Ensure the rescan only covers the blocks scanned by the wallet.
+        assert!(wallet.rescan(&ledger, 1).is_err());
+    }
+
+    #[test]
+    fn test_add_block() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the genesis block, which allocates the starting supply to the account.
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis(account.private_key(), rng).unwrap();
+
+        // Scan the genesis block.
+        let wallet = Wallet::new(vec![account.clone()]);
+        assert_eq!(wallet.height(), None);
+        wallet.add_block(&genesis).unwrap();
+        assert_eq!(wallet.height(), Some(0));
+
+        // Ensure the wallet tracks the records of the account.
+        let num_records: usize =
+            genesis.transactions().iter().map(|confirmed| confirmed.transaction().records().count()).sum();
+        assert_eq!(wallet.records().len(), num_records);
+        assert_eq!(wallet.unspent_records().len(), num_records);
+        assert!(wallet.balance() > 0);
+        assert_eq!(wallet.balance(), wallet.balance_of(&account.address()));
+
+        // Ensure the block must be the next block to scan.
+        assert!(wallet.add_block(&genesis).is_err());
+
+        // Ensure a wallet of another account does not track the records.
+        let other = Wallet::new(vec![Account::<CurrentNetwork>::new(rng).unwrap()]);
+        other.add_block(&genesis).unwrap();
+        assert!(other.records().is_empty());
+        assert_eq!(other.balance(), 0);
+    }
+
+    #[test]
+    fn test_reserve_records() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a wallet with the records of the genesis block.
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis(account.private_key(), rng).unwrap();
+        let wallet = Wallet::new(vec![account.clone()]);
+        wallet.add_block(&genesis).unwrap();
+        let addresses = [account.address()];
+        let reserve = |timeout| {
+            wallet.reserve_records(&addresses, 1, 0, RecordSelection::LargestFirst, timeout, &mut TestRng::default())
+        };
+
+        // Ensure the reserved records are not selected again.
+        let (input, fee) = reserve(RESERVATION_TIMEOUT).unwrap();
+        assert_ne!(input.commitment(), fee.commitment());
+        while let Ok((other_input, other_fee)) = reserve(RESERVATION_TIMEOUT) {
+            for commitment in [other_input.commitment(), other_fee.commitment()] {
+                assert!(![input.commitment(), fee.commitment()].contains(&commitment));
+            }
+        }
+
+        // Ensure the released records are selected again.
+        wallet.release_records(&[input.commitment(), fee.commitment()]);
+        let (released_input, released_fee) = reserve(Duration::ZERO).unwrap();
+        assert_eq!((released_input.commitment(), released_fee.commitment()), (input.commitment(), fee.commitment()));
+
+        // Ensure the records are selected again once their reservation expires.
+        assert_eq!(reserve(RESERVATION_TIMEOUT).unwrap().0.commitment(), input.commitment());
+    }
+
+    #[test]
+    fn test_reserve_records_while_scanning() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a wallet with the records of the genesis block.
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis(account.private_key(), rng).unwrap();
+        let wallet = Wallet::new(vec![account.clone()]);
+        wallet.add_block(&genesis).unwrap();
+        let addresses = [account.address()];
+
+        // Ensure the records are reserved while a block is scanned, without a deadlock.
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in 0..50 {
+                    wallet.scan_block(&genesis).unwrap();
+                }
+            });
+            for _ in 0..50 {
+                let (input, fee) = wallet
+                    .reserve_records(&addresses, 1, 0, RecordSelection::LargestFirst, RESERVATION_TIMEOUT, rng)
+                    .unwrap();
+                wallet.release_records(&[input.commitment(), fee.commitment()]);
+            }
+        });
+    }
+}
@@ -0,0 +1,373 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use snarkvm::{
+    circuit::Aleo,
+    prelude::{
+        block::{Execution, Transaction},
+        execution_cost,
+        query::Query,
+        Authorization,
+        Identifier,
+        Literal,
+        ProgramID,
+        Request,
+        ValueType,
+        VM,
+    },
+};
+
+/// The duration for which the records spent by a prepared transfer are reserved, which allows for the time
+/// to sign it offline, unless the transaction is confirmed or the records are released first.
+pub const PREPARED_RESERVATION_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// A call to a program function, to be signed by the private key of the signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct UnsignedCall<N: Network> {
+    /// The address of the account that signs the call.
+    signer: Address<N>,
+    /// The ID of the program.
+    program_id: ProgramID<N>,
+    /// The name of the function.
+    function_name: Identifier<N>,
+    /// The inputs of the function.
+    inputs: Vec<Value<N>>,
+    /// The types of the inputs of the function.
+    input_types: Vec<ValueType<N>>,
+}
+
+impl<N: Network> UnsignedCall<N> {
+    /// Initializes a new call to the given function of the loaded program, for the given signer.
+    fn new<C: ConsensusStorage<N>>(
+        vm: &VM<N, C>,
+        signer: Address<N>,
+        (program_id, function_name): (&str, &str),
+        inputs: Vec<Value<N>>,
+    ) -> Result<Self> {
+        let (program_id, function_name) = (ProgramID::from_str(program_id)?, Identifier::from_str(function_name)?);
+        let input_types = vm.process().read().get_program(program_id)?.get_function(&function_name)?.input_types();
+        Ok(Self { signer, program_id, function_name, inputs, input_types })
+    }
+
+    /// Returns the address of the account that signs the call.
+    pub const fn signer(&self) -> Address<N> {
+        self.signer
+    }
+
+    /// Returns the ID of the program.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the name of the function.
+    pub const fn function_name(&self) -> &Identifier<N> {
+        &self.function_name
+    }
+
+    /// Returns the inputs of the function.
+    pub fn inputs(&self) -> &[Value<N>] {
+        &self.inputs
+    }
+
+    /// Signs the call with the given private key.
+    fn sign<R: Rng + CryptoRng>(&self, private_key: &PrivateKey<N>, rng: &mut R) -> Result<Request<N>> {
+        let address = Address::try_from(private_key)?;
+        ensure!(address == self.signer, "The call must be signed by '{}', not '{address}'", self.signer);
+        Request::sign(private_key, self.program_id, self.function_name, self.inputs.iter(), &self.input_types, rng)
+    }
+}
+
+/// A transaction that is not complete yet, which passes between a (watch-only) online node,
+/// which prepares and proves it, and an offline machine holding the private key, which signs it.
+///
+/// A transaction is signed twice: first its execution, and then its fee, which commits to the proven execution.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "", tag = "status", rename_all = "snake_case")]
+pub enum PartialTransaction<N: Network> {
+    /// The execution awaits its signature.
+    UnsignedExecution {
+        /// The call of the execution.
+        call: UnsignedCall<N>,
+        /// The record that pays the fee.
+        fee_record: Record<N, Plaintext<N>>,
+        /// The priority fee, in microcredits.
+        priority_fee: u64,
+    },
+    /// The execution awaits its proof.
+    SignedExecution {
+        /// The signed request of the execution.
+        request: Request<N>,
+        /// The record that pays the fee.
+        fee_record: Record<N, Plaintext<N>>,
+        /// The priority fee, in microcredits.
+        priority_fee: u64,
+    },
+    /// The fee awaits its signature.
+    UnsignedFee {
+        /// The proven execution.
+        execution: Execution<N>,
+        /// The call of the fee.
+        call: UnsignedCall<N>,
+    },
+    /// The fee awaits its proof.
+    SignedFee {
+        /// The proven execution.
+        execution: Execution<N>,
+        /// The signed request of the fee.
+        request: Request<N>,
+    },
+}
+
+impl<N: Network> PartialTransaction<N> {
+    /// Returns the unsigned call, if the transaction awaits a signature.
+    pub const fn unsigned_call(&self) -> Option<&UnsignedCall<N>> {
+        match self {
+            Self::UnsignedExecution { call, .. } | Self::UnsignedFee { call, .. } => Some(call),
+            Self::SignedExecution { .. } | Self::SignedFee { .. } => None,
+        }
+    }
+
+    /// Returns the records spent by the transaction, and the tags of the records spent by its proven execution.
+    fn spent_records(&self) -> (Vec<&Record<N, Plaintext<N>>>, Vec<Field<N>>) {
+        let records_of = |inputs: &'_ [Value<N>]| -> Vec<&'_ Record<N, Plaintext<N>>> {
+            inputs
+                .iter()
+                .filter_map(|input| match input {
+                    Value::Record(record) => Some(record),
+                    _ => None,
+                })
+                .collect()
+        };
+        match self {
+            Self::UnsignedExecution { call, fee_record, .. } => {
+                let mut records = records_of(call.inputs());
+                records.push(fee_record);
+                (records, vec![])
+            }
+            Self::SignedExecution { request, fee_record, .. } => {
+                let mut records = records_of(request.inputs());
+                records.push(fee_record);
+                (records, vec![])
+            }
+            Self::UnsignedFee { execution, call } => (
+                records_of(call.inputs()),
+                execution.transitions().flat_map(|transition| transition.tags()).copied().collect(),
+            ),
+            Self::SignedFee { execution, request } => (
+                records_of(request.inputs()),
+                execution.transitions().flat_map(|transition| transition.tags()).copied().collect(),
+            ),
+        }
+    }
+
+    /// Signs the execution or the fee of the transaction with the given private key.
+    /// This does not require access to the ledger, so it can be done on an offline machine.
+    pub fn sign<R: Rng + CryptoRng>(&self, private_key: &PrivateKey<N>, rng: &mut R) -> Result<Self> {
+        match self {
+            Self::UnsignedExecution { call, fee_record, priority_fee } => Ok(Self::SignedExecution {
+                request: call.sign(private_key, rng)?,
+                fee_record: fee_record.clone(),
+                priority_fee: *priority_fee,
+            }),
+            Self::UnsignedFee { execution, call } => {
+                Ok(Self::SignedFee { execution: execution.clone(), request: call.sign(private_key, rng)? })
+            }
+            Self::SignedExecution { .. } | Self::SignedFee { .. } => {
+                bail!("The transaction awaits a proof, not a signature")
+            }
+        }
+    }
+
+    /// Proves the signed execution of the transaction, and returns the transaction with its fee to sign.
+    pub fn prove_execution<C: ConsensusStorage<N>, R: Rng + CryptoRng>(
+        &self,
+        vm: &VM<N, C>,
+        query: Query<N, C::BlockStorage>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let (request, fee_record, priority_fee) = match self {
+            Self::SignedExecution { request, fee_record, priority_fee } => (request, fee_record, *priority_fee),
+            _ => bail!("The transaction does not await the proof of its execution"),
+        };
+        // Prove the execution.
+        let authorization = Authorization::new(core::slice::from_ref(request));
+        let execution = match vm.execute_authorization(authorization, None, Some(query), rng)? {
+            Transaction::Execute(_, execution, _) => execution,
+            _ => bail!("Expected an execution transaction"),
+        };
+
+        // Prepare the call to pay the fee for the execution, which commits to the execution ID.
+        let (minimum_cost, _) = execution_cost(vm, &execution)?;
+        let fee = minimum_cost.checked_add(priority_fee).ok_or_else(|| anyhow!("The fee overflowed"))?;
+        let inputs = vec![
+            Value::Record(fee_record.clone()),
+            Value::from_str(&format!("{fee}u64"))?,
+            Value::from(Literal::Field(execution.to_execution_id()?)),
+        ];
+        let call = UnsignedCall::new(vm, *request.caller(), ("credits.aleo", "fee"), inputs)?;
+        Ok(Self::UnsignedFee { execution, call })
+    }
+
+    /// Proves the signed fee of the transaction, and returns the complete transaction.
+    pub fn prove_fee<A: Aleo<Network = N>, C: ConsensusStorage<N>, R: Rng + CryptoRng>(
+        &self,
+        vm: &VM<N, C>,
+        query: Query<N, C::BlockStorage>,
+        rng: &mut R,
+    ) -> Result<Transaction<N>> {
+        let (execution, request) = match self {
+            Self::SignedFee { execution, request } => (execution, request),
+            _ => bail!("The transaction does not await the proof of its fee"),
+        };
+        // Prove the fee.
+        let authorization = Authorization::new(core::slice::from_ref(request));
+        let (_, mut trace) = vm.process().read().execute::<A>(authorization)?;
+        trace.prepare(query)?;
+        let fee = trace.prove_fee::<A, R>(rng)?;
+        Transaction::from_execution(execution.clone(), Some(fee))
+    }
+}
+
+impl<N: Network> Wallet<N> {
+    /// Prepares a transaction that privately transfers the given amount (in microcredits) to the recipient,
+    /// to be signed offline, as in [`Wallet::transfer`]. This does not require the wallet to be unlocked,
+    /// and it also spends from the watch-only accounts, whose private keys are kept offline.
+    pub fn prepare_transfer<C: ConsensusStorage<N>, R: Rng>(
+        &self,
+        ledger: &Ledger<N, C>,
+        recipient: Address<N>,
+        amount: u64,
+        priority_fee: u64,
+        selection: RecordSelection,
+        rng: &mut R,
+    ) -> Result<PartialTransaction<N>> {
+        ensure!(amount > 0, "The amount to transfer must be greater than zero");
+        let recipient = Value::from_str(&recipient.to_string())?;
+        let amount_input = Value::from_str(&format!("{amount}u64"))?;
+        // Select the records to spend, including from the watch-only accounts, as the transaction is signed offline.
+        let (input_record, fee_record) = self.reserve_records(
+            &self.addresses(),
+            amount,
+            priority_fee,
+            selection,
+            PREPARED_RESERVATION_TIMEOUT,
+            rng,
+        )?;
+        let inputs = vec![Value::Record(input_record.record().clone()), recipient, amount_input];
+        let call =
+            match UnsignedCall::new(ledger.vm(), input_record.owner(), ("credits.aleo", "transfer_private"), inputs) {
+                Ok(call) => call,
+                // Release the records if the transfer could not be prepared.
+                Err(error) => {
+                    self.release_records(&[input_record.commitment(), fee_record.commitment()]);
+                    return Err(error);
+                }
+            };
+
+        Ok(PartialTransaction::UnsignedExecution { call, fee_record: fee_record.record().clone(), priority_fee })
+    }
+
+    /// Releases the reservation of the records spent by the given prepared transfer,
+    /// if it is abandoned before it is broadcast.
+    pub fn release_prepared_transfer(&self, partial: &PartialTransaction<N>) {
+        let (spent_records, spent_tags) = partial.spent_records();
+        let commitments = {
+            let (records, tags) = (self.records.read(), self.tags.read());
+            records
+                .values()
+                .filter(|record| spent_records.contains(&record.record()))
+                .map(|record| record.commitment())
+                .chain(spent_tags.iter().filter_map(|tag| tags.get(tag).copied()))
+                .collect::<Vec<_>>()
+        };
+        self.release_records(&commitments);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        TestRng,
+        Testnet3,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_sign() {
+        let rng = &mut TestRng::default();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let recipient = Account::<CurrentNetwork>::new(rng).unwrap();
+
+        // Prepare an unsigned transfer.
+        let inputs = vec![Value::from_str(&recipient.address().to_string()).unwrap(), Value::from_str("1u64").unwrap()];
+        let call = UnsignedCall::new(&vm, account.address(), ("credits.aleo", "transfer_public"), inputs).unwrap();
+        let fee_record = Record::from_str(&format!(
+            "{{ owner: {}.private, microcredits: 100u64.private, _nonce: 0group.public }}",
+            account.address()
+        ))
+        .unwrap();
+        let partial = PartialTransaction::UnsignedExecution { call: call.clone(), fee_record, priority_fee: 0 };
+
+        // Ensure the partial transaction round-trips through JSON.
+        let json = serde_json::to_string(&partial).unwrap();
+        assert_eq!(serde_json::from_str::<PartialTransaction<CurrentNetwork>>(&json).unwrap(), partial);
+        assert_eq!(partial.unsigned_call(), Some(&call));
+
+        // Ensure the execution is only signed by the private key of the signer.
+        assert!(partial.sign(recipient.private_key(), rng).is_err());
+        let signed = partial.sign(account.private_key(), rng).unwrap();
+        match &signed {
+            PartialTransaction::SignedExecution { request, .. } => {
+                assert_eq!(*request.caller(), account.address());
+                assert_eq!(request.inputs(), call.inputs());
+            }
+            _ => panic!("Expected a signed execution"),
+        }
+
+        // Ensure a signed transaction is not signed again.
+        assert!(signed.unsigned_call().is_none());
+        assert!(signed.sign(account.private_key(), rng).is_err());
+    }
+
+    #[test]
+    fn test_release_prepared_transfer() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the ledger, whose genesis block allocates the starting supply to the account.
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis(account.private_key(), rng).unwrap();
+        let ledger = Ledger::<CurrentNetwork, ConsensusMemory<_>>::load(genesis, None).unwrap();
+        let wallet = Wallet::new(vec![account.clone()]);
+        wallet.sync(&ledger).unwrap();
+        let prepare = |rng: &mut TestRng| {
+            wallet.prepare_transfer(&ledger, account.address(), 1, 0, RecordSelection::LargestFirst, rng)
+        };
+
+        // Ensure the records of a prepared transfer are reserved, until it is released.
+        let partial = prepare(rng).unwrap();
+        assert_ne!(prepare(rng).unwrap(), partial);
+        wallet.release_prepared_transfer(&partial);
+        let prepared = prepare(rng).unwrap();
+        assert_eq!(prepared.spent_records(), partial.spent_records());
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{Address, Entry, Field, Identifier, Literal, Network, Plaintext, Record};
+
+use core::str::FromStr;
+
+/// A record owned by an account of the wallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalletRecord<N: Network> {
+    /// The address of the account that owns the record.
+    owner: Address<N>,
+    /// The commitment of the record.
+    commitment: Field<N>,
+    /// The tag of the record, which is revealed when the record is spent.
+    tag: Field<N>,
+    /// The decrypted record.
+    record: Record<N, Plaintext<N>>,
+    /// The ID of the transaction that created the record.
+    transaction_id: N::TransactionID,
+    /// The height of the block that created the record.
+    height: u32,
+    /// The height of the block that spent the record, if it is spent.
+    spent_height: Option<u32>,
+}
+
+impl<N: Network> WalletRecord<N> {
+    /// Initializes a new unspent wallet record.
+    pub(crate) const fn new(
+        owner: Address<N>,
+        commitment: Field<N>,
+        tag: Field<N>,
+        record: Record<N, Plaintext<N>>,
+        transaction_id: N::TransactionID,
+        height: u32,
+    ) -> Self {
+        Self { owner, commitment, tag, record, transaction_id, height, spent_height: None }
+    }
+
+    /// Returns the address of the account that owns the record.
+    pub const fn owner(&self) -> Address<N> {
+        self.owner
+    }
+
+    /// Returns the commitment of the record.
+    pub const fn commitment(&self) -> Field<N> {
+        self.commitment
+    }
+
+    /// Returns the tag of the record.
+    pub const fn tag(&self) -> Field<N> {
+        self.tag
+    }
+
+    /// Returns the decrypted record.
+    pub const fn record(&self) -> &Record<N, Plaintext<N>> {
+        &self.record
+    }
+
+    /// Returns the ID of the transaction that created the record.
+    pub const fn transaction_id(&self) -> N::TransactionID {
+        self.transaction_id
+    }
+
+    /// Returns the height of the block that created the record.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the height of the block that spent the record, if it is spent.
+    pub const fn spent_height(&self) -> Option<u32> {
+        self.spent_height
+    }
+
+    /// Returns `true` if the record is spent.
+    pub const fn is_spent(&self) -> bool {
+        self.spent_height.is_some()
+    }
+
+    /// Returns the number of microcredits in the record, or `0` if it is not a credits record.
+    pub fn microcredits(&self) -> u64 {
+        let microcredits = match Identifier::from_str("microcredits") {
+            Ok(microcredits) => microcredits,
+            Err(_) => return 0,
+        };
+        match self.record.data().get(&microcredits) {
+            Some(Entry::Private(Plaintext::Literal(Literal::U64(amount), _))) => **amount,
+            _ => 0,
+        }
+    }
+
+    /// Marks the record as spent in the block at the given height.
+    pub(crate) fn set_spent(&mut self, height: u32) {
+        self.spent_height = Some(height);
+    }
+}
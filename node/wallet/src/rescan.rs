@@ -0,0 +1,122 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde::Serialize;
+
+/// The number of blocks between the progress logs of a rescan.
+const RESCAN_LOG_INTERVAL: u32 = 1000;
+
+/// The progress of a rescan of the ledger by the wallet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RescanProgress {
+    /// The height of the first block of the rescan.
+    start_height: u32,
+    /// The height of the latest block rescanned, if any.
+    height: Option<u32>,
+    /// The height of the latest block scanned by the wallet, at which the rescan ends.
+    end_height: u32,
+}
+
+impl RescanProgress {
+    /// Returns the height of the first block of the rescan.
+    pub const fn start_height(&self) -> u32 {
+        self.start_height
+    }
+
+    /// Returns the height of the latest block rescanned, if any.
+    pub const fn height(&self) -> Option<u32> {
+        self.height
+    }
+
+    /// Returns the height of the latest block scanned by the wallet, at which the rescan ends.
+    pub const fn end_height(&self) -> u32 {
+        self.end_height
+    }
+}
+
+impl<N: Network> Wallet<N> {
+    /// Returns the progress of the rescan of the ledger, if the wallet is rescanning.
+    pub fn rescan_progress(&self) -> Option<RescanProgress> {
+        *self.rescan.read()
+    }
+
+    /// Scans the blocks in the given ledger again, from the given height up to the latest block scanned by the wallet,
+    /// to find the records of the accounts imported after the blocks were first scanned.
+    pub fn rescan<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>, from_height: u32) -> Result<()> {
+        self.start_rescan(from_height)?;
+        let result = self.rescan_blocks(ledger, from_height);
+        *self.rescan.write() = None;
+        result
+    }
+
+    /// Spawns a task that rescans the blocks in the given ledger from the given height, as in [`Wallet::rescan`].
+    /// The progress of the rescan is available from [`Wallet::rescan_progress`] until it completes.
+    pub fn spawn_rescan<C: 'static + ConsensusStorage<N>>(
+        &self,
+        ledger: Ledger<N, C>,
+        from_height: u32,
+    ) -> Result<JoinHandle<()>> {
+        self.start_rescan(from_height)?;
+        let wallet = self.clone();
+        Ok(tokio::task::spawn_blocking(move || {
+            if let Err(error) = wallet.rescan_blocks(&ledger, from_height) {
+                warn!("Failed to rescan the ledger for the wallet - {error}");
+            }
+            *wallet.rescan.write() = None;
+        }))
+    }
+
+    /// Starts tracking the progress of a rescan from the given height.
+    fn start_rescan(&self, from_height: u32) -> Result<()> {
+        let height = self.height.read();
+        let mut rescan = self.rescan.write();
+        ensure!(rescan.is_none(), "The wallet is already rescanning the ledger");
+        match *height {
+            Some(end_height) if from_height <= end_height => {
+                *rescan = Some(RescanProgress { start_height: from_height, height: None, end_height });
+                Ok(())
+            }
+            _ => bail!("The wallet has not scanned block {from_height} yet"),
+        }
+    }
+
+    /// Rescans the blocks in the given ledger from the given height, until it reaches the latest block scanned by the wallet.
+    fn rescan_blocks<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>, from_height: u32) -> Result<()> {
+        let mut next_height = from_height;
+        loop {
+            // Hold the height of the wallet, so the new blocks are scanned after the rescanned blocks.
+            let height = self.height.read();
+            let end_height = match *height {
+                Some(end_height) if next_height <= end_height => end_height,
+                _ => break,
+            };
+            self.scan_block(&ledger.get_block(next_height)?)?;
+            drop(height);
+
+            // Update the progress of the rescan.
+            if let Some(rescan) = &mut *self.rescan.write() {
+                rescan.height = Some(next_height);
+                rescan.end_height = end_height;
+            }
+            if (next_height - from_height) % RESCAN_LOG_INTERVAL == 0 {
+                info!("Rescanning the ledger for the wallet (block {next_height} of {end_height})");
+            }
+            next_height += 1;
+        }
+        info!("Rescanned blocks {from_height} to {} for the wallet", next_height.saturating_sub(1));
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+
+/// The strategy to select the record that a transfer spends, among the unspent records of an account.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordSelection {
+    /// Spends the largest record, which consolidates the balance into fewer records.
+    LargestFirst,
+    /// Spends the record that leaves the least change, which avoids splitting off dust records.
+    /// As a transfer spends a single record, the branch-and-bound search reduces to the smallest record
+    /// that covers the amount, with an exact match ending the search.
+    #[default]
+    BranchAndBound,
+    /// Spends a random record that covers the amount, so the choice does not reveal the other records of the account.
+    Random,
+}
+
+impl RecordSelection {
+    /// Selects a record that covers the given amount (in microcredits) among the given records, if there is one.
+    pub fn select<'a, N: Network, R: Rng>(
+        &self,
+        records: &[&'a WalletRecord<N>],
+        amount: u64,
+        rng: &mut R,
+    ) -> Option<&'a WalletRecord<N>> {
+        let covering = records.iter().copied().filter(|record| record.microcredits() >= amount);
+        match self {
+            Self::LargestFirst => covering.max_by_key(|record| record.microcredits()),
+            Self::BranchAndBound => {
+                let mut best: Option<&WalletRecord<N>> = None;
+                for record in covering {
+                    // Keep the record if it leaves less change than the best record so far.
+                    if best.map_or(true, |best| record.microcredits() < best.microcredits()) {
+                        best = Some(record);
+                    }
+                    // An exact match leaves no change, so it cannot be improved.
+                    if record.microcredits() == amount {
+                        break;
+                    }
+                }
+                best
+            }
+            Self::Random => {
+                let covering = covering.collect::<Vec<_>>();
+                match covering.is_empty() {
+                    true => None,
+                    false => Some(covering[rng.gen_range(0..covering.len())]),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for RecordSelection {
+    type Err = anyhow::Error;
+
+    /// Parses the name of a record selection strategy.
+    fn from_str(selection: &str) -> Result<Self> {
+        match selection {
+            "largest_first" => Ok(Self::LargestFirst),
+            "branch_and_bound" => Ok(Self::BranchAndBound),
+            "random" => Ok(Self::Random),
+            _ => bail!("Unknown record selection '{selection}' (expected largest_first, branch_and_bound, or random)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{TestRng, Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns a new wallet record with the given number of microcredits.
+    fn sample_record(address: Address<CurrentNetwork>, microcredits: u64) -> WalletRecord<CurrentNetwork> {
+        let record = Record::from_str(&format!(
+            "{{ owner: {address}.private, microcredits: {microcredits}u64.private, _nonce: 0group.public }}"
+        ))
+        .unwrap();
+        let commitment = Field::from_u64(microcredits);
+        WalletRecord::new(address, commitment, commitment, record, Default::default(), 0)
+    }
+
+    #[test]
+    fn test_select() {
+        let rng = &mut TestRng::default();
+        let address = Account::<CurrentNetwork>::new(rng).unwrap().address();
+        let records = [5, 20, 10, 50].map(|microcredits| sample_record(address, microcredits));
+        let records = records.iter().collect::<Vec<_>>();
+        let select = |selection: RecordSelection, amount, rng: &mut TestRng| {
+            selection.select(&records, amount, rng).map(|record| record.microcredits())
+        };
+
+        // Ensure each strategy only selects a record that covers the amount.
+        assert_eq!(select(RecordSelection::LargestFirst, 8, rng), Some(50));
+        assert_eq!(select(RecordSelection::BranchAndBound, 8, rng), Some(10));
+        assert_eq!(select(RecordSelection::BranchAndBound, 20, rng), Some(20));
+        for _ in 0..10 {
+            assert!([10, 20, 50].contains(&select(RecordSelection::Random, 8, rng).unwrap()));
+        }
+        for selection in [RecordSelection::LargestFirst, RecordSelection::BranchAndBound, RecordSelection::Random] {
+            assert_eq!(select(selection, 51, rng), None);
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(RecordSelection::from_str("largest_first").unwrap(), RecordSelection::LargestFirst);
+        assert_eq!(RecordSelection::from_str("branch_and_bound").unwrap(), RecordSelection::BranchAndBound);
+        assert_eq!(RecordSelection::from_str("random").unwrap(), RecordSelection::Random);
+        assert!(RecordSelection::from_str("smallest_first").is_err());
+    }
+}
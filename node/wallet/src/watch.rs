@@ -0,0 +1,102 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> Wallet<N> {
+    /// Returns `true` if the given address is a watch-only account of the wallet.
+    pub fn is_watch_only(&self, address: &Address<N>) -> bool {
+        self.watch_only.read().contains(address)
+    }
+
+    /// Returns the addresses of the watch-only accounts of the wallet.
+    pub fn watch_only_addresses(&self) -> Vec<Address<N>> {
+        let watch_only = self.watch_only.read();
+        self.addresses().into_iter().filter(|address| watch_only.contains(address)).collect()
+    }
+
+    /// Returns the addresses of the accounts of the wallet, except the watch-only accounts.
+    pub fn spendable_addresses(&self) -> Vec<Address<N>> {
+        let watch_only = self.watch_only.read();
+        self.addresses().into_iter().filter(|address| !watch_only.contains(address)).collect()
+    }
+
+    /// Returns the balance of the watch-only accounts, as the number of microcredits in their unspent records.
+    pub fn watch_only_balance(&self) -> u64 {
+        let watch_only = self.watch_only.read();
+        self.records
+            .read()
+            .values()
+            .filter(|record| !record.is_spent() && watch_only.contains(&record.owner()))
+            .map(|record| record.microcredits())
+            .sum()
+    }
+
+    /// Adds a watch-only account for the given view key to the wallet, and stores it in the wallet file
+    /// if the wallet is encrypted. The wallet tracks the records of the account, but cannot spend them.
+    ///
+    /// The wallet only finds the records of the account in the blocks it already scanned by rescanning them.
+    pub fn watch(&self, view_key: ViewKey<N>) -> Result<Address<N>> {
+        let address = Address::try_from(&view_key)?;
+        ensure!(!self.view_keys.read().contains_key(&address), "The address '{address}' is already in the wallet");
+
+        // Store the account in the wallet file, which does not require the password.
+        if let (Some(keystore), Some(path)) = (&self.keystore, &self.path) {
+            let mut keystore = keystore.write();
+            let mut updated = keystore.clone();
+            updated.add_watch_only(view_key)?;
+            updated.save(path)?;
+            *keystore = updated;
+        }
+
+        self.watch_only.write().insert(address);
+        self.view_keys.write().insert(address, view_key);
+        info!("Watching '{address}' in the wallet");
+        Ok(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{TestRng, Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_watch() {
+        let rng = &mut TestRng::default();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let watched = Account::<CurrentNetwork>::new(rng).unwrap();
+        let path = std::env::temp_dir().join("snarkos-test-wallet-watch.json");
+        Keystore::new(core::slice::from_ref(&account), "password", rng).unwrap().save(&path).unwrap();
+        let wallet = Wallet::<CurrentNetwork>::open(&path).unwrap();
+
+        // Ensure a watch-only account is added while the wallet is locked, and only once.
+        assert_eq!(wallet.watch(*watched.view_key()).unwrap(), watched.address());
+        assert!(wallet.watch(*watched.view_key()).is_err());
+        assert!(wallet.is_watch_only(&watched.address()));
+        assert_eq!(wallet.watch_only_addresses(), vec![watched.address()]);
+        assert_eq!(wallet.spendable_addresses(), vec![account.address()]);
+
+        // Ensure the watch-only account is stored in the wallet file, and never has a private key.
+        let wallet = Wallet::<CurrentNetwork>::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(wallet.addresses(), vec![account.address(), watched.address()]);
+        assert!(wallet.is_watch_only(&watched.address()));
+        wallet.unlock("password", None).unwrap();
+        assert_eq!(wallet.private_key(&account.address()).unwrap(), *account.private_key());
+        assert!(wallet.private_key(&watched.address()).is_err());
+    }
+}
@@ -15,7 +15,7 @@
 use snarkos_cli::{commands::CLI, helpers::Updater};
 use snarkos_node_env::EnvInfo;
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 #[cfg(feature = "jemalloc")]
 use tikv_jemallocator::Jemalloc;
 
@@ -23,16 +23,23 @@ use tikv_jemallocator::Jemalloc;
 #[global_allocator]
 static GLOBAL: Jemalloc = Jemalloc;
 
+/// The feature flags that snarkOS was built with.
+const FEATURES: &[&str] = &[
+    #[cfg(feature = "jemalloc")]
+    "jemalloc",
+];
+
 fn main() -> anyhow::Result<()> {
     // Register the environment information.
-    EnvInfo::register();
+    EnvInfo::register(FEATURES);
 
-    // Parse the given arguments.
-    let cli = CLI::parse();
+    // Parse the given arguments, and keep their matches, which tell how each setting was given.
+    let matches = CLI::command().get_matches();
+    let cli = CLI::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
     // Run the updater.
     println!("{}", Updater::print_cli());
     // Run the CLI.
-    match cli.command.parse() {
+    match cli.command.parse(&matches) {
         Ok(output) => println!("{output}\n"),
         Err(error) => println!("⚠️  {error}\n"),
     }